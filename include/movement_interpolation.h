@@ -0,0 +1,37 @@
+#ifndef MOVEMENT_INTERPOLATION_H
+#define MOVEMENT_INTERPOLATION_H
+
+#include <stdbool.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct MiInterpolator MiInterpolator;
+
+typedef struct MiPose {
+    float position[3];
+    /* w, x, y, z */
+    float quaternion[4];
+    /* radians */
+    float euler[3];
+    /* column-major 4x4 */
+    float quaternion_frame[16];
+    float euler_frame[16];
+} MiPose;
+
+/* Failures, including internal panics, are reported as null or false. */
+
+/* json: AnimationData document, missing fields take the visualizer defaults. */
+MiInterpolator *mi_interpolator_from_json(const char *json, bool use_euler_input);
+void mi_interpolator_free(MiInterpolator *interpolator);
+
+bool mi_interpolator_sample(const MiInterpolator *interpolator, float t, MiPose *out);
+bool mi_interpolator_frames(const MiInterpolator *interpolator, uint16_t count, MiPose *out);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
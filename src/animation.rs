@@ -1,17 +1,71 @@
+use std::cell::OnceCell;
 use std::f32::consts::PI;
+use std::sync::Arc;
+use std::time::Instant;
 
 use derive_builder::Builder;
 use derive_getters::Getters;
 use derive_new::new;
-use egui::emath::normalized_angle;
-use nalgebra::{Matrix4, Quaternion, Rotation3, UnitQuaternion, Vector3};
+use nalgebra::{
+    Matrix3, Matrix4, Quaternion, Translation3, UnitDualQuaternion, UnitQuaternion, Vector3,
+};
 
-use crate::animation_data::QuaternionInterpolationType;
+use crate::animation_data::{
+    CubicBezier, Easing, EulerOrder, EulerRepresentation, FrameSpacing, NumericalPolicy,
+    PositionInterpolationType, TimeAccumulation, EULER_METHOD,
+};
+use crate::rotation_interpolator::{get_rotation_interpolator, RotationInterpolator};
+use crate::slow_motion::SlowMotionProfile;
+
+pub const MAX_FRAMES_COUNT: u32 = 10_000;
+pub const QUATERNION_METHOD_INDEX: usize = 0;
+pub const EULER_METHOD_INDEX: usize = 1;
+pub const FIXED_TIMESTEP: f64 = 1f64 / 240f64;
+
+const MAX_ACCUMULATED_TIME: f64 = 0.25;
+const TIMESTEP_EPSILON: f64 = 1e-9;
+
+const FRAME_ROTATION_MAX_ITERATIONS: usize = 100;
+const ARC_LENGTH_SAMPLES_COUNT: u16 = 1000;
 
 pub trait Animation {
-    fn get_quaternion_frames(&self) -> Vec<Matrix4<f32>>;
-    fn get_euler_frames(&self) -> Vec<Matrix4<f32>>;
+    fn get_methods(&self) -> &[String];
+    fn get_frames(&self, method: usize) -> &[Matrix4<f32>];
+    fn get_path(&self, method: usize, samples_count: u16) -> Vec<Vector3<f32>>;
+    fn get_sample(&self, x: f32) -> AnimationSample;
     fn make_step(&mut self, time_elapsed: f64);
+    fn seek(&mut self, x: f32);
+    fn set_playback_speed(&mut self, speed: f64);
+    fn set_paused(&mut self, paused: bool);
+    fn get_progress(&self) -> Option<f32>;
+    fn get_time_drift(&self) -> Option<f64>;
+}
+
+#[derive(Debug, Clone, Getters)]
+pub struct AnimationSample {
+    position: Vector3<f32>,
+    quaternion: UnitQuaternion<f32>,
+    euler: Vector3<f32>,
+    quaternion_frame: Matrix4<f32>,
+    euler_frame: Matrix4<f32>,
+}
+
+impl AnimationSample {
+    pub fn is_finite(&self) -> bool {
+        self.position.iter().all(|v| v.is_finite())
+            && self.quaternion.coords.iter().all(|v| v.is_finite())
+            && self.euler.iter().all(|v| v.is_finite())
+            && self.quaternion_frame.iter().all(|v| v.is_finite())
+            && self.euler_frame.iter().all(|v| v.is_finite())
+    }
+
+    pub fn get_method_frame(&self, method: &str) -> Matrix4<f32> {
+        if method == EULER_METHOD {
+            self.euler_frame
+        } else {
+            self.quaternion_frame
+        }
+    }
 }
 
 #[derive(Debug, Clone, new)]
@@ -20,176 +74,481 @@ pub enum AnimationAngle {
     Euler(Vector3<f32>),
 }
 
-#[derive(Debug, Clone, Getters, new, Builder)]
-#[builder(build_fn(validate = "Self::validate"))]
-pub struct DiscreteFrameAnimation {
+#[derive(Debug, Clone, Getters, Builder)]
+pub struct Interpolator {
     begin_position: Vector3<f32>,
     end_position: Vector3<f32>,
-    frames_count: u8,
     begin_angle: AnimationAngle,
     end_angle: AnimationAngle,
-    quaternion_interpolation_type: QuaternionInterpolationType,
+    rotation_interpolator: Arc<dyn RotationInterpolator>,
+    #[builder(default = "true")]
+    normalize_quaternions: bool,
+    #[builder(default)]
+    begin_euler_representation: EulerRepresentation,
+    #[builder(default)]
+    end_euler_representation: EulerRepresentation,
+    #[builder(default)]
+    waypoints: Vec<Vector3<f32>>,
+    #[builder(default)]
+    position_interpolation_type: PositionInterpolationType,
+    #[builder(default)]
+    catmull_rom_tension: f32,
+    #[builder(default)]
+    tcb_parameters: Vec<(f32, f32, f32)>,
+    #[builder(default)]
+    begin_velocity: Vector3<f32>,
+    #[builder(default)]
+    end_velocity: Vector3<f32>,
+    #[builder(default)]
+    translation_easing: Easing,
+    #[builder(default)]
+    rotation_easing: Easing,
+    #[builder(default)]
+    euler_order: EulerOrder,
+    #[builder(default)]
+    numerical_policy: NumericalPolicy,
+}
+
+#[derive(Debug, Clone, Getters, new, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct DiscreteFrameAnimation {
+    interpolator: Interpolator,
+    frames_count: u32,
+    #[builder(default)]
+    frame_spacing: FrameSpacing,
+    #[builder(default = "self.get_default_methods()?")]
+    methods: Vec<String>,
 
     #[builder(setter(skip))]
-    quaternion_frames: Option<Vec<Matrix4<f32>>>,
+    #[getter(skip)]
+    method_interpolators: OnceCell<Vec<Interpolator>>,
     #[builder(setter(skip))]
-    euler_frames: Option<Vec<Matrix4<f32>>>,
+    frames: Option<Vec<Vec<Matrix4<f32>>>>,
 }
 
-#[derive(Debug, Clone, Getters, new, Builder)]
+#[derive(Debug, Clone, Getters, Builder)]
 pub struct ContinuousAnimation {
-    begin_position: Vector3<f32>,
-    end_position: Vector3<f32>,
+    interpolator: Interpolator,
     animation_time: f64,
-    begin_angle: AnimationAngle,
-    end_angle: AnimationAngle,
-    quaternion_interpolation_type: QuaternionInterpolationType,
+    #[builder(default)]
+    time_remap: Option<CubicBezier>,
+    #[builder(default)]
+    time_accumulation: TimeAccumulation,
+    #[builder(default)]
+    slow_motion: Option<SlowMotionProfile>,
+    #[builder(default = "1f64")]
+    playback_speed: f64,
+    #[builder(default = "self.get_default_methods()?")]
+    methods: Vec<String>,
 
     #[builder(setter(skip))]
     time_elapsed: f64,
+    #[builder(setter(skip))]
+    summed_time_elapsed: f64,
+    #[builder(setter(skip))]
+    time_drift: f64,
+    #[builder(setter(skip))]
+    time_offset: f64,
+    #[builder(setter(skip), default = "Some(Instant::now())")]
+    start_time: Option<Instant>,
+    #[builder(setter(skip))]
+    #[getter(skip)]
+    method_interpolators: OnceCell<Vec<Interpolator>>,
+    #[builder(setter(skip))]
+    #[getter(skip)]
+    frames: OnceCell<Vec<Matrix4<f32>>>,
+}
+
+impl Interpolator {
+    pub fn get_sample(&self, x: f32) -> AnimationSample {
+        self.get_sample_with_angles(&self.get_normalized_angles(), x)
+    }
+
+    pub fn get_samples(&self, samples_count: u16) -> Vec<AnimationSample> {
+        self.get_samples_at(
+            &(0..samples_count)
+                .map(|s| s as f32 / (samples_count.max(2) - 1) as f32)
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    pub fn get_samples_at(&self, xs: &[f32]) -> Vec<AnimationSample> {
+        let angles = self.get_normalized_angles();
+
+        xs.iter()
+            .map(|x| self.get_sample_with_angles(&angles, *x))
+            .collect()
+    }
+
+    pub fn with_method(&self, method: &str) -> Interpolator {
+        match get_rotation_interpolator(method) {
+            Some(rotation_interpolator) => Interpolator {
+                rotation_interpolator,
+                ..self.clone()
+            },
+            None => self.clone(),
+        }
+    }
+
+    pub fn get_path(&self, samples_count: u16) -> Vec<Vector3<f32>> {
+        self.get_samples(samples_count)
+            .into_iter()
+            .map(|s| s.position)
+            .collect()
+    }
+
+    fn get_sample_with_angles(
+        &self,
+        angles: &(
+            UnitQuaternion<f32>,
+            Vector3<f32>,
+            UnitQuaternion<f32>,
+            Vector3<f32>,
+        ),
+        x: f32,
+    ) -> AnimationSample {
+        let (begin_quaternion, begin_euler, end_quaternion, end_euler) = angles;
+        let translation_x = get_eased(&self.translation_easing, x);
+        let screw_position = self.rotation_interpolator.interpolate_position(
+            &self.begin_position,
+            begin_quaternion,
+            &self.end_position,
+            end_quaternion,
+            translation_x,
+            &self.numerical_policy,
+        );
+        let rotation_x = if screw_position.is_some() {
+            translation_x
+        } else {
+            get_eased(&self.rotation_easing, x)
+        };
+
+        let position = screw_position.unwrap_or_else(|| {
+            get_positions_interpolation(
+                &[
+                    vec![self.begin_position],
+                    self.waypoints.clone(),
+                    vec![self.end_position],
+                ]
+                .concat(),
+                &self.position_interpolation_type,
+                self.catmull_rom_tension,
+                &self.tcb_parameters,
+                &[self.begin_velocity, self.end_velocity],
+                translation_x,
+            )
+        });
+        let quaternion = get_quaternions_interpolation(
+            begin_quaternion,
+            end_quaternion,
+            rotation_x,
+            self.rotation_interpolator.as_ref(),
+            self.normalize_quaternions,
+            &self.numerical_policy,
+        );
+        let euler = (1f32 - rotation_x) * begin_euler + rotation_x * end_euler;
+
+        AnimationSample {
+            position,
+            quaternion,
+            euler,
+            quaternion_frame: Matrix4::new_translation(&position)
+                * quaternion.to_rotation_matrix().to_homogeneous(),
+            euler_frame: Matrix4::new_translation(&position)
+                * self.euler_order.get_quaternion(&euler).to_homogeneous(),
+        }
+    }
+
+    fn get_normalized_angles(
+        &self,
+    ) -> (
+        UnitQuaternion<f32>,
+        Vector3<f32>,
+        UnitQuaternion<f32>,
+        Vector3<f32>,
+    ) {
+        AnimationAngle::get_normalized_angles(
+            &self.begin_angle,
+            &self.end_angle,
+            self.normalize_quaternions,
+            &self.begin_euler_representation,
+            &self.end_euler_representation,
+            &self.euler_order,
+            &self.numerical_policy,
+        )
+    }
+}
+
+impl DiscreteFrameAnimation {
+    fn get_method_interpolators(&self) -> &[Interpolator] {
+        self.method_interpolators
+            .get_or_init(|| get_method_interpolators(&self.interpolator, &self.methods))
+    }
+
+    fn get_frame_parameters(&self) -> Vec<f32> {
+        let last = (self.frames_count - 1) as f32;
+        let uniform = (0..self.frames_count).map(|i| i as f32 / last);
+        match self.frame_spacing {
+            FrameSpacing::Uniform => uniform.collect(),
+            FrameSpacing::ArcLength => {
+                let arc_lengths =
+                    get_arc_lengths(&self.interpolator.get_path(ARC_LENGTH_SAMPLES_COUNT));
+                let length = *arc_lengths.last().unwrap();
+                if length > 0f32 {
+                    uniform
+                        .map(|x| get_parameter_at_arc_length(&arc_lengths, x * length))
+                        .collect()
+                } else {
+                    uniform.collect()
+                }
+            }
+        }
+    }
 }
 
 impl Animation for DiscreteFrameAnimation {
-    fn get_quaternion_frames(&self) -> Vec<Matrix4<f32>> {
-        self.quaternion_frames.clone().unwrap()
+    fn get_methods(&self) -> &[String] {
+        &self.methods
+    }
+
+    fn get_frames(&self, method: usize) -> &[Matrix4<f32>] {
+        self.frames
+            .as_ref()
+            .and_then(|frames| frames.get(method))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
     }
 
-    fn get_euler_frames(&self) -> Vec<Matrix4<f32>> {
-        self.euler_frames.clone().unwrap()
+    fn get_path(&self, method: usize, samples_count: u16) -> Vec<Vector3<f32>> {
+        self.get_method_interpolators()
+            .get(method)
+            .map(|i| i.get_path(samples_count))
+            .unwrap_or_default()
+    }
+
+    fn get_sample(&self, x: f32) -> AnimationSample {
+        self.interpolator.get_sample(x)
     }
 
     fn make_step(&mut self, _time_elapsed: f64) {
-        if self.euler_frames.is_some() {
+        if self.frames.is_some() {
             return;
         }
 
-        let (begin_quaternion, begin_euler, end_quaternion, end_euler) =
-            AnimationAngle::get_normalized_angles(&self.begin_angle, &self.end_angle);
-
-        self.quaternion_frames = Some(
-            (0..self.frames_count)
-                .map(|f| {
-                    let x = f as f32 / (self.frames_count - 1) as f32;
-                    let t = (1f32 - x) * self.begin_position + x * self.end_position;
-                    let r = get_quaternions_interpolation(
-                        &begin_quaternion,
-                        &end_quaternion,
-                        x,
-                        &self.quaternion_interpolation_type,
-                    );
-                    Matrix4::new_translation(&t) * r.to_rotation_matrix().to_homogeneous()
-                })
-                .collect(),
-        );
+        let xs = self.get_frame_parameters();
+        let frames = self
+            .get_method_interpolators()
+            .iter()
+            .zip(&self.methods)
+            .map(|(interpolator, method)| {
+                interpolator
+                    .get_samples_at(&xs)
+                    .iter()
+                    .map(|s| s.get_method_frame(method))
+                    .collect()
+            })
+            .collect();
+        self.frames = Some(frames);
+    }
 
-        self.euler_frames = Some(
-            (0..self.frames_count)
-                .map(|f| {
-                    let x = f as f32 / (self.frames_count - 1) as f32;
-                    let t = (1f32 - x) * self.begin_position + x * self.end_position;
-                    let r = (1f32 - x) * begin_euler + x * end_euler;
-                    Matrix4::new_translation(&t)
-                        * Rotation3::from_euler_angles(r.x, r.y, r.z).to_homogeneous()
-                })
-                .collect(),
-        );
+    fn seek(&mut self, _x: f32) {}
+
+    fn set_playback_speed(&mut self, _speed: f64) {}
+
+    fn set_paused(&mut self, _paused: bool) {}
+
+    fn get_progress(&self) -> Option<f32> {
+        None
+    }
+
+    fn get_time_drift(&self) -> Option<f64> {
+        None
+    }
+}
+
+impl ContinuousAnimation {
+    fn get_method_interpolators(&self) -> &[Interpolator] {
+        self.method_interpolators
+            .get_or_init(|| get_method_interpolators(&self.interpolator, &self.methods))
+    }
+
+    fn get_current_frames(&self) -> &[Matrix4<f32>] {
+        self.frames.get_or_init(|| {
+            let x = self.get_remapped((self.time_elapsed / self.animation_time) as f32);
+            self.get_method_interpolators()
+                .iter()
+                .zip(&self.methods)
+                .map(|(interpolator, method)| interpolator.get_sample(x).get_method_frame(method))
+                .collect()
+        })
+    }
+
+    fn get_remapped(&self, x: f32) -> f32 {
+        match &self.time_remap {
+            Some(time_remap) => time_remap.evaluate(x),
+            None => x,
+        }
+    }
+
+    fn get_absolute_time_elapsed(&self) -> f64 {
+        self.time_offset
+            + self.start_time.map_or(0f64, |start_time| {
+                start_time.elapsed().as_secs_f64() * self.playback_speed
+            })
+    }
+
+    fn rebase_start_time(&mut self, time_offset: f64, running: bool) {
+        self.time_offset = time_offset;
+        self.start_time = running.then(Instant::now);
     }
 }
 
 impl Animation for ContinuousAnimation {
-    fn get_quaternion_frames(&self) -> Vec<Matrix4<f32>> {
-        let (begin_quaternion, _, end_quaternion, _) =
-            AnimationAngle::get_normalized_angles(&self.begin_angle, &self.end_angle);
-
-        let x = (self.time_elapsed / self.animation_time) as f32;
-        let t = (1f32 - x) * self.begin_position + x * self.end_position;
-        let r = get_quaternions_interpolation(
-            &begin_quaternion,
-            &end_quaternion,
-            x,
-            &self.quaternion_interpolation_type,
-        );
-        vec![Matrix4::new_translation(&t) * r.to_rotation_matrix().to_homogeneous()]
+    fn get_methods(&self) -> &[String] {
+        &self.methods
     }
 
-    fn get_euler_frames(&self) -> Vec<Matrix4<f32>> {
-        let (_, begin_euler, _, end_euler) =
-            AnimationAngle::get_normalized_angles(&self.begin_angle, &self.end_angle);
+    fn get_frames(&self, method: usize) -> &[Matrix4<f32>] {
+        self.get_current_frames()
+            .get(method..=method)
+            .unwrap_or_default()
+    }
 
-        let x = (self.time_elapsed / self.animation_time) as f32;
-        let t = (1f32 - x) * self.begin_position + x * self.end_position;
-        let r = (1f32 - x) * begin_euler + x * end_euler;
-        vec![
-            Matrix4::new_translation(&t)
-                * Rotation3::from_euler_angles(r.x, r.y, r.z).to_homogeneous(),
-        ]
+    fn get_path(&self, method: usize, samples_count: u16) -> Vec<Vector3<f32>> {
+        self.get_method_interpolators()
+            .get(method)
+            .map(|i| i.get_path(samples_count))
+            .unwrap_or_default()
+    }
+
+    fn get_sample(&self, x: f32) -> AnimationSample {
+        self.interpolator.get_sample(self.get_remapped(x))
     }
 
     fn make_step(&mut self, time_elapsed: f64) {
-        self.time_elapsed += time_elapsed;
+        if self.time_elapsed >= self.animation_time {
+            return;
+        }
+
+        self.frames.take();
+        let time_elapsed = time_elapsed * self.playback_speed;
+        self.summed_time_elapsed += time_elapsed;
+        let absolute_time_elapsed = self.get_absolute_time_elapsed();
+        self.time_drift = self.summed_time_elapsed - absolute_time_elapsed;
+        self.time_elapsed = match (&self.slow_motion, &self.time_accumulation) {
+            (Some(slow_motion), _) => {
+                let x = (self.time_elapsed / self.animation_time) as f32;
+                self.time_elapsed + time_elapsed * slow_motion.get_speed(x) as f64
+            }
+            (None, TimeAccumulation::RunningSum) => self.summed_time_elapsed,
+            (None, TimeAccumulation::AbsoluteTimestamp) => absolute_time_elapsed,
+        };
 
         if self.time_elapsed >= self.animation_time {
             self.time_elapsed = self.animation_time;
         }
     }
+
+    fn seek(&mut self, x: f32) {
+        self.frames.take();
+        self.time_elapsed = x.clamp(0f32, 1f32) as f64 * self.animation_time;
+        self.summed_time_elapsed = self.time_elapsed;
+        self.rebase_start_time(self.time_elapsed, self.start_time.is_some());
+    }
+
+    fn set_playback_speed(&mut self, speed: f64) {
+        if speed != self.playback_speed {
+            let time_offset = self.get_absolute_time_elapsed();
+            self.playback_speed = speed;
+            self.rebase_start_time(time_offset, self.start_time.is_some());
+        }
+    }
+
+    fn set_paused(&mut self, paused: bool) {
+        if paused == self.start_time.is_some() {
+            self.rebase_start_time(self.get_absolute_time_elapsed(), !paused);
+        }
+    }
+
+    fn get_progress(&self) -> Option<f32> {
+        Some((self.time_elapsed / self.animation_time) as f32)
+    }
+
+    fn get_time_drift(&self) -> Option<f64> {
+        self.slow_motion.is_none().then_some(self.time_drift)
+    }
 }
 
 impl DiscreteFrameAnimationBuilder {
+    fn get_default_methods(&self) -> Result<Vec<String>, String> {
+        get_default_methods(self.interpolator.as_ref())
+    }
+
     fn validate(&self) -> Result<(), String> {
-        if let Some(fc) = self.frames_count {
-            if fc >= 2 {
-                return Ok(());
-            } else {
-                return Result::Err("Frames count too low".to_string());
-            }
-        } else {
-            return Ok(());
+        match self.frames_count {
+            Some(fc) if fc < 2 => Result::Err("Frames count too low".to_string()),
+            Some(fc) if fc > MAX_FRAMES_COUNT => Result::Err("Frames count too high".to_string()),
+            _ => Ok(()),
         }
     }
 }
 
+impl ContinuousAnimationBuilder {
+    fn get_default_methods(&self) -> Result<Vec<String>, String> {
+        get_default_methods(self.interpolator.as_ref())
+    }
+}
+
 impl AnimationAngle {
-    fn deconstruct(&self) -> (UnitQuaternion<f32>, Vector3<f32>) {
-        let mut result = match self {
+    fn deconstruct(
+        &self,
+        normalize: bool,
+        euler_order: &EulerOrder,
+        policy: &NumericalPolicy,
+    ) -> (UnitQuaternion<f32>, Vector3<f32>) {
+        let (quaternion, euler) = match self {
+            AnimationAngle::Quternion(quaternion)
+                if quaternion.norm_squared() < policy.zero_norm_epsilon =>
+            {
+                (UnitQuaternion::identity(), Vector3::zeros())
+            }
             AnimationAngle::Quternion(quaternion) => {
-                let q = UnitQuaternion::from_quaternion(*quaternion);
-                let e = q.euler_angles();
-                (q, Vector3::new(e.0, e.1, e.2))
+                let unit = UnitQuaternion::from_quaternion(*quaternion);
+                let q = if normalize {
+                    unit
+                } else {
+                    UnitQuaternion::new_unchecked(*quaternion)
+                };
+                (q, euler_order.get_euler(&unit))
             }
             AnimationAngle::Euler(euler) => {
-                let e = Vector3::new(
-                    Self::normalize_angle(euler.x),
-                    Self::normalize_angle(euler.y),
-                    Self::normalize_angle(euler.z),
-                );
-                (UnitQuaternion::from_euler_angles(e.x, e.y, e.z), e)
+                let e = euler.map(|a| Self::normalize_angle(a, policy));
+                (euler_order.get_quaternion(&e), e)
             }
         };
 
-        if result.0.norm_squared() < 1e-6 {
-            result.0 = UnitQuaternion::from_quaternion(Quaternion::new(1f32, 0f32, 0f32, 0f32));
-        }
-
-        result.1 = Vector3::new(
-            Self::normalize_angle(result.1.x),
-            Self::normalize_angle(result.1.y),
-            Self::normalize_angle(result.1.z),
-        );
-
-        result
+        (quaternion, euler.map(|a| Self::normalize_angle(a, policy)))
     }
 
     fn get_normalized_angles(
         begin: &AnimationAngle,
         end: &AnimationAngle,
+        normalize: bool,
+        begin_representation: &EulerRepresentation,
+        end_representation: &EulerRepresentation,
+        euler_order: &EulerOrder,
+        policy: &NumericalPolicy,
     ) -> (
         UnitQuaternion<f32>,
         Vector3<f32>,
         UnitQuaternion<f32>,
         Vector3<f32>,
     ) {
-        let (begin_quaternion, begin_euler) = begin.deconstruct();
-        let (end_quaternion, end_euler) = end.deconstruct();
+        let (begin_quaternion, begin_euler) = begin.deconstruct(normalize, euler_order, policy);
+        let (end_quaternion, end_euler) = end.deconstruct(normalize, euler_order, policy);
+        let begin_euler =
+            Self::get_euler_representation(&begin_euler, begin_representation, policy);
+        let end_euler = Self::get_euler_representation(&end_euler, end_representation, policy);
 
         let (begin_euler, end_euler) = {
             let mut begin = begin_euler;
@@ -203,8 +562,8 @@ impl AnimationAngle {
 
         let (begin_quaternion, end_quaternion) = if let AnimationAngle::Euler(_) = begin {
             (
-                UnitQuaternion::from_euler_angles(begin_euler.x, begin_euler.y, begin_euler.z),
-                UnitQuaternion::from_euler_angles(end_euler.x, end_euler.y, end_euler.z),
+                euler_order.get_quaternion(&begin_euler),
+                euler_order.get_quaternion(&end_euler),
             )
         } else {
             let qs = [
@@ -240,12 +599,39 @@ impl AnimationAngle {
         (begin_quaternion, begin_euler, end_quaternion, end_euler)
     }
 
-    fn normalize_angle(angle: f32) -> f32 {
-        let angle = normalized_angle(angle);
-        if angle >= 0f32 {
-            angle
+    pub fn get_euler_representations(
+        &self,
+        euler_order: &EulerOrder,
+        policy: &NumericalPolicy,
+    ) -> [Vector3<f32>; 2] {
+        let (_, euler) = self.deconstruct(true, euler_order, policy);
+        [
+            euler,
+            Self::get_euler_representation(&euler, &EulerRepresentation::Alternative, policy),
+        ]
+    }
+
+    fn get_euler_representation(
+        euler: &Vector3<f32>,
+        representation: &EulerRepresentation,
+        policy: &NumericalPolicy,
+    ) -> Vector3<f32> {
+        match representation {
+            EulerRepresentation::Primary => *euler,
+            EulerRepresentation::Alternative => Vector3::new(
+                Self::normalize_angle(euler.x + PI, policy),
+                Self::normalize_angle(PI - euler.y, policy),
+                Self::normalize_angle(euler.z + PI, policy),
+            ),
+        }
+    }
+
+    fn normalize_angle(angle: f32, policy: &NumericalPolicy) -> f32 {
+        let angle = angle.rem_euclid(2f32 * PI);
+        if 2f32 * PI - angle < policy.angle_wrap_epsilon {
+            0f32
         } else {
-            angle + 2f32 * PI
+            angle
         }
     }
 
@@ -269,30 +655,570 @@ impl AnimationAngle {
     }
 }
 
+fn get_default_methods(interpolator: Option<&Interpolator>) -> Result<Vec<String>, String> {
+    let interpolator = interpolator.ok_or("interpolator must be set")?;
+    Ok(vec![
+        interpolator.rotation_interpolator.get_id().to_string(),
+        EULER_METHOD.to_string(),
+    ])
+}
+
+fn get_method_interpolators(interpolator: &Interpolator, methods: &[String]) -> Vec<Interpolator> {
+    methods
+        .iter()
+        .map(|m| interpolator.with_method(m))
+        .collect()
+}
+
+pub fn make_fixed_steps(animation: &mut dyn Animation, accumulator: &mut f64, time_elapsed: f64) {
+    *accumulator = (*accumulator + time_elapsed).min(MAX_ACCUMULATED_TIME);
+    while *accumulator >= FIXED_TIMESTEP - TIMESTEP_EPSILON {
+        animation.make_step(FIXED_TIMESTEP);
+        *accumulator -= FIXED_TIMESTEP;
+    }
+}
+
+pub fn get_frame_quaternion(frame: &Matrix4<f32>) -> UnitQuaternion<f32> {
+    let rotation = frame.fixed_view::<3, 3>(0, 0).into_owned();
+    let scale = rotation.determinant().abs().cbrt();
+    let rotation = if scale > 0f32 && scale.is_finite() {
+        rotation / scale
+    } else {
+        rotation
+    };
+    UnitQuaternion::from_matrix_eps(
+        &rotation,
+        f32::EPSILON,
+        FRAME_ROTATION_MAX_ITERATIONS,
+        UnitQuaternion::identity(),
+    )
+}
+
+pub fn is_near_gimbal_lock(euler: &Vector3<f32>, euler_order: &EulerOrder, threshold: f32) -> bool {
+    euler[euler_order.get_middle_axis()].cos().abs() < threshold.sin()
+}
+
+pub fn get_gimbal_lock_intervals(
+    animation: &dyn Animation,
+    euler_order: &EulerOrder,
+    threshold: f32,
+    samples_count: u16,
+) -> Vec<(f32, f32)> {
+    let mut intervals: Vec<(f32, f32)> = Vec::new();
+    let mut previous_locked = false;
+    for s in 0..samples_count {
+        let x = s as f32 / (samples_count - 1) as f32;
+        let locked = is_near_gimbal_lock(animation.get_sample(x).euler(), euler_order, threshold);
+        if locked {
+            match intervals.last_mut() {
+                Some(interval) if previous_locked => interval.1 = x,
+                _ => intervals.push((x, x)),
+            }
+        }
+        previous_locked = locked;
+    }
+    intervals
+}
+
+fn get_eased(easing: &Easing, x: f32) -> f32 {
+    match easing {
+        Easing::Linear => x,
+        Easing::SmoothStep => x * x * (3f32 - 2f32 * x),
+        Easing::EaseInOutCubic => {
+            if x < 0.5f32 {
+                4f32 * x * x * x
+            } else {
+                1f32 - (-2f32 * x + 2f32).powi(3) / 2f32
+            }
+        }
+        Easing::EaseOutBounce => {
+            let n = 7.5625f32;
+            let d = 2.75f32;
+            if x < 1f32 / d {
+                n * x * x
+            } else if x < 2f32 / d {
+                let x = x - 1.5f32 / d;
+                n * x * x + 0.75f32
+            } else if x < 2.5f32 / d {
+                let x = x - 2.25f32 / d;
+                n * x * x + 0.9375f32
+            } else {
+                let x = x - 2.625f32 / d;
+                n * x * x + 0.984375f32
+            }
+        }
+    }
+}
+
+fn get_positions_interpolation(
+    points: &[Vector3<f32>],
+    interpolation_type: &PositionInterpolationType,
+    tension: f32,
+    tcb_parameters: &[(f32, f32, f32)],
+    velocities: &[Vector3<f32>; 2],
+    t: f32,
+) -> Vector3<f32> {
+    let segments_count = points.len() - 1;
+    let s = t.clamp(0f32, 1f32) * segments_count as f32;
+    let i = (s.floor() as usize).min(segments_count - 1);
+    let u = s - i as f32;
+    let (p1, p2) = (points[i], points[i + 1]);
+
+    match interpolation_type {
+        PositionInterpolationType::Linear => (1f32 - u) * p1 + u * p2,
+        PositionInterpolationType::CatmullRom => {
+            let p0 = points[i.saturating_sub(1)];
+            let p3 = points[(i + 2).min(segments_count)];
+            let m1 = (1f32 - tension) * (p2 - p0) / 2f32;
+            let m2 = (1f32 - tension) * (p3 - p1) / 2f32;
+            get_hermite_interpolation(&p1, &m1, &p2, &m2, u)
+        }
+        PositionInterpolationType::Tcb => {
+            let p0 = points[i.saturating_sub(1)];
+            let p3 = points[(i + 2).min(segments_count)];
+            let (t1, c1, b1) = tcb_parameters.get(i).copied().unwrap_or_default();
+            let (t2, c2, b2) = tcb_parameters.get(i + 1).copied().unwrap_or_default();
+            let m1 = (1f32 - t1) * (1f32 + c1) * (1f32 + b1) / 2f32 * (p1 - p0)
+                + (1f32 - t1) * (1f32 - c1) * (1f32 - b1) / 2f32 * (p2 - p1);
+            let m2 = (1f32 - t2) * (1f32 - c2) * (1f32 + b2) / 2f32 * (p2 - p1)
+                + (1f32 - t2) * (1f32 + c2) * (1f32 - b2) / 2f32 * (p3 - p2);
+            get_hermite_interpolation(&p1, &m1, &p2, &m2, u)
+        }
+        PositionInterpolationType::Hermite => {
+            let p0 = points[i.saturating_sub(1)];
+            let p3 = points[(i + 2).min(segments_count)];
+            let m1 = if i == 0 {
+                velocities[0] / segments_count as f32
+            } else {
+                (p2 - p0) / 2f32
+            };
+            let m2 = if i + 1 == segments_count {
+                velocities[1] / segments_count as f32
+            } else {
+                (p3 - p1) / 2f32
+            };
+            get_hermite_interpolation(&p1, &m1, &p2, &m2, u)
+        }
+    }
+}
+
+fn get_hermite_interpolation(
+    p1: &Vector3<f32>,
+    m1: &Vector3<f32>,
+    p2: &Vector3<f32>,
+    m2: &Vector3<f32>,
+    u: f32,
+) -> Vector3<f32> {
+    let u2 = u * u;
+    let u3 = u2 * u;
+    (2f32 * u3 - 3f32 * u2 + 1f32) * p1
+        + (u3 - 2f32 * u2 + u) * m1
+        + (-2f32 * u3 + 3f32 * u2) * p2
+        + (u3 - u2) * m2
+}
+
+pub fn get_screw_positions_interpolation(
+    begin_position: &Vector3<f32>,
+    begin_quaternion: &UnitQuaternion<f32>,
+    end_position: &Vector3<f32>,
+    end_quaternion: &UnitQuaternion<f32>,
+    t: f32,
+    policy: &NumericalPolicy,
+) -> Vector3<f32> {
+    let begin = UnitDualQuaternion::from_parts(
+        Translation3::from(*begin_position),
+        UnitQuaternion::new_normalize(begin_quaternion.into_inner()),
+    );
+    let end = UnitDualQuaternion::from_parts(
+        Translation3::from(*end_position),
+        UnitQuaternion::new_normalize(end_quaternion.into_inner()),
+    );
+    begin
+        .try_sclerp(&end, t, policy.theta_sin_epsilon)
+        .unwrap_or_else(|| begin.nlerp(&end, t))
+        .translation()
+        .vector
+}
+
+/// Same screw as ScLERP, so positions match `get_screw_positions_interpolation`.
+pub fn get_screw_motion_positions_interpolation(
+    begin_position: &Vector3<f32>,
+    begin_quaternion: &UnitQuaternion<f32>,
+    end_position: &Vector3<f32>,
+    end_quaternion: &UnitQuaternion<f32>,
+    t: f32,
+    policy: &NumericalPolicy,
+) -> Vector3<f32> {
+    let begin_quaternion = UnitQuaternion::new_normalize(begin_quaternion.into_inner());
+    let end_quaternion = UnitQuaternion::new_normalize(end_quaternion.into_inner());
+    let relative_rotation = begin_quaternion.inverse() * end_quaternion;
+    let relative_position = begin_quaternion.inverse() * (end_position - begin_position);
+
+    let rotation_vector = relative_rotation.scaled_axis();
+    let translation_vector = get_twist_matrix(&rotation_vector, policy)
+        .try_inverse()
+        .unwrap_or_else(Matrix3::identity)
+        * relative_position;
+
+    begin_position
+        + begin_quaternion
+            * (get_twist_matrix(&(t * rotation_vector), policy) * (t * translation_vector))
+}
+
+fn get_twist_matrix(rotation_vector: &Vector3<f32>, policy: &NumericalPolicy) -> Matrix3<f32> {
+    let theta = rotation_vector.norm();
+    if theta < policy.theta_sin_epsilon {
+        return Matrix3::identity();
+    }
+
+    let skew = rotation_vector.cross_matrix();
+    Matrix3::identity()
+        + (1f32 - theta.cos()) / theta.powi(2) * skew
+        + (theta - theta.sin()) / theta.powi(3) * skew * skew
+}
+
 fn get_quaternions_interpolation(
     begin: &UnitQuaternion<f32>,
     end: &UnitQuaternion<f32>,
     t: f32,
-    interpolation_type: &QuaternionInterpolationType,
+    rotation_interpolator: &dyn RotationInterpolator,
+    normalize: bool,
+    policy: &NumericalPolicy,
 ) -> UnitQuaternion<f32> {
-    let r = match interpolation_type {
-        QuaternionInterpolationType::Linear => {
-            (1f32 - t) * begin.quaternion() + t * end.quaternion()
+    let r = rotation_interpolator
+        .interpolate(begin, end, t, policy)
+        .into_inner();
+    if !normalize || !rotation_interpolator.allows_normalization() {
+        UnitQuaternion::new_unchecked(r)
+    } else if r.norm_squared() < policy.zero_norm_epsilon {
+        *begin
+    } else {
+        UnitQuaternion::from_quaternion(r)
+    }
+}
+
+pub fn get_arc_lengths(path: &[Vector3<f32>]) -> Vec<f32> {
+    std::iter::once(0f32)
+        .chain(path.windows(2).scan(0f32, |length, w| {
+            *length += (w[1] - w[0]).norm();
+            Some(*length)
+        }))
+        .collect()
+}
+
+pub fn get_parameter_at_arc_length(arc_lengths: &[f32], arc_length: f32) -> f32 {
+    if arc_lengths.len() < 2 {
+        return 0f32;
+    }
+
+    let segments_count = (arc_lengths.len() - 1) as f32;
+    let i = arc_lengths
+        .partition_point(|l| *l < arc_length)
+        .clamp(1, arc_lengths.len() - 1);
+    let segment_length = arc_lengths[i] - arc_lengths[i - 1];
+    let fraction = if segment_length > 0f32 {
+        ((arc_length - arc_lengths[i - 1]) / segment_length).clamp(0f32, 1f32)
+    } else {
+        0f32
+    };
+    ((i - 1) as f32 + fraction) / segments_count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rotation_interpolator::{
+        get_rotation_interpolator, get_rotation_interpolators, LINEAR, RAW_LERP, SCLERP,
+        SCREW_MOTION,
+    };
+
+    const EPSILON: f32 = 1e-4;
+
+    fn get_interpolator(rotation_interpolator: Arc<dyn RotationInterpolator>) -> Interpolator {
+        InterpolatorBuilder::default()
+            .begin_position(Vector3::new(0f32, 1f32, 2f32))
+            .end_position(Vector3::new(4f32, -1f32, 3f32))
+            .begin_angle(AnimationAngle::new_quternion(
+                UnitQuaternion::from_euler_angles(0.3, -0.2, 0.1).into_inner(),
+            ))
+            .end_angle(AnimationAngle::new_quternion(
+                UnitQuaternion::from_euler_angles(-0.4, 1.1, 0.6).into_inner(),
+            ))
+            .rotation_interpolator(rotation_interpolator)
+            .build()
+            .unwrap()
+    }
+
+    fn get_quaternion_distance(a: &UnitQuaternion<f32>, b: &UnitQuaternion<f32>) -> f32 {
+        (a.coords - b.coords)
+            .norm()
+            .min((a.coords + b.coords).norm())
+    }
+
+    #[test]
+    fn samples_hit_end_poses() {
+        for rotation_interpolator in get_rotation_interpolators() {
+            let interpolator = get_interpolator(rotation_interpolator.clone());
+            let (begin_quaternion, _, end_quaternion, _) = interpolator.get_normalized_angles();
+            let [begin, end] = [0f32, 1f32].map(|x| interpolator.get_sample(x));
+
+            let id = rotation_interpolator.get_id();
+            assert!(
+                (begin.position - interpolator.begin_position).norm() < EPSILON,
+                "{id}"
+            );
+            assert!(
+                (end.position - interpolator.end_position).norm() < EPSILON,
+                "{id}"
+            );
+            assert!(
+                get_quaternion_distance(&begin.quaternion, &begin_quaternion) < EPSILON,
+                "{id}"
+            );
+            assert!(
+                get_quaternion_distance(&end.quaternion, &end_quaternion) < EPSILON,
+                "{id}"
+            );
         }
-        QuaternionInterpolationType::Spherical => {
-            let cos = begin.dot(&end).clamp(-1f32, 1f32);
-            let theta = cos.acos();
-            let theta_sin = theta.sin();
-            let (s1, s2) = if theta_sin == 0.0 {
-                (1f32 - t, t)
-            } else {
-                (
-                    ((1f32 - t) * theta).sin() / theta_sin,
-                    (t * theta).sin() / theta_sin,
-                )
-            };
-            s1 * begin.into_inner() + s2 * end.into_inner()
+    }
+
+    #[test]
+    fn normalized_samples_are_unit_quaternions() {
+        for rotation_interpolator in get_rotation_interpolators()
+            .into_iter()
+            .filter(|i| i.get_id() != RAW_LERP)
+        {
+            let id = rotation_interpolator.get_id().to_string();
+            for sample in get_interpolator(rotation_interpolator).get_samples(11) {
+                assert!(sample.is_finite(), "{id}");
+                assert!((sample.quaternion.norm() - 1f32).abs() < EPSILON, "{id}");
+            }
         }
-    };
-    UnitQuaternion::from_quaternion(r)
+    }
+
+    #[test]
+    fn samples_are_evenly_spaced() {
+        let interpolator = get_interpolator(get_rotation_interpolator(LINEAR).unwrap());
+        let samples = interpolator.get_samples(5);
+
+        assert_eq!(samples.len(), 5);
+        for (i, sample) in samples.iter().enumerate() {
+            let expected = interpolator.begin_position
+                + i as f32 / 4f32 * (interpolator.end_position - interpolator.begin_position);
+            assert!((sample.position - expected).norm() < EPSILON);
+        }
+    }
+
+    #[test]
+    fn euler_angles_round_trip_through_quaternions() {
+        let euler = Vector3::new(0.3f32, -0.5, 1.2);
+        for euler_order in [
+            EulerOrder::XYZ,
+            EulerOrder::XZY,
+            EulerOrder::YXZ,
+            EulerOrder::YZX,
+            EulerOrder::ZXY,
+            EulerOrder::ZYX,
+        ] {
+            let quaternion = euler_order.get_quaternion(&euler);
+            let recovered = euler_order.get_euler(&quaternion);
+
+            assert!((recovered - euler).norm() < EPSILON, "{euler_order:?}");
+            assert!(
+                get_quaternion_distance(&euler_order.get_quaternion(&recovered), &quaternion)
+                    < EPSILON,
+                "{euler_order:?}"
+            );
+        }
+    }
+
+    fn get_control_points() -> Vec<Vector3<f32>> {
+        vec![
+            Vector3::new(0f32, 0f32, 0f32),
+            Vector3::new(1f32, 2f32, 0f32),
+            Vector3::new(3f32, 1f32, -1f32),
+            Vector3::new(4f32, 3f32, 2f32),
+        ]
+    }
+
+    #[test]
+    fn neutral_tcb_matches_catmull_rom() {
+        let points = get_control_points();
+        let velocities = [Vector3::zeros(), Vector3::zeros()];
+        for i in 0..=20 {
+            let t = i as f32 / 20f32;
+            let tcb = get_positions_interpolation(
+                &points,
+                &PositionInterpolationType::Tcb,
+                0f32,
+                &[(0f32, 0f32, 0f32); 4],
+                &velocities,
+                t,
+            );
+            let catmull_rom = get_positions_interpolation(
+                &points,
+                &PositionInterpolationType::CatmullRom,
+                0f32,
+                &[],
+                &velocities,
+                t,
+            );
+            assert!((tcb - catmull_rom).norm() < EPSILON, "t = {t}");
+        }
+    }
+
+    #[test]
+    fn hermite_leaves_and_reaches_with_end_velocities() {
+        let points = get_control_points();
+        let velocities = [
+            Vector3::new(2f32, -1f32, 0.5),
+            Vector3::new(-1f32, 0f32, 3f32),
+        ];
+        let get_position = |t: f32| {
+            get_positions_interpolation(
+                &points,
+                &PositionInterpolationType::Hermite,
+                0f32,
+                &[],
+                &velocities,
+                t,
+            )
+        };
+        let h = 1e-3f32;
+
+        let begin_velocity =
+            (4f32 * get_position(h) - 3f32 * get_position(0f32) - get_position(2f32 * h))
+                / (2f32 * h);
+        let end_velocity = (3f32 * get_position(1f32) - 4f32 * get_position(1f32 - h)
+            + get_position(1f32 - 2f32 * h))
+            / (2f32 * h);
+        assert!((get_position(0f32) - points[0]).norm() < EPSILON);
+        assert!((get_position(1f32) - points[3]).norm() < EPSILON);
+        assert!((begin_velocity - velocities[0]).norm() < 1e-2);
+        assert!((end_velocity - velocities[1]).norm() < 1e-2);
+    }
+
+    fn get_rigid_ends() -> (
+        Vector3<f32>,
+        UnitQuaternion<f32>,
+        Vector3<f32>,
+        UnitQuaternion<f32>,
+    ) {
+        (
+            Vector3::new(1f32, 0f32, -2f32),
+            UnitQuaternion::from_euler_angles(0.2, 0.4, -0.3),
+            Vector3::new(-3f32, 2f32, 1f32),
+            UnitQuaternion::from_euler_angles(-1.2, 2.5, 0.9),
+        )
+    }
+
+    #[test]
+    fn sclerp_returns_end_poses() {
+        let (begin_position, begin_quaternion, end_position, end_quaternion) = get_rigid_ends();
+        let policy = NumericalPolicy::default();
+        let sclerp = get_rotation_interpolator(SCLERP).unwrap();
+
+        for (t, position, quaternion) in [
+            (0f32, begin_position, begin_quaternion),
+            (1f32, end_position, end_quaternion),
+        ] {
+            let interpolated_position = get_screw_positions_interpolation(
+                &begin_position,
+                &begin_quaternion,
+                &end_position,
+                &end_quaternion,
+                t,
+                &policy,
+            );
+            let interpolated_quaternion =
+                sclerp.interpolate(&begin_quaternion, &end_quaternion, t, &policy);
+            assert!(
+                (interpolated_position - position).norm() < EPSILON,
+                "t = {t}"
+            );
+            assert!(
+                get_quaternion_distance(&interpolated_quaternion, &quaternion) < EPSILON,
+                "t = {t}"
+            );
+        }
+    }
+
+    #[test]
+    fn screw_motion_returns_end_poses() {
+        let (begin_position, begin_quaternion, end_position, end_quaternion) = get_rigid_ends();
+        let policy = NumericalPolicy::default();
+        let screw_motion = get_rotation_interpolator(SCREW_MOTION).unwrap();
+
+        for (t, position, quaternion) in [
+            (0f32, begin_position, begin_quaternion),
+            (1f32, end_position, end_quaternion),
+        ] {
+            let interpolated_position = get_screw_motion_positions_interpolation(
+                &begin_position,
+                &begin_quaternion,
+                &end_position,
+                &end_quaternion,
+                t,
+                &policy,
+            );
+            let interpolated_quaternion =
+                screw_motion.interpolate(&begin_quaternion, &end_quaternion, t, &policy);
+            assert!(
+                (interpolated_position - position).norm() < EPSILON,
+                "t = {t}"
+            );
+            assert!(
+                get_quaternion_distance(&interpolated_quaternion, &quaternion) < EPSILON,
+                "t = {t}"
+            );
+        }
+    }
+
+    #[test]
+    fn screw_motion_positions_match_sclerp() {
+        let (begin_position, begin_quaternion, end_position, end_quaternion) = get_rigid_ends();
+        let policy = NumericalPolicy::default();
+
+        for i in 0..=10 {
+            let t = i as f32 / 10f32;
+            let screw_motion = get_screw_motion_positions_interpolation(
+                &begin_position,
+                &begin_quaternion,
+                &end_position,
+                &end_quaternion,
+                t,
+                &policy,
+            );
+            let sclerp = get_screw_positions_interpolation(
+                &begin_position,
+                &begin_quaternion,
+                &end_position,
+                &end_quaternion,
+                t,
+                &policy,
+            );
+            assert!((screw_motion - sclerp).norm() < 1e-3, "t = {t}");
+        }
+    }
+
+    #[test]
+    fn screw_modes_share_one_easing() {
+        for id in [SCLERP, SCREW_MOTION] {
+            let mut coupled = get_interpolator(get_rotation_interpolator(id).unwrap());
+            coupled.translation_easing = Easing::SmoothStep;
+            coupled.rotation_easing = Easing::EaseOutBounce;
+            let mut shared = coupled.clone();
+            shared.rotation_easing = Easing::SmoothStep;
+
+            for i in 0..=10 {
+                let x = i as f32 / 10f32;
+                let (a, b) = (coupled.get_sample(x), shared.get_sample(x));
+                assert!((a.position - b.position).norm() < EPSILON, "{id}, x = {x}");
+                assert!(
+                    get_quaternion_distance(&a.quaternion, &b.quaternion) < EPSILON,
+                    "{id}, x = {x}"
+                );
+            }
+        }
+    }
 }
@@ -1,4 +1,24 @@
-#[derive(Debug, Clone, Default)]
+use std::f32::consts::PI;
+use std::fmt::Display;
+use std::sync::Arc;
+
+use derive_getters::Getters;
+
+use nalgebra::{Matrix3, Matrix4, Quaternion, Unit, UnitQuaternion, Vector3};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::animation::{
+    get_frame_quaternion, AnimationAngle, Interpolator, InterpolatorBuilder, FIXED_TIMESTEP,
+    MAX_FRAMES_COUNT,
+};
+use crate::random::Random;
+use crate::rotation_interpolator::{get_rotation_interpolator, RotationInterpolator, LINEAR};
+
+pub const EULER_METHOD: &str = "Euler";
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default = "AnimationData::new")]
 pub struct AnimationData {
     pub begin_position: (f32, f32, f32),
     pub end_position: (f32, f32, f32),
@@ -6,17 +26,217 @@ pub struct AnimationData {
     pub end_rotation_quaternion: (f32, f32, f32, f32),
     pub begin_rotation_xyz: (f32, f32, f32),
     pub end_rotation_xyz: (f32, f32, f32),
-    pub quaternion_interpolation_type: QuaternionInterpolationType,
+    pub quaternion_interpolation_type: String,
     pub display_all_frames: bool,
+    pub quaternion_normalization_policy: QuaternionNormalizationPolicy,
+    pub quaternion_convention: QuaternionConvention,
+    pub begin_euler_representation: EulerRepresentation,
+    pub end_euler_representation: EulerRepresentation,
     pub animation_time: f64,
-    pub frames_count: u8,
+    #[serde(deserialize_with = "deserialize_frames_count")]
+    pub frames_count: u32,
+    pub frame_spacing: FrameSpacing,
+    pub frame_step: FrameStep,
+    pub export_samples_count: u16,
+    pub obstacles: Vec<Obstacle>,
+    pub seed: u64,
+    pub random_position_range: f32,
+    pub reference_trajectory_path: String,
+    pub waypoints: Vec<(f32, f32, f32)>,
+    pub position_interpolation_type: PositionInterpolationType,
+    pub catmull_rom_tension: f32,
+    pub tcb_parameters: Vec<(f32, f32, f32)>,
+    pub begin_velocity: (f32, f32, f32),
+    pub end_velocity: (f32, f32, f32),
+    pub translation_easing: Easing,
+    pub rotation_easing: Easing,
+    pub time_remap_enabled: bool,
+    pub time_remap: CubicBezier,
+    pub euler_order: EulerOrder,
+    pub time_accumulation: TimeAccumulation,
+    pub auto_slow_motion: bool,
+    pub midpoint_slow_motion: bool,
+    pub playback_speed: f32,
+    pub begin_rotation_axis: (f32, f32, f32),
+    pub begin_rotation_angle: f32,
+    pub end_rotation_axis: (f32, f32, f32),
+    pub end_rotation_angle: f32,
+    pub pasted_matrix: String,
+    pub numerical_policy: NumericalPolicy,
+    pub gimbal_lock_threshold: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Getters, Serialize, Deserialize)]
+pub struct RunStamp {
+    seed: u64,
+    parameters_hash: u64,
+}
+
+#[derive(Serialize)]
+struct RunParameters<'a> {
+    begin_position: &'a (f32, f32, f32),
+    end_position: &'a (f32, f32, f32),
+    begin_rotation_quaternion: &'a (f32, f32, f32, f32),
+    end_rotation_quaternion: &'a (f32, f32, f32, f32),
+    begin_rotation_xyz: &'a (f32, f32, f32),
+    end_rotation_xyz: &'a (f32, f32, f32),
+    begin_rotation_axis: &'a (f32, f32, f32),
+    begin_rotation_angle: &'a f32,
+    end_rotation_axis: &'a (f32, f32, f32),
+    end_rotation_angle: &'a f32,
+    quaternion_interpolation_type: &'a str,
+    quaternion_normalization_policy: &'a QuaternionNormalizationPolicy,
+    begin_euler_representation: &'a EulerRepresentation,
+    end_euler_representation: &'a EulerRepresentation,
+    display_all_frames: &'a bool,
+    animation_time: &'a f64,
+    frames_count: &'a u32,
+    frame_spacing: &'a FrameSpacing,
+    waypoints: &'a [(f32, f32, f32)],
+    position_interpolation_type: &'a PositionInterpolationType,
+    catmull_rom_tension: &'a f32,
+    tcb_parameters: &'a [(f32, f32, f32)],
+    begin_velocity: &'a (f32, f32, f32),
+    end_velocity: &'a (f32, f32, f32),
+    translation_easing: &'a Easing,
+    rotation_easing: &'a Easing,
+    time_remap_enabled: &'a bool,
+    time_remap: &'a CubicBezier,
+    euler_order: &'a EulerOrder,
+    time_accumulation: &'a TimeAccumulation,
+    auto_slow_motion: &'a bool,
+    midpoint_slow_motion: &'a bool,
+    numerical_policy: &'a NumericalPolicy,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum PositionInterpolationType {
+    #[default]
+    Linear,
+    CatmullRom,
+    Tcb,
+    Hermite,
 }
 
-#[derive(Debug, Clone, Default, PartialEq)]
-pub enum QuaternionInterpolationType {
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum Easing {
     #[default]
     Linear,
-    Spherical,
+    SmoothStep,
+    EaseInOutCubic,
+    EaseOutBounce,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CubicBezier {
+    pub x1: f32,
+    pub y1: f32,
+    pub x2: f32,
+    pub y2: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NumericalPolicy {
+    pub theta_sin_epsilon: f32,
+    pub zero_norm_epsilon: f32,
+    pub angle_wrap_epsilon: f32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum FrameSpacing {
+    #[default]
+    Uniform,
+    ArcLength,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum FrameStep {
+    #[default]
+    Timestep,
+    Frame,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum TimeAccumulation {
+    #[default]
+    RunningSum,
+    AbsoluteTimestamp,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum PoseGizmo {
+    #[default]
+    Off,
+    Begin,
+    End,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum QuaternionNormalizationPolicy {
+    NormalizeOnEdit,
+    #[default]
+    NormalizeAtRun,
+    Raw,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QuaternionConvention {
+    pub component_order: QuaternionComponentOrder,
+    pub multiplication: QuaternionMultiplication,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum QuaternionComponentOrder {
+    #[default]
+    Wxyz,
+    Xyzw,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum QuaternionMultiplication {
+    #[default]
+    Hamilton,
+    Jpl,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum EulerOrder {
+    #[default]
+    XYZ,
+    XZY,
+    YXZ,
+    YZX,
+    ZXY,
+    ZYX,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum EulerRepresentation {
+    #[default]
+    Primary,
+    Alternative,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum AngleInput {
+    #[default]
+    Quaternion,
+    Euler,
+    AxisAngle,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Obstacle {
+    pub shape: ObstacleShape,
+    pub position: (f32, f32, f32),
+    pub size: (f32, f32, f32),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum ObstacleShape {
+    #[default]
+    Box,
+    Sphere,
 }
 
 impl AnimationData {
@@ -24,9 +244,563 @@ impl AnimationData {
         Self {
             begin_rotation_quaternion: (1f32, 0f32, 0f32, 0f32),
             end_rotation_quaternion: (1f32, 0f32, 0f32, 0f32),
+            quaternion_interpolation_type: LINEAR.to_string(),
             frames_count: 10,
             animation_time: 10.0,
+            playback_speed: 1f32,
+            export_samples_count: 100,
+            random_position_range: 5f32,
+            begin_rotation_axis: (0f32, 1f32, 0f32),
+            end_rotation_axis: (0f32, 1f32, 0f32),
+            gimbal_lock_threshold: 5f32,
             ..Default::default()
         }
     }
+
+    pub fn get_begin_position(&self) -> Vector3<f32> {
+        Vector3::new(
+            self.begin_position.0,
+            self.begin_position.1,
+            self.begin_position.2,
+        )
+    }
+
+    pub fn get_end_position(&self) -> Vector3<f32> {
+        Vector3::new(
+            self.end_position.0,
+            self.end_position.1,
+            self.end_position.2,
+        )
+    }
+
+    pub fn get_begin_quaternion(&self) -> Quaternion<f32> {
+        self.get_input_quaternion(&self.begin_rotation_quaternion)
+    }
+
+    pub fn get_end_quaternion(&self) -> Quaternion<f32> {
+        self.get_input_quaternion(&self.end_rotation_quaternion)
+    }
+
+    pub fn get_begin_euler(&self) -> Vector3<f32> {
+        get_euler_in_radians(&self.begin_rotation_xyz)
+    }
+
+    pub fn get_end_euler(&self) -> Vector3<f32> {
+        get_euler_in_radians(&self.end_rotation_xyz)
+    }
+
+    pub fn normalize_quaternions(&self) -> bool {
+        self.quaternion_normalization_policy != QuaternionNormalizationPolicy::Raw
+    }
+
+    pub fn normalize_quaternion_inputs(&mut self) {
+        self.begin_rotation_quaternion = normalize_tuple(&self.begin_rotation_quaternion);
+        self.end_rotation_quaternion = normalize_tuple(&self.end_rotation_quaternion);
+    }
+
+    pub fn get_angles(&self, angle_input: &AngleInput) -> (AnimationAngle, AnimationAngle) {
+        match angle_input {
+            AngleInput::Quaternion => (
+                AnimationAngle::new_quternion(self.get_begin_quaternion()),
+                AnimationAngle::new_quternion(self.get_end_quaternion()),
+            ),
+            AngleInput::Euler => (
+                AnimationAngle::new_euler(self.get_begin_euler()),
+                AnimationAngle::new_euler(self.get_end_euler()),
+            ),
+            AngleInput::AxisAngle => (
+                AnimationAngle::new_quternion(
+                    get_axis_angle_quaternion(&self.begin_rotation_axis, self.begin_rotation_angle)
+                        .into_inner(),
+                ),
+                AnimationAngle::new_quternion(
+                    get_axis_angle_quaternion(&self.end_rotation_axis, self.end_rotation_angle)
+                        .into_inner(),
+                ),
+            ),
+        }
+    }
+
+    pub fn get_interpolator(
+        &self,
+        begin_angle: AnimationAngle,
+        end_angle: AnimationAngle,
+    ) -> Interpolator {
+        InterpolatorBuilder::default()
+            .begin_position(self.get_begin_position())
+            .end_position(self.get_end_position())
+            .begin_angle(begin_angle)
+            .end_angle(end_angle)
+            .normalize_quaternions(self.normalize_quaternions())
+            .rotation_interpolator(self.get_rotation_interpolator())
+            .begin_euler_representation(self.begin_euler_representation.clone())
+            .end_euler_representation(self.end_euler_representation.clone())
+            .waypoints(
+                self.waypoints
+                    .iter()
+                    .map(|w| Vector3::new(w.0, w.1, w.2))
+                    .collect(),
+            )
+            .position_interpolation_type(self.position_interpolation_type.clone())
+            .catmull_rom_tension(self.catmull_rom_tension)
+            .tcb_parameters(self.tcb_parameters.clone())
+            .begin_velocity(Vector3::new(
+                self.begin_velocity.0,
+                self.begin_velocity.1,
+                self.begin_velocity.2,
+            ))
+            .end_velocity(Vector3::new(
+                self.end_velocity.0,
+                self.end_velocity.1,
+                self.end_velocity.2,
+            ))
+            .translation_easing(self.translation_easing.clone())
+            .rotation_easing(self.rotation_easing.clone())
+            .euler_order(self.euler_order.clone())
+            .numerical_policy(self.numerical_policy.clone())
+            .build()
+            .unwrap()
+    }
+
+    pub fn get_method_name(&self, method: &str) -> String {
+        if method == EULER_METHOD {
+            return format!("Euler: {:?}", self.euler_order);
+        }
+        get_rotation_interpolator(method)
+            .map(|i| i.get_name().to_string())
+            .unwrap_or_else(|| method.to_string())
+    }
+
+    pub fn get_rotation_interpolator(&self) -> Arc<dyn RotationInterpolator> {
+        get_rotation_interpolator(&self.quaternion_interpolation_type)
+            .unwrap_or_else(|| get_rotation_interpolator(LINEAR).unwrap())
+    }
+
+    pub fn set_begin_pose(&mut self, quaternion_model: &Matrix4<f32>, euler_model: &Matrix4<f32>) {
+        self.begin_position = get_position_tuple(quaternion_model);
+        self.begin_rotation_quaternion = get_quaternion_tuple(quaternion_model);
+        self.begin_rotation_xyz = self.get_euler_tuple(euler_model);
+        (self.begin_rotation_axis, self.begin_rotation_angle) =
+            get_axis_angle_tuple(quaternion_model);
+    }
+
+    pub fn set_end_pose(&mut self, quaternion_model: &Matrix4<f32>, euler_model: &Matrix4<f32>) {
+        self.end_position = get_position_tuple(quaternion_model);
+        self.end_rotation_quaternion = get_quaternion_tuple(quaternion_model);
+        self.end_rotation_xyz = self.get_euler_tuple(euler_model);
+        (self.end_rotation_axis, self.end_rotation_angle) = get_axis_angle_tuple(quaternion_model);
+    }
+
+    pub fn swap_poses(&mut self) {
+        std::mem::swap(&mut self.begin_position, &mut self.end_position);
+        std::mem::swap(
+            &mut self.begin_rotation_quaternion,
+            &mut self.end_rotation_quaternion,
+        );
+        std::mem::swap(&mut self.begin_rotation_xyz, &mut self.end_rotation_xyz);
+        std::mem::swap(&mut self.begin_rotation_axis, &mut self.end_rotation_axis);
+        std::mem::swap(&mut self.begin_rotation_angle, &mut self.end_rotation_angle);
+        std::mem::swap(
+            &mut self.begin_euler_representation,
+            &mut self.end_euler_representation,
+        );
+    }
+
+    pub fn copy_begin_to_end(&mut self) {
+        self.end_position = self.begin_position;
+        self.end_rotation_quaternion = self.begin_rotation_quaternion;
+        self.end_rotation_xyz = self.begin_rotation_xyz;
+        self.end_rotation_axis = self.begin_rotation_axis;
+        self.end_rotation_angle = self.begin_rotation_angle;
+        self.end_euler_representation = self.begin_euler_representation.clone();
+    }
+
+    pub fn get_pose(
+        &self,
+        pose: &PoseGizmo,
+        angle_input: &AngleInput,
+    ) -> Option<(Vector3<f32>, UnitQuaternion<f32>)> {
+        let (begin_angle, end_angle) = self.get_angles(angle_input);
+        let (position, angle) = match pose {
+            PoseGizmo::Off => return None,
+            PoseGizmo::Begin => (self.get_begin_position(), begin_angle),
+            PoseGizmo::End => (self.get_end_position(), end_angle),
+        };
+        let rotation = match angle {
+            AnimationAngle::Quternion(quaternion) if quaternion.norm_squared() > 1e-6 => {
+                UnitQuaternion::from_quaternion(quaternion)
+            }
+            AnimationAngle::Quternion(_) => UnitQuaternion::identity(),
+            AnimationAngle::Euler(euler) => self.euler_order.get_quaternion(&euler),
+        };
+        Some((position, rotation))
+    }
+
+    pub fn rotate_pose(
+        &mut self,
+        pose: &PoseGizmo,
+        angle_input: &AngleInput,
+        rotation: &UnitQuaternion<f32>,
+    ) {
+        let Some((position, current)) = self.get_pose(pose, angle_input) else {
+            return;
+        };
+        let model = Matrix4::new_translation(&position) * (rotation * current).to_homogeneous();
+        if *pose == PoseGizmo::Begin {
+            self.set_begin_pose(&model, &model);
+        } else {
+            self.set_end_pose(&model, &model);
+        }
+    }
+
+    pub fn get_frame_step(&self) -> f32 {
+        match self.frame_step {
+            FrameStep::Timestep => (FIXED_TIMESTEP / self.animation_time) as f32,
+            FrameStep::Frame => 1f32 / (self.frames_count.max(2) - 1) as f32,
+        }
+    }
+
+    pub fn randomize_poses(&mut self) {
+        let mut random = Random::new(self.seed);
+        let range = self.random_position_range;
+        let mut get_random_pose = || {
+            let position = random.next_xyz(-range, range);
+            let q = random.next_unit_wxyz();
+            let rotation = UnitQuaternion::from_quaternion(Quaternion::new(q.0, q.1, q.2, q.3));
+            Matrix4::new_translation(&Vector3::new(position.0, position.1, position.2))
+                * rotation.to_homogeneous()
+        };
+        let begin_model = get_random_pose();
+        let end_model = get_random_pose();
+
+        self.set_begin_pose(&begin_model, &begin_model);
+        self.set_end_pose(&end_model, &end_model);
+    }
+
+    pub fn set_begin_from_pasted_matrix(&mut self) -> Result<(), String> {
+        let model = parse_matrix(&self.pasted_matrix, &self.get_begin_position())?;
+        self.set_begin_pose(&model, &model);
+        Ok(())
+    }
+
+    pub fn set_end_from_pasted_matrix(&mut self) -> Result<(), String> {
+        let model = parse_matrix(&self.pasted_matrix, &self.get_end_position())?;
+        self.set_end_pose(&model, &model);
+        Ok(())
+    }
+
+    pub fn get_run_stamp(&self) -> RunStamp {
+        let parameters = RunParameters {
+            begin_position: &self.begin_position,
+            end_position: &self.end_position,
+            begin_rotation_quaternion: &self.begin_rotation_quaternion,
+            end_rotation_quaternion: &self.end_rotation_quaternion,
+            begin_rotation_xyz: &self.begin_rotation_xyz,
+            end_rotation_xyz: &self.end_rotation_xyz,
+            begin_rotation_axis: &self.begin_rotation_axis,
+            begin_rotation_angle: &self.begin_rotation_angle,
+            end_rotation_axis: &self.end_rotation_axis,
+            end_rotation_angle: &self.end_rotation_angle,
+            quaternion_interpolation_type: &self.quaternion_interpolation_type,
+            quaternion_normalization_policy: &self.quaternion_normalization_policy,
+            begin_euler_representation: &self.begin_euler_representation,
+            end_euler_representation: &self.end_euler_representation,
+            display_all_frames: &self.display_all_frames,
+            animation_time: &self.animation_time,
+            frames_count: &self.frames_count,
+            frame_spacing: &self.frame_spacing,
+            waypoints: &self.waypoints,
+            position_interpolation_type: &self.position_interpolation_type,
+            catmull_rom_tension: &self.catmull_rom_tension,
+            tcb_parameters: &self.tcb_parameters,
+            begin_velocity: &self.begin_velocity,
+            end_velocity: &self.end_velocity,
+            translation_easing: &self.translation_easing,
+            rotation_easing: &self.rotation_easing,
+            time_remap_enabled: &self.time_remap_enabled,
+            time_remap: &self.time_remap,
+            euler_order: &self.euler_order,
+            time_accumulation: &self.time_accumulation,
+            auto_slow_motion: &self.auto_slow_motion,
+            midpoint_slow_motion: &self.midpoint_slow_motion,
+            numerical_policy: &self.numerical_policy,
+        };
+        RunStamp {
+            seed: self.seed,
+            parameters_hash: get_fnv1a_hash(serde_json::to_string(&parameters).unwrap().as_bytes()),
+        }
+    }
+
+    fn get_euler_tuple(&self, model: &Matrix4<f32>) -> (f32, f32, f32) {
+        let euler = self.euler_order.get_euler(&get_frame_quaternion(model));
+        (
+            euler.x.to_degrees(),
+            euler.y.to_degrees(),
+            euler.z.to_degrees(),
+        )
+    }
+
+    fn get_input_quaternion(&self, q: &(f32, f32, f32, f32)) -> Quaternion<f32> {
+        let quaternion = Quaternion::new(q.0, q.1, q.2, q.3);
+        if self.quaternion_normalization_policy == QuaternionNormalizationPolicy::NormalizeAtRun
+            && quaternion.norm_squared() > 1e-6
+        {
+            quaternion.normalize()
+        } else {
+            quaternion
+        }
+    }
+}
+
+impl QuaternionConvention {
+    pub fn to_components(&self, quaternion: &Quaternion<f32>) -> [f32; 4] {
+        let (w, v) = match self.multiplication {
+            QuaternionMultiplication::Hamilton => (quaternion.w, quaternion.imag()),
+            QuaternionMultiplication::Jpl => (quaternion.w, -quaternion.imag()),
+        };
+        match self.component_order {
+            QuaternionComponentOrder::Wxyz => [w, v.x, v.y, v.z],
+            QuaternionComponentOrder::Xyzw => [v.x, v.y, v.z, w],
+        }
+    }
+
+    pub fn from_components(&self, components: [f32; 4]) -> Quaternion<f32> {
+        let [w, x, y, z] = match self.component_order {
+            QuaternionComponentOrder::Wxyz => components,
+            QuaternionComponentOrder::Xyzw => {
+                [components[3], components[0], components[1], components[2]]
+            }
+        };
+        match self.multiplication {
+            QuaternionMultiplication::Hamilton => Quaternion::new(w, x, y, z),
+            QuaternionMultiplication::Jpl => Quaternion::new(w, -x, -y, -z),
+        }
+    }
+
+    pub fn get_labels(&self) -> [&'static str; 4] {
+        match self.component_order {
+            QuaternionComponentOrder::Wxyz => ["W", "X", "Y", "Z"],
+            QuaternionComponentOrder::Xyzw => ["X", "Y", "Z", "W"],
+        }
+    }
+}
+
+impl Display for QuaternionConvention {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} order, {} convention",
+            self.get_labels().join("").to_lowercase(),
+            match self.multiplication {
+                QuaternionMultiplication::Hamilton => "Hamilton",
+                QuaternionMultiplication::Jpl => "JPL",
+            }
+        )
+    }
+}
+
+impl EulerOrder {
+    pub fn get_quaternion(&self, euler: &Vector3<f32>) -> UnitQuaternion<f32> {
+        self.get_axes()
+            .iter()
+            .fold(UnitQuaternion::identity(), |rotation, axis| {
+                UnitQuaternion::from_axis_angle(&Vector3::ith_axis(*axis), euler[*axis]) * rotation
+            })
+    }
+
+    pub fn get_euler(&self, quaternion: &UnitQuaternion<f32>) -> Vector3<f32> {
+        let axes = self.get_axes();
+        let (angles, _) = quaternion
+            .to_rotation_matrix()
+            .euler_angles_ordered(axes.map(Vector3::ith_axis), true);
+
+        let mut euler = Vector3::zeros();
+        for (axis, angle) in axes.iter().zip(angles) {
+            euler[*axis] = angle;
+        }
+        euler
+    }
+
+    pub fn get_middle_axis(&self) -> usize {
+        self.get_axes()[1]
+    }
+
+    fn get_axes(&self) -> [usize; 3] {
+        match self {
+            EulerOrder::XYZ => [0, 1, 2],
+            EulerOrder::XZY => [0, 2, 1],
+            EulerOrder::YXZ => [1, 0, 2],
+            EulerOrder::YZX => [1, 2, 0],
+            EulerOrder::ZXY => [2, 0, 1],
+            EulerOrder::ZYX => [2, 1, 0],
+        }
+    }
+}
+
+impl CubicBezier {
+    pub fn new(x1: f32, y1: f32, x2: f32, y2: f32) -> Self {
+        Self { x1, y1, x2, y2 }
+    }
+
+    pub fn get_point(&self, s: f32) -> (f32, f32) {
+        (
+            get_bezier_coordinate(self.x1, self.x2, s),
+            get_bezier_coordinate(self.y1, self.y2, s),
+        )
+    }
+
+    pub fn evaluate(&self, x: f32) -> f32 {
+        let x = x.clamp(0f32, 1f32);
+        let (mut low, mut high) = (0f32, 1f32);
+        for _ in 0..24 {
+            let s = (low + high) / 2f32;
+            if get_bezier_coordinate(self.x1, self.x2, s) < x {
+                low = s;
+            } else {
+                high = s;
+            }
+        }
+        get_bezier_coordinate(self.y1, self.y2, (low + high) / 2f32)
+    }
+}
+
+impl Default for NumericalPolicy {
+    fn default() -> Self {
+        Self {
+            theta_sin_epsilon: 1e-6,
+            zero_norm_epsilon: 1e-6,
+            angle_wrap_epsilon: 1e-6,
+        }
+    }
+}
+
+impl Default for CubicBezier {
+    fn default() -> Self {
+        Self::new(0.42, 0.0, 0.58, 1.0)
+    }
+}
+
+impl Display for RunStamp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "seed {} / parameters hash {:016x}",
+            self.seed, self.parameters_hash
+        )
+    }
+}
+
+impl Obstacle {
+    pub fn new(shape: ObstacleShape) -> Self {
+        Self {
+            shape,
+            position: (0f32, 1f32, 0f32),
+            size: (1f32, 1f32, 1f32),
+        }
+    }
+
+    pub fn get_model(&self) -> Matrix4<f32> {
+        Matrix4::new_translation(&Vector3::new(
+            self.position.0,
+            self.position.1,
+            self.position.2,
+        )) * Matrix4::new_nonuniform_scaling(&Vector3::new(self.size.0, self.size.1, self.size.2))
+    }
+
+    pub fn contains(&self, point: &Vector3<f32>) -> bool {
+        let local = Vector3::new(
+            (point.x - self.position.0) / self.size.0,
+            (point.y - self.position.1) / self.size.1,
+            (point.z - self.position.2) / self.size.2,
+        );
+        match self.shape {
+            ObstacleShape::Box => local.amax() <= 0.5f32,
+            ObstacleShape::Sphere => local.norm() <= 0.5f32,
+        }
+    }
+}
+
+fn get_euler_in_radians(euler: &(f32, f32, f32)) -> Vector3<f32> {
+    Vector3::new(
+        euler.0 / 180f32 * PI,
+        euler.1 / 180f32 * PI,
+        euler.2 / 180f32 * PI,
+    )
+}
+
+fn get_position_tuple(model: &Matrix4<f32>) -> (f32, f32, f32) {
+    (model[(0, 3)], model[(1, 3)], model[(2, 3)])
+}
+
+fn get_quaternion_tuple(model: &Matrix4<f32>) -> (f32, f32, f32, f32) {
+    let q = get_frame_quaternion(model);
+    (q.w, q.i, q.j, q.k)
+}
+
+fn get_axis_angle_tuple(model: &Matrix4<f32>) -> ((f32, f32, f32), f32) {
+    match get_frame_quaternion(model).axis_angle() {
+        Some((axis, angle)) => ((axis.x, axis.y, axis.z), angle.to_degrees()),
+        None => ((0f32, 1f32, 0f32), 0f32),
+    }
+}
+
+fn parse_matrix(text: &str, position: &Vector3<f32>) -> Result<Matrix4<f32>, String> {
+    let values = text
+        .split(|c: char| c == ',' || c == ';' || c.is_whitespace())
+        .filter(|v| !v.is_empty())
+        .map(|v| {
+            v.parse::<f32>()
+                .map_err(|_| format!("Invalid number '{}'", v))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let model = match values.len() {
+        9 => Matrix4::new_translation(position) * Matrix3::from_row_slice(&values).to_homogeneous(),
+        16 => Matrix4::from_row_slice(&values),
+        n => return Err(format!("Expected 9 or 16 values, got {}", n)),
+    };
+
+    if model.fixed_view::<3, 3>(0, 0).determinant() <= 0f32 {
+        return Err("Matrix is not a rotation (determinant is not positive)".to_string());
+    }
+    Ok(model)
+}
+
+fn get_axis_angle_quaternion(axis: &(f32, f32, f32), angle: f32) -> UnitQuaternion<f32> {
+    match Unit::try_new(Vector3::new(axis.0, axis.1, axis.2), 1e-6) {
+        Some(axis) => UnitQuaternion::from_axis_angle(&axis, angle.to_radians()),
+        None => UnitQuaternion::identity(),
+    }
+}
+
+fn normalize_tuple(q: &(f32, f32, f32, f32)) -> (f32, f32, f32, f32) {
+    let quaternion = Quaternion::new(q.0, q.1, q.2, q.3);
+    if quaternion.norm_squared() > 1e-6 {
+        let n = quaternion.normalize();
+        (n.w, n.i, n.j, n.k)
+    } else {
+        *q
+    }
+}
+
+fn get_bezier_coordinate(p1: f32, p2: f32, s: f32) -> f32 {
+    let r = 1f32 - s;
+    3f32 * r * r * s * p1 + 3f32 * r * s * s * p2 + s * s * s
+}
+
+fn get_fnv1a_hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, b| {
+        (hash ^ *b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+fn deserialize_frames_count<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u32, D::Error> {
+    let frames_count = u32::deserialize(deserializer)?;
+    if !(2..=MAX_FRAMES_COUNT).contains(&frames_count) {
+        return Err(D::Error::custom(format!(
+            "frames_count must be between 2 and {}",
+            MAX_FRAMES_COUNT
+        )));
+    }
+    Ok(frames_count)
 }
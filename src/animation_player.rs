@@ -0,0 +1,108 @@
+use nalgebra::Matrix4;
+
+use movement_interpolation::animation::{make_fixed_steps, Animation};
+
+use crate::get_frame_discrepancies;
+
+pub struct AnimationPlayer {
+    animation: Option<Box<dyn Animation>>,
+    paused: bool,
+    suspended: bool,
+    accumulator: f64,
+}
+
+impl AnimationPlayer {
+    pub fn new() -> Self {
+        Self {
+            animation: None,
+            paused: false,
+            suspended: false,
+            accumulator: 0f64,
+        }
+    }
+
+    pub fn load(&mut self, animation: Box<dyn Animation>) -> &dyn Animation {
+        self.accumulator = 0f64;
+        self.animation = Some(animation);
+        self.update_clock();
+        self.animation.as_deref().unwrap()
+    }
+
+    pub fn get_animation(&self) -> Option<&dyn Animation> {
+        self.animation.as_deref()
+    }
+
+    pub fn toggle_paused(&mut self) {
+        self.paused = !self.paused;
+        self.update_clock();
+    }
+
+    pub fn set_suspended(&mut self, suspended: bool) {
+        if suspended != self.suspended {
+            self.suspended = suspended;
+            self.update_clock();
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn is_running(&self) -> bool {
+        !self.paused
+            && self
+                .animation
+                .as_ref()
+                .and_then(|a| a.get_progress())
+                .is_some_and(|p| p < 1f32)
+    }
+
+    pub fn step(&mut self, dx: f32) {
+        if let Some(animation) = self.animation.as_mut() {
+            if let Some(progress) = animation.get_progress() {
+                self.paused = true;
+                animation.seek(progress + dx);
+            }
+        }
+        self.update_clock();
+    }
+
+    pub fn set_playback_speed(&mut self, speed: f32) {
+        if let Some(animation) = self.animation.as_mut() {
+            animation.set_playback_speed(speed as f64);
+        }
+    }
+
+    pub fn update(&mut self, time_elapsed: f64) {
+        if self.paused || self.suspended {
+            return;
+        }
+        if let Some(animation) = self.animation.as_mut() {
+            make_fixed_steps(animation.as_mut(), &mut self.accumulator, time_elapsed);
+        }
+    }
+
+    pub fn seek(&mut self, x: f32) {
+        if let Some(animation) = self.animation.as_mut() {
+            animation.seek(x);
+        }
+    }
+
+    fn update_clock(&mut self) {
+        let paused = self.paused || self.suspended;
+        if let Some(animation) = self.animation.as_mut() {
+            animation.set_paused(paused);
+        }
+    }
+
+    pub fn frames_for(&self, method: usize) -> &[Matrix4<f32>] {
+        match &self.animation {
+            Some(animation) => animation.get_frames(method),
+            None => &[],
+        }
+    }
+
+    pub fn get_frame_discrepancies(&self, method: usize, reference_method: usize) -> Vec<f32> {
+        get_frame_discrepancies(self.frames_for(method), self.frames_for(reference_method))
+    }
+}
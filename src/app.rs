@@ -0,0 +1,1077 @@
+use std::path::Path;
+use std::time::Instant;
+
+use chrono::{DateTime, Local};
+use egui::pos2;
+use egui_dock::DockState;
+use egui_glium::EguiGlium;
+use glium::glutin::surface::WindowSurface;
+use glium::{Blend, Display, DrawParameters, Frame, Surface};
+use movement_interpolation::animation::{
+    get_gimbal_lock_intervals, EULER_METHOD_INDEX, QUATERNION_METHOD_INDEX,
+};
+use movement_interpolation::animation_data::{AngleInput, AnimationData, ObstacleShape, RunStamp};
+use nalgebra::{Matrix4, Vector3};
+use winit::application::ApplicationHandler;
+use winit::event::{ElementState, MouseButton, MouseScrollDelta, StartCause, WindowEvent};
+use winit::event_loop::{ActiveEventLoop, ControlFlow};
+use winit::keyboard::{Key, NamedKey};
+use winit::window::{Window, WindowId};
+
+use crate::animation_player::AnimationPlayer;
+use crate::appearance::Appearance;
+use crate::autosave::{get_autosave_path, Autosave};
+use crate::axes_drawer::AxesDrawer;
+use crate::camera::Camera;
+use crate::cli::Cli;
+use crate::figures::{generate_teaching_set, FigureRenderers};
+use crate::filmstrip::Filmstrip;
+use crate::frame_pacing::FramePacing;
+use crate::infinite_grid_drawer::InfiniteGridDrawer;
+use crate::mesh::Mesh;
+use crate::mesh_cache::MeshCache;
+use crate::mesh_drawer::{MeshDrawer, MeshShading};
+use crate::panel_dock::{get_default_dock_state, PanelTab};
+use crate::path_analysis::{BoundingBox, MethodDivergence, PathWarnings};
+use crate::path_drawer::PathDrawer;
+use crate::path_handle::PathHandle;
+use crate::presets::get_presets;
+use crate::reference_trajectory::ReferenceTrajectory;
+use crate::render_settings::{RenderMode, RenderSettings, SceneObjectType, ShadingMode};
+use crate::scene_view::SceneView;
+use crate::screenshot::{save_screenshot, ScreenshotRegion};
+use crate::session_log::{SessionAction, SessionLog, SessionReplay};
+use crate::shadow_map::ShadowMap;
+use crate::shortcuts::{ShortcutAction, Shortcuts};
+use crate::startup_config::StartupConfig;
+use crate::vertex::InstanceData;
+use crate::video_export::export_video;
+use crate::wizard::is_wizard_done;
+use crate::{
+    build_animation, build_ui, get_edited_angle_input, get_frame_instances, get_gimbal_lock_tints,
+    get_grid_viewport, get_matrix_lerp_ghost, get_numerical_warning, get_panes, get_pose_ghosts,
+    get_rotation_axis_model, is_comparison_grid, Pane, RunView, UiRequests, UiState,
+    KEYBOARD_ORBIT_STEP, LIVE_UPDATE_DEBOUNCE_MS, MATRIX_LERP_GHOST_ALPHA, OBSTACLE_ALPHA,
+    OBSTACLE_COLOR, PATH_SAMPLES_COUNT, POSE_GHOST_ALPHA, REFERENCE_TRAJECTORY_COLOR,
+};
+
+pub struct App {
+    width: u32,
+    height: u32,
+    window: Window,
+    display: Display<WindowSurface>,
+    egui_glium: EguiGlium,
+    drawing_parameters: DrawParameters<'static>,
+    mouse_position: (f64, f64),
+    cameras: Vec<Camera>,
+    active_camera: usize,
+    camera_move_button_pressed: bool,
+    camera_pan_button_pressed: bool,
+    shift_pressed: bool,
+    viewport_split: f32,
+    dock_state: DockState<PanelTab>,
+    appearance: Appearance,
+    saved_appearance: Appearance,
+    frame_pacing: FramePacing,
+    saved_frame_pacing: FramePacing,
+    next_redraw: Option<Instant>,
+    focused: bool,
+    occluded: bool,
+    infinite_grid_drawer: InfiniteGridDrawer,
+    shadow_map: ShadowMap,
+    axes_drawer: AxesDrawer,
+    animation_data: AnimationData,
+    render_settings: RenderSettings,
+    player: AnimationPlayer,
+    mesh_cache: MeshCache,
+    obstacle_box: Mesh,
+    obstacle_sphere: Mesh,
+    rotation_axis_arrow: Mesh,
+    mesh_drawer: MeshDrawer,
+    path_drawer: PathDrawer,
+    filmstrip: Filmstrip,
+    filmstrip_outdated: bool,
+    displayed_models: [Matrix4<f32>; 2],
+    method_paths: Vec<Vec<Vector3<f32>>>,
+    status_message: Option<String>,
+    path_warnings: Option<PathWarnings>,
+    run_stamp: Option<RunStamp>,
+    reference_trajectory: Option<ReferenceTrajectory>,
+    bounding_box: Option<BoundingBox>,
+    numerical_warning: Option<String>,
+    max_divergence: Option<MethodDivergence>,
+    gimbal_lock_intervals: Vec<(f32, f32)>,
+    path_handle: PathHandle,
+    watched_animation_data: AnimationData,
+    last_change_time: Option<DateTime<Local>>,
+    last_angle_input: Option<AngleInput>,
+    ghost_angle_input: AngleInput,
+    session_log: SessionLog,
+    session_replay: Option<SessionReplay>,
+    pending_run: Option<AngleInput>,
+    shortcuts: Shortcuts,
+    saved_shortcuts: Shortcuts,
+    shortcut_capture: Option<ShortcutAction>,
+    shortcuts_open: bool,
+    screenshot_request: Option<ScreenshotRegion>,
+    wizard_open: bool,
+    previous_time: DateTime<Local>,
+}
+
+impl App {
+    pub fn new(
+        window: Window,
+        display: Display<WindowSurface>,
+        mut egui_glium: EguiGlium,
+        cli: &Cli,
+        startup_config: StartupConfig,
+        frame_pacing: FramePacing,
+    ) -> Self {
+        let (width, height) = window.inner_size().into();
+        let drawing_parameters = DrawParameters {
+            depth: glium::Depth {
+                test: glium::draw_parameters::DepthTest::IfLess,
+                write: true,
+                ..Default::default()
+            },
+            backface_culling: glium::draw_parameters::BackfaceCullingMode::CullClockwise,
+            blend: Blend::alpha_blending(),
+            ..Default::default()
+        };
+
+        let autosave = (cli.config.is_none() && cli.replay.is_none())
+            .then(get_autosave_path)
+            .flatten()
+            .and_then(|path| Autosave::load(&path).ok());
+        let (animation_data, render_settings, camera_states, viewport_split, dock_state) =
+            match autosave {
+                Some(autosave) => (
+                    autosave.animation_data,
+                    autosave.render_settings,
+                    autosave.cameras,
+                    autosave.viewport_split,
+                    autosave.dock_state,
+                ),
+                None => (
+                    startup_config.animation,
+                    startup_config.render,
+                    startup_config.cameras,
+                    startup_config.viewport_split,
+                    get_default_dock_state(),
+                ),
+            };
+        let mut cameras = vec![Camera::new(), Camera::new()];
+        cameras.resize_with(camera_states.len().max(2), Camera::new);
+        for (camera, state) in cameras.iter_mut().zip(&camera_states) {
+            camera.set_state(state);
+        }
+        let filmstrip = Filmstrip::new(&display, &mut egui_glium);
+        let session_replay = cli.replay.as_ref().map(|path| {
+            SessionReplay::load(path, Local::now()).unwrap_or_else(|error| {
+                eprintln!("Session replay load failed: {}", error);
+                std::process::exit(1);
+            })
+        });
+        let wizard_open =
+            cli.wizard || (session_replay.is_none() && cli.config.is_none() && !is_wizard_done());
+        let appearance = Appearance::load().unwrap_or_default();
+        egui_glium.egui_ctx.set_visuals(appearance.get_visuals());
+        let shortcuts = Shortcuts::load().unwrap_or_default();
+
+        Self {
+            width,
+            height,
+            drawing_parameters,
+            mouse_position: (0.0, 0.0),
+            cameras,
+            active_camera: 0,
+            camera_move_button_pressed: false,
+            camera_pan_button_pressed: false,
+            shift_pressed: false,
+            viewport_split,
+            dock_state,
+            saved_appearance: appearance.clone(),
+            appearance,
+            saved_frame_pacing: frame_pacing.clone(),
+            frame_pacing,
+            next_redraw: None,
+            focused: true,
+            occluded: false,
+            infinite_grid_drawer: InfiniteGridDrawer::new(&display),
+            shadow_map: ShadowMap::new(&display),
+            axes_drawer: AxesDrawer::new(&display),
+            watched_animation_data: animation_data.clone(),
+            animation_data,
+            render_settings,
+            player: AnimationPlayer::new(),
+            mesh_cache: MeshCache::new(),
+            obstacle_box: Mesh::generate_cube(OBSTACLE_COLOR, &display),
+            obstacle_sphere: Mesh::generate_sphere(16, OBSTACLE_COLOR, &display),
+            rotation_axis_arrow: Mesh::generate_arrow(10, &display),
+            mesh_drawer: MeshDrawer::new(&display),
+            path_drawer: PathDrawer::new(&display),
+            filmstrip,
+            filmstrip_outdated: false,
+            displayed_models: [Matrix4::identity(), Matrix4::identity()],
+            method_paths: Vec::new(),
+            status_message: None,
+            path_warnings: None,
+            run_stamp: None,
+            reference_trajectory: None,
+            bounding_box: None,
+            numerical_warning: None,
+            max_divergence: None,
+            gimbal_lock_intervals: Vec::new(),
+            path_handle: PathHandle::new(),
+            last_change_time: None,
+            last_angle_input: None,
+            ghost_angle_input: AngleInput::default(),
+            session_log: SessionLog::new(Local::now()),
+            session_replay,
+            pending_run: startup_config.run,
+            saved_shortcuts: shortcuts.clone(),
+            shortcuts,
+            shortcut_capture: None,
+            shortcuts_open: false,
+            screenshot_request: None,
+            wizard_open,
+            previous_time: Local::now(),
+            window,
+            display,
+            egui_glium,
+        }
+    }
+
+    fn handle_shortcut(&mut self, action: &ShortcutAction) {
+        match action {
+            ShortcutAction::Run => {
+                self.pending_run = Some(self.last_angle_input.clone().unwrap_or_default());
+            }
+            ShortcutAction::ToggleCamera => {
+                self.camera_move_button_pressed = !self.camera_move_button_pressed;
+            }
+            ShortcutAction::PlayPause => self.player.toggle_paused(),
+            ShortcutAction::StepBackward => {
+                self.player.step(-self.animation_data.get_frame_step());
+            }
+            ShortcutAction::StepForward => {
+                self.player.step(self.animation_data.get_frame_step());
+            }
+            ShortcutAction::Screenshot => {
+                self.screenshot_request = Some(ScreenshotRegion::Window);
+            }
+            ShortcutAction::Camera(camera_preset) => {
+                self.cameras[self.active_camera]
+                    .snap_to(camera_preset, self.render_settings.animate_camera_presets);
+            }
+            ShortcutAction::Preset(i) => {
+                if let Some((_, preset)) = get_presets().into_iter().nth(*i) {
+                    self.animation_data = preset;
+                }
+            }
+        }
+    }
+
+    fn get_panes(&self) -> Vec<Pane> {
+        get_panes(
+            self.player.get_animation(),
+            &self.animation_data,
+            &self.render_settings,
+            self.viewport_split,
+        )
+    }
+
+    fn draw_pane(
+        &mut self,
+        target: &mut Frame,
+        index: usize,
+        pane: &Pane,
+        pose_ghosts: &Option<[[Matrix4<f32>; 2]; 2]>,
+        matrix_lerp_ghost: &Option<Matrix4<f32>>,
+    ) {
+        let mesh = self.mesh_cache.get(
+            &self.render_settings.scene_object_type,
+            &self.render_settings.block_geometry,
+            &self.display,
+        );
+        let camera = &self.cameras[index];
+        let viewport = get_grid_viewport(&pane.cell, self.width, self.height);
+        let perspective = self.render_settings.projection.get_matrix(
+            viewport.width as f32 / viewport.height as f32,
+            *camera.distant(),
+        );
+        let drawing_parameters = DrawParameters {
+            viewport: Some(viewport),
+            ..self.drawing_parameters.clone()
+        };
+        let is_euler = pane.method == EULER_METHOD_INDEX;
+        let lighting = if is_euler {
+            &self.render_settings.euler_lighting
+        } else {
+            &self.render_settings.quaternion_lighting
+        };
+        let scene = SceneView::new(
+            &perspective,
+            camera.view(),
+            camera.get_position(),
+            &drawing_parameters,
+        );
+        let shading = MeshShading::new(
+            &self.render_settings.up_axis,
+            lighting,
+            &self.render_settings.render_mode,
+        );
+
+        if let Some(a) = self.player.get_animation() {
+            let frames = self.player.frames_for(pane.method);
+            let (reference_method, tints) = if is_euler {
+                (
+                    QUATERNION_METHOD_INDEX,
+                    get_gimbal_lock_tints(frames, &self.animation_data, &self.render_settings),
+                )
+            } else {
+                (EULER_METHOD_INDEX, vec![0f32; frames.len()])
+            };
+            let instances = get_frame_instances(
+                frames,
+                &self
+                    .player
+                    .get_frame_discrepancies(pane.method, reference_method),
+                &tints,
+                self.render_settings.first_frame_alpha,
+            );
+            if self.render_settings.grid.shadows {
+                self.shadow_map.render(
+                    &self.display,
+                    mesh,
+                    &instances,
+                    &self.render_settings.up_axis,
+                    lighting,
+                );
+            }
+            self.mesh_drawer.draw_instanced(
+                target,
+                &self.display,
+                &scene,
+                &instances,
+                &MeshShading {
+                    discrepancy_shading: self.render_settings.shading_mode
+                        == ShadingMode::Discrepancy,
+                    ..shading
+                },
+                mesh,
+            );
+
+            if let Some(model) = (self.render_settings.display_rotation_axis
+                && pane.method == QUATERNION_METHOD_INDEX)
+                .then(|| get_rotation_axis_model(a, *mesh.radius()))
+                .flatten()
+            {
+                self.mesh_drawer.draw(
+                    target,
+                    &scene,
+                    &model,
+                    1f32,
+                    &MeshShading {
+                        render_mode: &RenderMode::Shaded,
+                        ..shading
+                    },
+                    &self.rotation_axis_arrow,
+                );
+            }
+
+            if let Some(path) = self
+                .method_paths
+                .get(pane.method)
+                .filter(|_| self.render_settings.display_path)
+            {
+                self.path_drawer
+                    .draw(target, &self.display, &scene, path, [1f32, 1f32, 0f32]);
+            }
+
+            if let Some(path_warnings) = &self.path_warnings {
+                self.path_drawer.draw_markers(
+                    target,
+                    &self.display,
+                    &scene,
+                    &path_warnings.get_markers(),
+                    [1f32, 0f32, 0f32],
+                );
+            }
+        } else {
+            if self.render_settings.grid.shadows {
+                self.shadow_map.render(
+                    &self.display,
+                    mesh,
+                    &[InstanceData::new(
+                        Matrix4::identity().data.0,
+                        1f32,
+                        0f32,
+                        0f32,
+                    )],
+                    &self.render_settings.up_axis,
+                    lighting,
+                );
+            }
+            self.mesh_drawer
+                .draw(target, &scene, &Matrix4::identity(), 1f32, &shading, mesh);
+        }
+
+        if let Some(reference_trajectory) = &self.reference_trajectory {
+            self.path_drawer.draw(
+                target,
+                &self.display,
+                &scene,
+                &reference_trajectory.get_path(),
+                REFERENCE_TRAJECTORY_COLOR,
+            );
+        }
+
+        for obstacle in &self.animation_data.obstacles {
+            self.mesh_drawer.draw(
+                target,
+                &scene,
+                &obstacle.get_model(),
+                OBSTACLE_ALPHA,
+                &MeshShading {
+                    render_mode: &RenderMode::Shaded,
+                    ..shading
+                },
+                match obstacle.shape {
+                    ObstacleShape::Box => &self.obstacle_box,
+                    ObstacleShape::Sphere => &self.obstacle_sphere,
+                },
+            );
+        }
+
+        for model in pose_ghosts.iter().flat_map(|g| &g[is_euler as usize]) {
+            self.mesh_drawer
+                .draw(target, &scene, model, POSE_GHOST_ALPHA, &shading, mesh);
+        }
+
+        if let Some(model) = matrix_lerp_ghost
+            .as_ref()
+            .filter(|_| pane.method == QUATERNION_METHOD_INDEX)
+        {
+            self.mesh_drawer.draw(
+                target,
+                &scene,
+                model,
+                MATRIX_LERP_GHOST_ALPHA,
+                &MeshShading {
+                    lighting: &self.render_settings.euler_lighting,
+                    ..shading
+                },
+                mesh,
+            );
+        }
+
+        if self.render_settings.display_axes {
+            self.axes_drawer
+                .draw(target, &perspective, camera.view(), &drawing_parameters);
+        }
+
+        self.infinite_grid_drawer.draw(
+            target,
+            &SceneView {
+                view: camera.grid_view(),
+                ..scene
+            },
+            &self.render_settings.projection,
+            &self.render_settings.grid,
+            &self.shadow_map,
+        );
+    }
+
+    fn save_session(&self) {
+        let Some(path) = get_autosave_path() else {
+            return;
+        };
+        let autosave = Autosave {
+            animation_data: self.animation_data.clone(),
+            render_settings: self.render_settings.clone(),
+            cameras: self.cameras.iter().map(|c| c.get_state()).collect(),
+            viewport_split: self.viewport_split,
+            dock_state: self.dock_state.clone(),
+        };
+        if let Err(error) = autosave.save(&path) {
+            eprintln!("Session autosave failed: {}", error);
+        }
+    }
+
+    fn redraw(&mut self) {
+        let current_time = Local::now();
+        let duration = current_time - self.previous_time;
+        let duration_in_seconds = duration.num_microseconds().unwrap_or(1) as f64 / 1_000_000.0;
+        let fps = 1.0 / duration_in_seconds;
+        self.previous_time = current_time;
+
+        let filmstrip_thumbnails = self.filmstrip.get_thumbnails();
+        let requests = build_ui(
+            &mut self.egui_glium,
+            &self.window,
+            &mut self.dock_state,
+            &mut UiState {
+                animation_data: &mut self.animation_data,
+                render_settings: &mut self.render_settings,
+                status_message: &mut self.status_message,
+                reference_trajectory: &mut self.reference_trajectory,
+                viewport_split: &mut self.viewport_split,
+                appearance: &mut self.appearance,
+                frame_pacing: &mut self.frame_pacing,
+                shortcuts: &mut self.shortcuts,
+                shortcut_capture: &mut self.shortcut_capture,
+                shortcuts_open: &mut self.shortcuts_open,
+                wizard_open: &mut self.wizard_open,
+                screenshot_request: &mut self.screenshot_request,
+            },
+            &RunView {
+                player: &self.player,
+                displayed_models: &self.displayed_models,
+                path_warnings: &self.path_warnings,
+                run_stamp: &self.run_stamp,
+                bounding_box: &self.bounding_box,
+                numerical_warning: &self.numerical_warning,
+                max_divergence: &self.max_divergence,
+                gimbal_lock_intervals: &self.gimbal_lock_intervals,
+                last_angle_input: &self.last_angle_input,
+                ghost_angle_input: &self.ghost_angle_input,
+                filmstrip_thumbnails: &filmstrip_thumbnails,
+                session_log: &self.session_log,
+                path_handle: &self.path_handle,
+                fps,
+            },
+        );
+        let UiRequests {
+            run: mut run_request,
+            fit_view,
+            camera_preset,
+            teaching_set_requested,
+            video_export_requested,
+            mesh_load_requested,
+            reset_requested,
+            seek,
+            playback_action,
+        } = requests;
+
+        if let Some(angle_input) = self.pending_run.take() {
+            run_request = Some(angle_input);
+        }
+
+        if let Some(x) = seek {
+            self.player.seek(x);
+        }
+
+        if let Some(action) = playback_action {
+            self.handle_shortcut(&action);
+        }
+
+        if self.appearance != self.saved_appearance {
+            self.egui_glium
+                .egui_ctx
+                .set_visuals(self.appearance.get_visuals());
+            if let Err(error) = self.appearance.save() {
+                eprintln!("Appearance save failed: {}", error);
+            }
+            self.saved_appearance = self.appearance.clone();
+        }
+
+        if self.frame_pacing != self.saved_frame_pacing {
+            if let Err(error) = self.frame_pacing.save() {
+                eprintln!("Frame pacing save failed: {}", error);
+            }
+            self.saved_frame_pacing = self.frame_pacing.clone();
+        }
+
+        if reset_requested {
+            self.animation_data = AnimationData::new();
+            self.render_settings = RenderSettings::new();
+            self.cameras = vec![Camera::new(), Camera::new()];
+            self.viewport_split = 0.5f32;
+            self.dock_state = get_default_dock_state();
+            self.status_message = Some("Reset to defaults".to_string());
+        }
+
+        if self.shortcuts != self.saved_shortcuts {
+            if let Err(error) = self.shortcuts.save() {
+                eprintln!("Shortcuts save failed: {}", error);
+            }
+            self.saved_shortcuts = self.shortcuts.clone();
+        }
+
+        if mesh_load_requested {
+            self.status_message = Some(
+                match Mesh::load_obj(
+                    Path::new(&self.render_settings.custom_mesh_path),
+                    &self.display,
+                ) {
+                    Ok(mesh) => {
+                        self.mesh_cache.set_custom_mesh(mesh);
+                        self.render_settings.scene_object_type = SceneObjectType::Custom;
+                        "Mesh loaded".to_string()
+                    }
+                    Err(e) => format!("Mesh load failed: {}", e),
+                },
+            );
+        }
+
+        if let Some(replay) = self.session_replay.as_mut() {
+            for action in replay.poll(current_time) {
+                match action {
+                    SessionAction::SetParameters(data) => self.animation_data = *data,
+                    SessionAction::Run(angle_input) => run_request = Some(angle_input),
+                    SessionAction::MoveCamera(index, state) => {
+                        if self.cameras.len() <= index {
+                            self.cameras.resize_with(index + 1, Camera::new);
+                        }
+                        self.cameras[index].set_state(&state)
+                    }
+                }
+            }
+            if replay.is_finished() {
+                self.session_replay = None;
+                self.status_message = Some("Session replay finished".to_string());
+            }
+        }
+
+        let panes = self.get_panes();
+        if self.cameras.len() < panes.len() {
+            self.cameras.resize_with(panes.len(), Camera::new);
+        }
+
+        if let Some(camera_preset) = &camera_preset {
+            for camera in self.cameras.iter_mut() {
+                camera.snap_to(camera_preset, self.render_settings.animate_camera_presets);
+            }
+        }
+        for camera in self.cameras.iter_mut() {
+            camera.update(duration_in_seconds as f32);
+            camera.set_scene(self.render_settings.up_axis.get_scene_matrix());
+        }
+
+        if fit_view {
+            if let Some(bounding_box) = &self.bounding_box {
+                for (camera, pane) in self.cameras.iter_mut().zip(&panes) {
+                    let viewport = get_grid_viewport(&pane.cell, self.width, self.height);
+                    camera.fit(
+                        bounding_box,
+                        viewport.width as f32 / viewport.height as f32,
+                        self.render_settings.projection.fov.to_radians(),
+                    );
+                }
+            }
+        }
+
+        if self.animation_data != self.watched_animation_data {
+            if let Some(angle_input) =
+                get_edited_angle_input(&self.watched_animation_data, &self.animation_data)
+            {
+                self.ghost_angle_input = angle_input;
+            }
+            self.watched_animation_data = self.animation_data.clone();
+            self.last_change_time = Some(current_time);
+        }
+        let debounced = self
+            .last_change_time
+            .is_some_and(|t| (current_time - t).num_milliseconds() >= LIVE_UPDATE_DEBOUNCE_MS);
+        if debounced {
+            self.session_log
+                .record_parameters(current_time, &self.animation_data);
+        }
+        let methods_missing = is_comparison_grid(&self.render_settings)
+            && self.player.get_animation().is_some_and(|a| {
+                self.render_settings
+                    .comparison_methods
+                    .iter()
+                    .any(|m| !a.get_methods().contains(m))
+            });
+        let run_request = run_request.or_else(|| {
+            if (self.render_settings.live_update && debounced) || methods_missing {
+                self.last_angle_input.clone()
+            } else {
+                None
+            }
+        });
+
+        if !self.camera_move_button_pressed && !self.camera_pan_button_pressed {
+            for (i, camera) in self
+                .cameras
+                .iter()
+                .enumerate()
+                .filter(|(_, c)| !c.is_transitioning())
+            {
+                self.session_log
+                    .record_camera(current_time, i, camera.get_state());
+            }
+        }
+
+        if let Some(angle_input) = run_request {
+            self.session_log
+                .record_parameters(current_time, &self.animation_data);
+            self.session_log
+                .record(current_time, SessionAction::Run(angle_input.clone()));
+            let comparison_methods = if is_comparison_grid(&self.render_settings) {
+                self.render_settings.comparison_methods.as_slice()
+            } else {
+                &[]
+            };
+            let animation = self.player.load(build_animation(
+                &self.animation_data,
+                &angle_input,
+                comparison_methods,
+            ));
+            self.ghost_angle_input = angle_input.clone();
+            self.last_angle_input = Some(angle_input);
+            self.last_change_time = None;
+            self.run_stamp = Some(self.animation_data.get_run_stamp());
+            self.filmstrip_outdated = true;
+            self.numerical_warning = get_numerical_warning(animation);
+            self.max_divergence = Some(MethodDivergence::get_max(animation, PATH_SAMPLES_COUNT));
+            self.gimbal_lock_intervals = get_gimbal_lock_intervals(
+                animation,
+                &self.animation_data.euler_order,
+                self.animation_data.gimbal_lock_threshold.to_radians(),
+                PATH_SAMPLES_COUNT,
+            );
+            self.method_paths = (0..animation.get_methods().len())
+                .map(|i| animation.get_path(i, PATH_SAMPLES_COUNT))
+                .collect();
+            self.path_warnings = Some(PathWarnings::analyze(
+                &self.method_paths[QUATERNION_METHOD_INDEX],
+                &self.animation_data.obstacles,
+                &self.render_settings.up_axis.get_up(),
+            ));
+            if let Some(reference_trajectory) = self.reference_trajectory.as_mut() {
+                reference_trajectory.update_errors(animation);
+            }
+            self.path_handle.set_path(animation, PATH_SAMPLES_COUNT);
+        }
+
+        let idle = !self.player.is_running() && !self.cameras.iter().any(|c| c.is_transitioning());
+        let inactive =
+            !self.focused || self.occluded || self.window.is_minimized().unwrap_or(false);
+        match self.frame_pacing.get_frame_time(idle) {
+            _ if inactive && self.frame_pacing.pause_when_inactive => self.next_redraw = None,
+            Some(frame_time) => self.next_redraw = Some(Instant::now() + frame_time),
+            None => {
+                self.next_redraw = None;
+                self.window.request_redraw();
+            }
+        }
+
+        let mesh = self.mesh_cache.get(
+            &self.render_settings.scene_object_type,
+            &self.render_settings.block_geometry,
+            &self.display,
+        );
+        if self.filmstrip_outdated {
+            if let Some(a) = self.player.get_animation() {
+                self.filmstrip.render(
+                    &self.display,
+                    a,
+                    &SceneView::new(
+                        &self
+                            .render_settings
+                            .projection
+                            .get_matrix(1f32, *self.cameras[0].distant()),
+                        self.cameras[0].view(),
+                        self.cameras[0].get_position(),
+                        &self.drawing_parameters,
+                    ),
+                    &MeshShading::new(
+                        &self.render_settings.up_axis,
+                        &self.render_settings.quaternion_lighting,
+                        &self.render_settings.render_mode,
+                    ),
+                    mesh,
+                    &self.mesh_drawer,
+                );
+            }
+            self.filmstrip_outdated = false;
+        }
+        if teaching_set_requested {
+            let prefix = format!("teaching_{}", Local::now().format("%Y%m%d_%H%M%S"));
+            self.status_message = Some(
+                match generate_teaching_set(
+                    &self.display,
+                    &self.animation_data,
+                    &self.render_settings,
+                    self.last_angle_input.as_ref().unwrap(),
+                    &self.cameras[0],
+                    &FigureRenderers {
+                        mesh,
+                        mesh_drawer: &self.mesh_drawer,
+                        path_drawer: &self.path_drawer,
+                        infinite_grid_drawer: &self.infinite_grid_drawer,
+                        shadow_map: &self.shadow_map,
+                    },
+                    &prefix,
+                ) {
+                    Ok(paths) => format!(
+                        "Teaching set saved as {}_*.png ({} figures)",
+                        prefix,
+                        paths.len()
+                    ),
+                    Err(e) => format!("Teaching set generation failed: {}", e),
+                },
+            );
+        }
+        if video_export_requested {
+            self.status_message = Some(
+                match export_video(
+                    &self.display,
+                    &self.animation_data,
+                    &self.render_settings,
+                    self.last_angle_input.as_ref().unwrap(),
+                    &self.cameras,
+                    &self.appearance,
+                    &FigureRenderers {
+                        mesh,
+                        mesh_drawer: &self.mesh_drawer,
+                        path_drawer: &self.path_drawer,
+                        infinite_grid_drawer: &self.infinite_grid_drawer,
+                        shadow_map: &self.shadow_map,
+                    },
+                ) {
+                    Ok(path) => format!("Video exported to {}", path.display()),
+                    Err(e) => format!("Video export failed: {}", e),
+                },
+            );
+        }
+        self.bounding_box = self
+            .method_paths
+            .first()
+            .map(|path| BoundingBox::from_path(path, *mesh.radius()));
+
+        let pose_ghosts = self
+            .render_settings
+            .display_pose_ghosts
+            .then(|| get_pose_ghosts(&self.animation_data, &self.ghost_angle_input));
+        let matrix_lerp_ghost = self
+            .player
+            .get_animation()
+            .and_then(|a| a.get_progress())
+            .filter(|_| self.render_settings.display_matrix_lerp_ghost)
+            .map(|x| get_matrix_lerp_ghost(&self.animation_data, &self.ghost_angle_input, x));
+
+        self.player
+            .set_playback_speed(self.animation_data.playback_speed);
+        self.player.update(duration_in_seconds);
+        self.player
+            .set_suspended(inactive && self.frame_pacing.pause_when_inactive);
+
+        let mut target = self.display.draw();
+
+        target.clear_color_and_depth(self.appearance.get_clear_color(), 1.0);
+
+        for (model, method) in self
+            .displayed_models
+            .iter_mut()
+            .zip([QUATERNION_METHOD_INDEX, EULER_METHOD_INDEX])
+        {
+            if let Some(frame) = self.player.frames_for(method).last() {
+                *model = *frame;
+            }
+        }
+
+        let panes = self.get_panes();
+        if self.cameras.len() < panes.len() {
+            self.cameras.resize_with(panes.len(), Camera::new);
+        }
+        let pixels_per_point = self.egui_glium.egui_ctx.pixels_per_point();
+        let screen_size = egui::vec2(self.width as f32, self.height as f32) / pixels_per_point;
+        self.path_handle.set_viewports_count(panes.len());
+        for (i, pane) in panes.iter().enumerate() {
+            let viewport = get_grid_viewport(&pane.cell, self.width, self.height);
+            self.path_handle.set_viewport(
+                i,
+                self.render_settings.projection.get_matrix(
+                    viewport.width as f32 / viewport.height as f32,
+                    *self.cameras[i].distant(),
+                ) * self.cameras[i].view(),
+                egui::Rect::from_min_max(
+                    (pane.cell.min.to_vec2() * screen_size).to_pos2(),
+                    (pane.cell.max.to_vec2() * screen_size).to_pos2(),
+                ),
+            );
+            self.draw_pane(&mut target, i, pane, &pose_ghosts, &matrix_lerp_ghost);
+        }
+
+        self.egui_glium.paint(&self.display, &mut target);
+
+        target.finish().unwrap();
+
+        if let Some(region) = self.screenshot_request.take() {
+            let name = format!("screenshot_{}.png", Local::now().format("%Y%m%d_%H%M%S"));
+            let panes = self.get_panes();
+            let cell = match region {
+                ScreenshotRegion::Window => None,
+                ScreenshotRegion::Pane(index) => panes.get(index).map(|pane| &pane.cell),
+            };
+            self.status_message = Some(match save_screenshot(&self.display, cell, &name) {
+                Ok(path) => format!("Screenshot saved to {}", path.display()),
+                Err(e) => format!("Screenshot failed: {}", e),
+            });
+        }
+    }
+}
+
+impl ApplicationHandler<egui_winit::accesskit_winit::Event> for App {
+    fn resumed(&mut self, _event_loop: &ActiveEventLoop) {}
+
+    fn new_events(&mut self, _event_loop: &ActiveEventLoop, cause: StartCause) {
+        if let StartCause::ResumeTimeReached { .. } = cause {
+            self.next_redraw = None;
+            self.window.request_redraw();
+        }
+    }
+
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        match self.next_redraw {
+            Some(next_redraw) => event_loop.set_control_flow(ControlFlow::WaitUntil(next_redraw)),
+            None => event_loop.set_control_flow(ControlFlow::Wait),
+        }
+    }
+
+    fn window_event(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        _window_id: WindowId,
+        event: WindowEvent,
+    ) {
+        match &event {
+            WindowEvent::RedrawRequested => self.redraw(),
+            WindowEvent::CloseRequested | WindowEvent::Destroyed => {
+                self.save_session();
+                event_loop.exit();
+            }
+            WindowEvent::Focused(focused) => {
+                self.focused = *focused;
+                self.window.request_redraw();
+            }
+            WindowEvent::Occluded(occluded) => {
+                self.occluded = *occluded;
+                self.window.request_redraw();
+            }
+            WindowEvent::Resized(new_size) => {
+                self.display.resize((*new_size).into());
+                self.width = new_size.width;
+                self.height = new_size.height;
+            }
+            WindowEvent::CursorMoved { position, .. } => {
+                let delta = (
+                    position.x - self.mouse_position.0,
+                    position.y - self.mouse_position.1,
+                );
+                self.mouse_position = (position.x, position.y);
+                if self.camera_pan_button_pressed
+                    || (self.camera_move_button_pressed && self.shift_pressed)
+                {
+                    self.cameras[self.active_camera].pan(delta.0 as f32, delta.1 as f32);
+                } else if self.camera_move_button_pressed {
+                    self.cameras[self.active_camera]
+                        .orbit(delta.1 as f32 * 0.01, delta.0 as f32 * 0.01);
+                } else {
+                    let point = pos2(
+                        position.x as f32 / self.width as f32,
+                        position.y as f32 / self.height as f32,
+                    );
+                    self.active_camera = self
+                        .get_panes()
+                        .iter()
+                        .position(|p| p.cell.contains(point))
+                        .unwrap_or(0)
+                        .min(self.cameras.len() - 1);
+                }
+            }
+            WindowEvent::MouseInput { state, button, .. } => {
+                if *button == MouseButton::Middle {
+                    self.camera_move_button_pressed = *state == ElementState::Pressed;
+                }
+                if *button == MouseButton::Right {
+                    self.camera_pan_button_pressed = *state == ElementState::Pressed
+                        && !self.egui_glium.egui_ctx.is_pointer_over_area();
+                }
+            }
+            WindowEvent::ModifiersChanged(modifiers) => {
+                self.shift_pressed = modifiers.state().shift_key();
+            }
+            WindowEvent::KeyboardInput {
+                device_id: _,
+                event,
+                is_synthetic: _,
+            } => {
+                if let Some(action) = self.shortcut_capture.as_ref() {
+                    if event.state.is_pressed() {
+                        if event.logical_key != Key::Named(NamedKey::Escape) {
+                            self.shortcuts.bind(action, &event.logical_key);
+                        }
+                        self.shortcut_capture = None;
+                        self.window.request_redraw();
+                    }
+                    return;
+                }
+
+                let ui_focused = self.egui_glium.egui_ctx.memory(|m| m.focused().is_some());
+                if event.state.is_pressed() && !ui_focused {
+                    let (orbit, zoom) = match &event.logical_key {
+                        Key::Named(NamedKey::ArrowLeft) => ((0f32, -1f32), 0f32),
+                        Key::Named(NamedKey::ArrowRight) => ((0f32, 1f32), 0f32),
+                        Key::Named(NamedKey::ArrowUp) => ((-1f32, 0f32), 0f32),
+                        Key::Named(NamedKey::ArrowDown) => ((1f32, 0f32), 0f32),
+                        key if *key == "+" || *key == "=" => ((0f32, 0f32), -1f32),
+                        key if *key == "-" => ((0f32, 0f32), 1f32),
+                        _ => ((0f32, 0f32), 0f32),
+                    };
+                    if let Some(action) = self
+                        .shortcuts
+                        .get_action(&event.logical_key)
+                        .filter(|_| !event.repeat)
+                    {
+                        self.handle_shortcut(&action);
+                        self.window.request_redraw();
+                    }
+                    if orbit != (0f32, 0f32) || zoom != 0f32 {
+                        self.cameras[self.active_camera]
+                            .orbit(orbit.0 * KEYBOARD_ORBIT_STEP, orbit.1 * KEYBOARD_ORBIT_STEP);
+                        self.cameras[self.active_camera].zoom(zoom * 0.1);
+                        self.window.request_redraw();
+                    }
+                }
+            }
+            WindowEvent::MouseWheel {
+                delta: MouseScrollDelta::LineDelta(_x, y),
+                ..
+            } => {
+                self.cameras[self.active_camera].zoom(-y * 0.1);
+            }
+            WindowEvent::PinchGesture { delta, .. } => {
+                self.cameras[self.active_camera].zoom(-*delta as f32 * 3.0);
+            }
+            _ => {}
+        }
+
+        let event_response = self.egui_glium.on_event(&self.window, &event);
+
+        if event_response.repaint {
+            self.window.request_redraw();
+        }
+    }
+
+    fn user_event(
+        &mut self,
+        _event_loop: &ActiveEventLoop,
+        event: egui_winit::accesskit_winit::Event,
+    ) {
+        use egui_winit::accesskit_winit::WindowEvent;
+        match event.window_event {
+            WindowEvent::InitialTreeRequested => self.egui_glium.egui_ctx.enable_accesskit(),
+            WindowEvent::ActionRequested(request) => self
+                .egui_glium
+                .egui_winit
+                .on_accesskit_action_request(request),
+            WindowEvent::AccessibilityDeactivated => self.egui_glium.egui_ctx.disable_accesskit(),
+        }
+        self.window.request_redraw();
+    }
+}
@@ -0,0 +1,55 @@
+use egui::Visuals;
+use serde::{Deserialize, Serialize};
+
+use crate::config_dir::{get_config_path, load_json, save_json};
+
+const APPEARANCE_FILE: &str = "appearance.json";
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum Theme {
+    #[default]
+    Dark,
+    Light,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Appearance {
+    pub background_color: [f32; 3],
+    pub theme: Theme,
+}
+
+impl Appearance {
+    pub fn load() -> std::io::Result<Self> {
+        load_json(&get_config_path(APPEARANCE_FILE)?)
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        save_json(&get_config_path(APPEARANCE_FILE)?, self)
+    }
+
+    pub fn get_visuals(&self) -> Visuals {
+        match self.theme {
+            Theme::Dark => Visuals::dark(),
+            Theme::Light => Visuals::light(),
+        }
+    }
+
+    pub fn get_clear_color(&self) -> (f32, f32, f32, f32) {
+        (
+            self.background_color[0],
+            self.background_color[1],
+            self.background_color[2],
+            1f32,
+        )
+    }
+}
+
+impl Default for Appearance {
+    fn default() -> Self {
+        Self {
+            background_color: [0f32, 0f32, 0f32],
+            theme: Theme::Dark,
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use std::f32::consts::PI;
+
+use crate::render_settings::UpAxis;
+use egui::{pos2, vec2, Align2, Color32, FontId, Painter, Pos2, Shape, Stroke, Vec2};
+use nalgebra::{Matrix4, Vector3};
+
+const SKY_COLOR: Color32 = Color32::from_rgb(70, 130, 200);
+const GROUND_COLOR: Color32 = Color32::from_rgb(140, 90, 40);
+
+#[derive(Debug, Clone)]
+pub struct Attitude {
+    roll: f32,
+    pitch: f32,
+    heading: f32,
+}
+
+impl Attitude {
+    pub fn from_model(model: &Matrix4<f32>, up_axis: &UpAxis) -> Self {
+        let scene = up_axis.get_scene_matrix();
+        let model = scene * model * scene.transpose();
+        let rotation = model.fixed_view::<3, 3>(0, 0);
+        let nose = rotation * Vector3::new(0f32, 0f32, -1f32);
+        let up = rotation * Vector3::new(0f32, 1f32, 0f32);
+        let right = rotation * Vector3::new(1f32, 0f32, 0f32);
+
+        let heading = nose.x.atan2(-nose.z);
+        Self {
+            roll: (-right.y).atan2(up.y),
+            pitch: nose.y.clamp(-1f32, 1f32).asin(),
+            heading: if heading < 0f32 {
+                heading + 2f32 * PI
+            } else {
+                heading
+            },
+        }
+    }
+}
+
+pub fn draw_attitude_hud(painter: &Painter, center: Pos2, radius: f32, attitude: &Attitude) {
+    let up = vec2(-attitude.roll.sin(), -attitude.roll.cos());
+    let along = vec2(-up.y, up.x);
+    let pitch_scale = radius / (PI / 2f32);
+    let horizon = center - up * attitude.pitch * pitch_scale;
+
+    painter.circle_filled(center, radius, SKY_COLOR);
+    painter.add(Shape::convex_polygon(
+        get_ground_polygon(center, radius, horizon, up),
+        GROUND_COLOR,
+        Stroke::NONE,
+    ));
+
+    for degrees in [-20f32, -10f32, 10f32, 20f32] {
+        let p = horizon + up * degrees.to_radians() * pitch_scale;
+        if (p - center).length() < radius * 0.8 {
+            let half = along * radius * 0.2;
+            painter.line_segment([p - half, p + half], Stroke::new(1f32, Color32::WHITE));
+        }
+    }
+
+    let half_horizon = along * radius;
+    if (horizon - center).length() < radius {
+        painter.line_segment(
+            [horizon - half_horizon, horizon + half_horizon],
+            Stroke::new(2f32, Color32::WHITE),
+        );
+    }
+
+    let wing = Stroke::new(3f32, Color32::YELLOW);
+    painter.line_segment(
+        [
+            center - vec2(radius * 0.5, 0f32),
+            center - vec2(radius * 0.15, 0f32),
+        ],
+        wing,
+    );
+    painter.line_segment(
+        [
+            center + vec2(radius * 0.15, 0f32),
+            center + vec2(radius * 0.5, 0f32),
+        ],
+        wing,
+    );
+    painter.circle_filled(center, 3f32, Color32::YELLOW);
+    painter.circle_stroke(center, radius, Stroke::new(2f32, Color32::GRAY));
+
+    painter.text(
+        center + vec2(0f32, radius + 4f32),
+        Align2::CENTER_TOP,
+        format!(
+            "HDG {:03.0}°  P {:+.0}°  R {:+.0}°",
+            attitude.heading.to_degrees(),
+            attitude.pitch.to_degrees(),
+            attitude.roll.to_degrees()
+        ),
+        FontId::monospace(12f32),
+        Color32::WHITE,
+    );
+}
+
+fn get_ground_polygon(center: Pos2, radius: f32, horizon: Pos2, up: Vec2) -> Vec<Pos2> {
+    let samples_count = 64;
+    let circle = (0..samples_count)
+        .map(|i| {
+            let a = i as f32 / samples_count as f32 * 2f32 * PI;
+            pos2(center.x + radius * a.cos(), center.y + radius * a.sin())
+        })
+        .collect::<Vec<_>>();
+    let distance = |p: Pos2| (p - horizon).dot(up);
+
+    let mut polygon = Vec::new();
+    for i in 0..samples_count {
+        let a = circle[i];
+        let b = circle[(i + 1) % samples_count];
+        let (da, db) = (distance(a), distance(b));
+
+        if da <= 0f32 {
+            polygon.push(a);
+        }
+        if (da <= 0f32) != (db <= 0f32) {
+            polygon.push(a + (b - a) * (da / (da - db)));
+        }
+    }
+
+    polygon
+}
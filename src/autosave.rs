@@ -0,0 +1,49 @@
+use std::path::{Path, PathBuf};
+
+use egui_dock::DockState;
+use serde::{Deserialize, Serialize};
+
+use movement_interpolation::animation_data::AnimationData;
+
+use crate::config_dir::{get_config_path, load_json, save_json};
+use crate::panel_dock::{get_default_dock_state, PanelTab};
+use crate::render_settings::RenderSettings;
+use crate::session_log::CameraState;
+
+const AUTOSAVE_FILE: &str = "session.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Autosave {
+    pub animation_data: AnimationData,
+    pub render_settings: RenderSettings,
+    pub cameras: Vec<CameraState>,
+    pub viewport_split: f32,
+    pub dock_state: DockState<PanelTab>,
+}
+
+impl Autosave {
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        load_json(path)
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        save_json(path, self)
+    }
+}
+
+impl Default for Autosave {
+    fn default() -> Self {
+        Self {
+            animation_data: AnimationData::new(),
+            render_settings: RenderSettings::new(),
+            cameras: Vec::new(),
+            viewport_split: 0.5,
+            dock_state: get_default_dock_state(),
+        }
+    }
+}
+
+pub fn get_autosave_path() -> Option<PathBuf> {
+    get_config_path(AUTOSAVE_FILE).ok()
+}
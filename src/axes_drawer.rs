@@ -0,0 +1,95 @@
+use glium::glutin::surface::WindowSurface;
+use glium::index::{NoIndices, PrimitiveType};
+use glium::{uniform, Display, DrawParameters, Program, Surface, VertexBuffer};
+use nalgebra::{Matrix4, Vector3};
+
+use crate::vertex::ColorVertex;
+
+pub const AXIS_COLORS: [[f32; 3]; 3] = [[1f32, 0.2, 0.2], [0.2, 1f32, 0.2], [0.3, 0.5, 1f32]];
+const AXIS_LENGTH: f32 = 100f32;
+const NEGATIVE_AXIS_DIMMING: f32 = 0.4;
+
+pub struct AxesDrawer {
+    program: Program,
+    vertex_buffer: VertexBuffer<ColorVertex>,
+}
+
+impl AxesDrawer {
+    pub fn new(display: &Display<WindowSurface>) -> Self {
+        let vertex_shader_src = r#"
+            #version 410 core
+
+            in vec3 position;
+            in vec3 color;
+
+            out vec3 color_out;
+
+            uniform mat4 perspective;
+            uniform mat4 view;
+
+            void main() {
+                gl_Position = perspective * view * vec4(position, 1.0);
+                color_out = color;
+            }
+        "#;
+
+        let fragment_shader_src = r#"
+            #version 410 core
+
+            in vec3 color_out;
+
+            out vec4 frag_color;
+
+            void main() {
+                frag_color = vec4(color_out, 1.0);
+            }
+        "#;
+
+        let program =
+            Program::from_source(display, vertex_shader_src, fragment_shader_src, None).unwrap();
+
+        let vertices = AXIS_COLORS
+            .iter()
+            .enumerate()
+            .flat_map(|(i, color)| {
+                let end = Vector3::ith(i, AXIS_LENGTH);
+                let dimmed = color.map(|c| c * NEGATIVE_AXIS_DIMMING);
+                [
+                    ColorVertex::new([0f32; 3], *color),
+                    ColorVertex::new(end.data.0[0], *color),
+                    ColorVertex::new([0f32; 3], dimmed),
+                    ColorVertex::new((-end).data.0[0], dimmed),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        Self {
+            program,
+            vertex_buffer: VertexBuffer::new(display, &vertices).unwrap(),
+        }
+    }
+
+    pub fn draw(
+        &self,
+        target: &mut impl Surface,
+        perspective: &Matrix4<f32>,
+        view: &Matrix4<f32>,
+        drawing_parameters: &DrawParameters,
+    ) {
+        target
+            .draw(
+                &self.vertex_buffer,
+                NoIndices(PrimitiveType::LinesList),
+                &self.program,
+                &uniform! {
+                    perspective: perspective.data.0,
+                    view: view.data.0,
+                },
+                &DrawParameters {
+                    line_width: Some(2f32),
+                    ..drawing_parameters.clone()
+                },
+            )
+            .unwrap();
+    }
+}
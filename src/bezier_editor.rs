@@ -0,0 +1,64 @@
+use egui::{pos2, vec2, Color32, Pos2, Rect, Response, Sense, Shape, Stroke, Ui};
+use movement_interpolation::animation_data::CubicBezier;
+
+const EDITOR_SIZE: f32 = 150f32;
+const HANDLE_RADIUS: f32 = 6f32;
+
+pub fn bezier_editor(ui: &mut Ui, bezier: &mut CubicBezier) -> Response {
+    let (mut response, painter) =
+        ui.allocate_painter(vec2(EDITOR_SIZE, EDITOR_SIZE), Sense::hover());
+    let rect = response.rect.shrink(HANDLE_RADIUS);
+    let to_screen = |x: f32, y: f32| {
+        pos2(
+            rect.left() + x * rect.width(),
+            rect.bottom() - y * rect.height(),
+        )
+    };
+    let from_screen = |p: Pos2| {
+        (
+            ((p.x - rect.left()) / rect.width()).clamp(0f32, 1f32),
+            ((rect.bottom() - p.y) / rect.height()).clamp(0f32, 1f32),
+        )
+    };
+
+    for (i, (x, y)) in [
+        (&mut bezier.x1, &mut bezier.y1),
+        (&mut bezier.x2, &mut bezier.y2),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let handle_rect =
+            Rect::from_center_size(to_screen(*x, *y), vec2(2f32, 2f32) * HANDLE_RADIUS);
+        let handle_response = ui.interact(handle_rect, response.id.with(i), Sense::drag());
+        if handle_response.dragged() {
+            (*x, *y) = from_screen(to_screen(*x, *y) + handle_response.drag_delta());
+            response.mark_changed();
+        }
+    }
+
+    let handle_stroke = Stroke::new(1f32, Color32::GRAY);
+    painter.rect_stroke(rect, 0f32, handle_stroke);
+    painter.line_segment(
+        [to_screen(0f32, 0f32), to_screen(bezier.x1, bezier.y1)],
+        handle_stroke,
+    );
+    painter.line_segment(
+        [to_screen(1f32, 1f32), to_screen(bezier.x2, bezier.y2)],
+        handle_stroke,
+    );
+    painter.add(Shape::line(
+        (0..=32)
+            .map(|s| {
+                let (x, y) = bezier.get_point(s as f32 / 32f32);
+                to_screen(x, y)
+            })
+            .collect(),
+        Stroke::new(2f32, Color32::YELLOW),
+    ));
+    for (x, y) in [(bezier.x1, bezier.y1), (bezier.x2, bezier.y2)] {
+        painter.circle_filled(to_screen(x, y), HANDLE_RADIUS, Color32::WHITE);
+    }
+
+    response
+}
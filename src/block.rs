@@ -1,133 +1,564 @@
 use std::f32::consts::PI;
 
-use derive_getters::Getters;
 use glium::glutin::surface::WindowSurface;
-use glium::index::PrimitiveType;
-use glium::{Display, IndexBuffer, VertexBuffer};
-use nalgebra::{Rotation3, Vector3};
+use glium::Display;
+use nalgebra::{Isometry3, Point3, Rotation3, Translation3, UnitQuaternion, Vector3};
 
+use crate::mesh::Mesh;
+use crate::render_settings::{BlockGeometry, SceneObjectType};
 use crate::vertex::Vertex;
 
-#[derive(Debug, Getters)]
-pub struct Block {
-    vertices: VertexBuffer<Vertex>,
-    indices: IndexBuffer<u16>,
-}
+impl Mesh {
+    pub fn generate_scene_object(
+        scene_object_type: &SceneObjectType,
+        block_geometry: &BlockGeometry,
+        display: &Display<WindowSurface>,
+    ) -> Self {
+        match scene_object_type {
+            SceneObjectType::Block | SceneObjectType::Custom => {
+                Self::generate(block_geometry, display)
+            }
+            SceneObjectType::Arrow => Self::generate_arrow(10, display),
+            SceneObjectType::Tripod => Self::generate_tripod(10, display),
+            SceneObjectType::Airplane => Self::generate_airplane(display),
+            SceneObjectType::Cube => Self::generate_axes_cube(display),
+            SceneObjectType::Cone => Self::generate_cone(24, display),
+            SceneObjectType::Torus => Self::generate_torus(24, display),
+            SceneObjectType::Teapot => Self::generate_teapot(24, display),
+        }
+    }
 
-impl Block {
-    pub fn generate(divisions_count: u16, display: &Display<WindowSurface>) -> Self {
-        let mut z_vertices = Vec::new();
-        let mut z_indices = Vec::new();
-        let radius = 1f32;
-        let len = 5f32;
-        for i in 0..divisions_count {
-            let a = (i as f32 / (divisions_count - 1) as f32) * 2f32 * PI;
-            let x = a.cos();
-            let y = a.sin();
-            let normal = [0f32, 0f32, 1f32];
-            let position = [radius * x, radius * y, 0f32];
-            let color = [0f32, 0f32, 1f32];
-            z_vertices.push(Vertex::new(position, normal, color));
-
-            z_indices.push(i);
-            z_indices.push((i + 1) % divisions_count);
-            z_indices.push(divisions_count);
+    pub fn generate(block_geometry: &BlockGeometry, display: &Display<WindowSurface>) -> Self {
+        let (z_vertices, z_indices) = generate_arm(
+            block_geometry.divisions_count,
+            block_geometry.radius,
+            block_geometry.length,
+            block_geometry.radius,
+            block_geometry.radius,
+            block_geometry.axis_colors[2],
+        );
+        let (x_vertices, x_indices) = transform_mesh(
+            &z_vertices,
+            &z_indices,
+            &Rotation3::from_euler_angles(0f32, PI / 2f32, 0f32),
+            Some(block_geometry.axis_colors[0]),
+            z_vertices.len() as u32,
+        );
+        let (y_vertices, y_indices) = transform_mesh(
+            &z_vertices,
+            &z_indices,
+            &Rotation3::from_euler_angles(PI / 2f32, 0f32, 0f32),
+            Some(block_geometry.axis_colors[1]),
+            2 * z_vertices.len() as u32,
+        );
+
+        Self::from_mesh(
+            &[z_vertices, x_vertices, y_vertices].concat(),
+            &[z_indices, x_indices, y_indices].concat(),
+            display,
+        )
+    }
+
+    pub fn generate_arrow(divisions_count: u32, display: &Display<WindowSurface>) -> Self {
+        let (vertices, indices) = generate_arm(
+            divisions_count,
+            0.3f32,
+            4f32,
+            0.8f32,
+            1.5f32,
+            [1f32, 1f32, 0f32],
+        );
+
+        Self::from_mesh(&vertices, &indices, display)
+    }
+
+    pub fn generate_tripod(divisions_count: u32, display: &Display<WindowSurface>) -> Self {
+        let (z_vertices, z_indices) = generate_arm(
+            divisions_count,
+            0.3f32,
+            2f32,
+            0.6f32,
+            0.8f32,
+            [0f32, 0f32, 1f32],
+        );
+        let (x_vertices, x_indices) = generate_arm(
+            divisions_count,
+            0.3f32,
+            5f32,
+            0.6f32,
+            0.8f32,
+            [1f32, 0f32, 0f32],
+        );
+        let (x_vertices, x_indices) = transform_mesh(
+            &x_vertices,
+            &x_indices,
+            &Rotation3::from_euler_angles(0f32, PI / 2f32, 0f32),
+            None,
+            z_vertices.len() as u32,
+        );
+        let (y_vertices, y_indices) = generate_arm(
+            divisions_count,
+            0.3f32,
+            3.5f32,
+            0.6f32,
+            0.8f32,
+            [0f32, 1f32, 0f32],
+        );
+        let (y_vertices, y_indices) = transform_mesh(
+            &y_vertices,
+            &y_indices,
+            &Rotation3::from_euler_angles(PI / 2f32, 0f32, 0f32),
+            None,
+            (z_vertices.len() + x_vertices.len()) as u32,
+        );
+
+        Self::from_mesh(
+            &[z_vertices, x_vertices, y_vertices].concat(),
+            &[z_indices, x_indices, y_indices].concat(),
+            display,
+        )
+    }
+
+    pub fn generate_airplane(display: &Display<WindowSurface>) -> Self {
+        let parts = [
+            (
+                Vector3::new(0f32, 0f32, 0f32),
+                Vector3::new(0.8f32, 0.8f32, 6f32),
+                [0.8f32, 0.8f32, 0.8f32],
+            ),
+            (
+                Vector3::new(0f32, 0f32, -3.4f32),
+                Vector3::new(0.5f32, 0.5f32, 0.8f32),
+                [1f32, 1f32, 0f32],
+            ),
+            (
+                Vector3::new(0f32, 0f32, -0.3f32),
+                Vector3::new(7f32, 0.15f32, 1.5f32),
+                [0f32, 0f32, 1f32],
+            ),
+            (
+                Vector3::new(0f32, 0f32, 2.6f32),
+                Vector3::new(2.6f32, 0.1f32, 0.8f32),
+                [0f32, 0f32, 1f32],
+            ),
+            (
+                Vector3::new(0f32, 0.9f32, 2.6f32),
+                Vector3::new(0.1f32, 1.2f32, 0.8f32),
+                [1f32, 0f32, 0f32],
+            ),
+        ];
+
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+        for (center, size, color) in parts {
+            let (part_vertices, part_indices) =
+                generate_box(&center, &size, color, vertices.len() as u32);
+            vertices.extend(part_vertices);
+            indices.extend(part_indices);
         }
-        z_vertices.push(Vertex::new(
-            [0f32, 0f32, 0f32],
+
+        Self::from_mesh(&vertices, &indices, display)
+    }
+
+    pub fn generate_axes_cube(display: &Display<WindowSurface>) -> Self {
+        let (vertices, indices) = generate_box(
+            &Vector3::zeros(),
+            &Vector3::new(4f32, 4f32, 4f32),
+            [0f32; 3],
+            0,
+        );
+        let vertices = vertices
+            .iter()
+            .map(|v| Vertex::new(*v.position(), *v.normal(), get_axis_color(v.normal())))
+            .collect::<Vec<_>>();
+
+        Self::from_mesh(&vertices, &indices, display)
+    }
+
+    pub fn generate_cone(divisions_count: u32, display: &Display<WindowSurface>) -> Self {
+        let (cone_vertices, cone_indices) = generate_lathe(
+            &[(0f32, 0f32), (1.5f32, 0f32), (0f32, 5f32)],
+            divisions_count,
+            (0f32, 2f32 * PI),
+            [1f32, 1f32, 0f32],
+            &Isometry3::from_parts(
+                Translation3::new(0f32, 0f32, 2.5f32),
+                UnitQuaternion::from_euler_angles(-PI / 2f32, 0f32, 0f32),
+            ),
+            0,
+        );
+        let (fin_vertices, fin_indices) = generate_box(
+            &Vector3::new(0f32, 1.6f32, 1.8f32),
+            &Vector3::new(0.1f32, 1f32, 0.8f32),
+            [1f32, 0f32, 0f32],
+            cone_vertices.len() as u32,
+        );
+
+        Self::from_mesh(
+            &[cone_vertices, fin_vertices].concat(),
+            &[cone_indices, fin_indices].concat(),
+            display,
+        )
+    }
+
+    pub fn generate_torus(divisions_count: u32, display: &Display<WindowSurface>) -> Self {
+        let profile = get_circle_profile(3f32, 0.8f32, divisions_count);
+        let colors = [
+            [1f32, 0f32, 0f32],
+            [0f32, 1f32, 0f32],
             [0f32, 0f32, 1f32],
+            [1f32, 1f32, 0f32],
+        ];
+
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+        for (i, color) in colors.into_iter().enumerate() {
+            let (part_vertices, part_indices) = generate_lathe(
+                &profile,
+                divisions_count / 4,
+                (i as f32 * PI / 2f32, (i + 1) as f32 * PI / 2f32),
+                color,
+                &Isometry3::identity(),
+                vertices.len() as u32,
+            );
+            vertices.extend(part_vertices);
+            indices.extend(part_indices);
+        }
+
+        Self::from_mesh(&vertices, &indices, display)
+    }
+
+    pub fn generate_teapot(divisions_count: u32, display: &Display<WindowSurface>) -> Self {
+        let parts = [
+            (
+                vec![
+                    (0f32, 0f32),
+                    (1.6f32, 0f32),
+                    (2.2f32, 0.4f32),
+                    (2.5f32, 1.2f32),
+                    (2.4f32, 2.2f32),
+                    (2f32, 2.8f32),
+                    (1.4f32, 3.1f32),
+                    (1.2f32, 3.3f32),
+                    (0.6f32, 3.6f32),
+                    (0.25f32, 3.7f32),
+                    (0.3f32, 4.1f32),
+                    (0f32, 4.2f32),
+                ],
+                (0f32, 2f32 * PI),
+                [0.9f32, 0.9f32, 0.9f32],
+                Isometry3::translation(0f32, -2f32, 0f32),
+            ),
+            (
+                vec![
+                    (0f32, 0f32),
+                    (0.5f32, 0f32),
+                    (0.25f32, 2.5f32),
+                    (0.3f32, 2.6f32),
+                    (0f32, 2.6f32),
+                ],
+                (0f32, 2f32 * PI),
+                [1f32, 0f32, 0f32],
+                Isometry3::from_parts(
+                    Translation3::new(0f32, -1.1f32, -1.9f32),
+                    UnitQuaternion::from_euler_angles(-0.9f32, 0f32, 0f32),
+                ),
+            ),
+            (
+                get_circle_profile(0.9f32, 0.2f32, divisions_count / 2),
+                (PI, 2f32 * PI),
+                [0f32, 0f32, 1f32],
+                Isometry3::from_parts(
+                    Translation3::new(0f32, -0.2f32, 2.1f32),
+                    UnitQuaternion::from_euler_angles(0f32, 0f32, PI / 2f32),
+                ),
+            ),
+        ];
+
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+        for (profile, angles, color, transform) in parts {
+            let (part_vertices, part_indices) = generate_lathe(
+                &profile,
+                divisions_count,
+                angles,
+                color,
+                &transform,
+                vertices.len() as u32,
+            );
+            vertices.extend(part_vertices);
+            indices.extend(part_indices);
+        }
+
+        Self::from_mesh(&vertices, &indices, display)
+    }
+
+    pub fn generate_cube(color: [f32; 3], display: &Display<WindowSurface>) -> Self {
+        let (vertices, indices) =
+            generate_box(&Vector3::zeros(), &Vector3::new(1f32, 1f32, 1f32), color, 0);
+
+        Self::from_mesh(&vertices, &indices, display)
+    }
+
+    pub fn generate_sphere(
+        divisions_count: u32,
+        color: [f32; 3],
+        display: &Display<WindowSurface>,
+    ) -> Self {
+        let stacks = divisions_count;
+        let slices = 2 * divisions_count;
+
+        let mut vertices = Vec::new();
+        for i in 0..=stacks {
+            let phi = i as f32 / stacks as f32 * PI;
+            for j in 0..=slices {
+                let theta = j as f32 / slices as f32 * 2f32 * PI;
+                let n = [phi.sin() * theta.cos(), phi.cos(), -phi.sin() * theta.sin()];
+                vertices.push(Vertex::new(
+                    [0.5f32 * n[0], 0.5f32 * n[1], 0.5f32 * n[2]],
+                    n,
+                    color,
+                ));
+            }
+        }
+
+        let mut indices = Vec::new();
+        for i in 0..stacks {
+            for j in 0..slices {
+                let a = i * (slices + 1) + j;
+                let b = a + slices + 1;
+                indices.extend([a, b, b + 1, a, b + 1, a + 1]);
+            }
+        }
+
+        Self::from_mesh(&vertices, &indices, display)
+    }
+}
+
+fn generate_arm(
+    divisions_count: u32,
+    radius: f32,
+    len: f32,
+    head_radius: f32,
+    head_len: f32,
+    color: [f32; 3],
+) -> (Vec<Vertex>, Vec<u32>) {
+    let mut vertices = Vec::new();
+    let mut indices = Vec::new();
+    let ring = |i: u32| {
+        let a = (i as f32 / (divisions_count - 1) as f32) * 2f32 * PI;
+        (a.cos(), a.sin())
+    };
+
+    for i in 0..divisions_count {
+        let (x, y) = ring(i);
+        vertices.push(Vertex::new(
+            [radius * x, radius * y, 0f32],
             [0f32, 0f32, 1f32],
+            color,
+        ));
+
+        indices.push(i);
+        indices.push((i + 1) % divisions_count);
+        indices.push(divisions_count);
+    }
+    vertices.push(Vertex::new([0f32, 0f32, 0f32], [0f32, 0f32, 1f32], color));
+
+    let top = divisions_count + 1;
+    let bottom = 2 * divisions_count + 1;
+    for i in 0..divisions_count {
+        let (x, y) = ring(i);
+        vertices.push(Vertex::new(
+            [radius * x, radius * y, 0f32],
+            [x, y, 0f32],
+            color,
         ));
 
+        indices.push(top + i);
+        indices.push(bottom + i);
+        indices.push(top + (i + 1) % divisions_count);
+
+        indices.push(top + (i + 1) % divisions_count);
+        indices.push(bottom + i);
+        indices.push(bottom + (i + 1) % divisions_count);
+    }
+    for i in 0..divisions_count {
+        let (x, y) = ring(i);
+        vertices.push(Vertex::new(
+            [radius * x, radius * y, -len],
+            [x, y, 0f32],
+            color,
+        ));
+    }
+
+    if head_radius > radius {
+        let inner = 3 * divisions_count + 1;
+        let outer = 4 * divisions_count + 1;
         for i in 0..divisions_count {
-            let a = (i as f32 / (divisions_count - 1) as f32) * 2f32 * PI;
-            let x = a.cos();
-            let y = a.sin();
-            let normal = [x, y, 0f32];
-            let position = [radius * x, radius * y, 0f32];
-            let color = [0f32, 0f32, 1f32];
-            z_vertices.push(Vertex::new(position, normal, color));
-
-            z_indices.push(divisions_count + 1 + i);
-            z_indices.push(2 * divisions_count + 1 + i);
-            z_indices.push(divisions_count + 1 + (i + 1) % divisions_count);
-
-            z_indices.push(divisions_count + 1 + (i + 1) % divisions_count);
-            z_indices.push(2 * divisions_count + 1 + i);
-            z_indices.push(2 * divisions_count + 1 + (i + 1) % divisions_count);
+            let (x, y) = ring(i);
+            vertices.push(Vertex::new(
+                [radius * x, radius * y, -len],
+                [0f32, 0f32, -1f32],
+                color,
+            ));
         }
-
         for i in 0..divisions_count {
-            let a = (i as f32 / (divisions_count - 1) as f32) * 2f32 * PI;
-            let x = a.cos();
-            let y = a.sin();
-            let z = -len;
-            let normal = [x, y, 0f32];
-            let position = [radius * x, radius * y, z];
-            let color = [0f32, 0f32, 1f32];
-            z_vertices.push(Vertex::new(position, normal, color));
-
-            z_indices.push(2 * divisions_count + 1 + i);
-            z_indices.push(3 * divisions_count + 1);
-            z_indices.push(2 * divisions_count + 1 + (i + 1) % divisions_count);
+            let (x, y) = ring(i);
+            vertices.push(Vertex::new(
+                [head_radius * x, head_radius * y, -len],
+                [0f32, 0f32, -1f32],
+                color,
+            ));
+
+            indices.push(inner + i);
+            indices.push(outer + (i + 1) % divisions_count);
+            indices.push(outer + i);
+
+            indices.push(inner + i);
+            indices.push(inner + (i + 1) % divisions_count);
+            indices.push(outer + (i + 1) % divisions_count);
         }
-        z_vertices.push(Vertex::new(
-            [0f32, 0f32, -len - radius],
-            [0f32, 0f32, 1f32],
-            [0f32, 0f32, 1f32],
+    }
+
+    let head = vertices.len() as u32;
+    let tip = head + divisions_count;
+    for i in 0..divisions_count {
+        let (x, y) = ring(i);
+        vertices.push(Vertex::new(
+            [head_radius * x, head_radius * y, -len],
+            [x, y, 0f32],
+            color,
         ));
 
-        let x_vertices = z_vertices
-            .iter()
-            .map(|v| {
-                let r = Rotation3::from_euler_angles(0f32, PI / 2f32, 0f32);
-                let p = Vector3::new(v.position()[0], v.position()[1], v.position()[2]);
-                let n = Vector3::new(v.normal()[0], v.normal()[1], v.normal()[2]);
+        indices.push(head + i);
+        indices.push(tip);
+        indices.push(head + (i + 1) % divisions_count);
+    }
+    vertices.push(Vertex::new(
+        [0f32, 0f32, -len - head_len],
+        [0f32, 0f32, 1f32],
+        color,
+    ));
 
-                let p = r * p;
-                let n = r * n;
+    (vertices, indices)
+}
 
-                let c = [1f32, 0f32, 0f32];
+fn generate_box(
+    center: &Vector3<f32>,
+    size: &Vector3<f32>,
+    color: [f32; 3],
+    offset: u32,
+) -> (Vec<Vertex>, Vec<u32>) {
+    let half = size / 2f32;
+    let faces = [
+        (Vector3::x(), Vector3::y(), Vector3::z()),
+        (-Vector3::x(), Vector3::z(), Vector3::y()),
+        (Vector3::y(), Vector3::z(), Vector3::x()),
+        (-Vector3::y(), Vector3::x(), Vector3::z()),
+        (Vector3::z(), Vector3::x(), Vector3::y()),
+        (-Vector3::z(), Vector3::y(), Vector3::x()),
+    ];
 
-                Vertex::new(p.data.0[0], n.data.0[0], c)
-            })
-            .collect::<Vec<_>>();
+    let mut vertices = Vec::new();
+    let mut indices = Vec::new();
+    for (n, u, v) in faces {
+        let face_center = center + n.component_mul(&half);
+        let u = u.component_mul(&half);
+        let v = v.component_mul(&half);
+        let first = offset + vertices.len() as u32;
 
-        let x_indices = z_indices
-            .iter()
-            .map(|i| i + z_vertices.len() as u16)
-            .collect::<Vec<_>>();
+        for p in [
+            face_center - u - v,
+            face_center + u - v,
+            face_center + u + v,
+            face_center - u + v,
+        ] {
+            vertices.push(Vertex::new(p.data.0[0], n.data.0[0], color));
+        }
 
-        let y_vertices = z_vertices
-            .iter()
-            .map(|v| {
-                let r = Rotation3::from_euler_angles(PI / 2f32, 0f32, 0f32);
-                let p = Vector3::new(v.position()[0], v.position()[1], v.position()[2]);
-                let n = Vector3::new(v.normal()[0], v.normal()[1], v.normal()[2]);
+        indices.extend([first, first + 1, first + 2, first, first + 2, first + 3]);
+    }
 
-                let p = r * p;
-                let n = r * n;
+    (vertices, indices)
+}
 
-                let c = [0f32, 1f32, 0f32];
+fn generate_lathe(
+    profile: &[(f32, f32)],
+    divisions_count: u32,
+    angles: (f32, f32),
+    color: [f32; 3],
+    transform: &Isometry3<f32>,
+    offset: u32,
+) -> (Vec<Vertex>, Vec<u32>) {
+    let mut vertices = Vec::new();
+    let mut indices = Vec::new();
+    for segment in profile.windows(2) {
+        let (r0, h0) = segment[0];
+        let (r1, h1) = segment[1];
+        let (nr, nh) = {
+            let len = (r1 - r0).hypot(h1 - h0).max(f32::EPSILON);
+            ((h1 - h0) / len, (r0 - r1) / len)
+        };
 
-                Vertex::new(p.data.0[0], n.data.0[0], c)
-            })
-            .collect::<Vec<_>>();
-
-        let y_indices = z_indices
-            .iter()
-            .map(|i| i + 2 * z_vertices.len() as u16)
-            .collect::<Vec<_>>();
+        let first = offset + vertices.len() as u32;
+        for (r, h) in [(r0, h0), (r1, h1)] {
+            for i in 0..=divisions_count {
+                let a = angles.0 + (angles.1 - angles.0) * i as f32 / divisions_count as f32;
+                let p = transform * Point3::new(r * a.cos(), h, -r * a.sin());
+                let n = transform * Vector3::new(nr * a.cos(), nh, -nr * a.sin());
+                vertices.push(Vertex::new(p.coords.data.0[0], n.data.0[0], color));
+            }
+        }
 
-        Self {
-            vertices: VertexBuffer::new(display, &[z_vertices, x_vertices, y_vertices].concat())
-                .unwrap(),
-            indices: IndexBuffer::new(
-                display,
-                PrimitiveType::TrianglesList,
-                &[z_indices, x_indices, y_indices].concat(),
-            )
-            .unwrap(),
+        let next = divisions_count + 1;
+        for i in 0..divisions_count {
+            let a = first + i;
+            indices.extend([a, a + 1, a + next, a + 1, a + next + 1, a + next]);
         }
     }
+
+    (vertices, indices)
+}
+
+fn get_circle_profile(center_radius: f32, radius: f32, segments_count: u32) -> Vec<(f32, f32)> {
+    (0..=segments_count)
+        .map(|i| {
+            let v = -PI / 2f32 + i as f32 / segments_count as f32 * 2f32 * PI;
+            (center_radius + radius * v.cos(), radius * v.sin())
+        })
+        .collect()
+}
+
+fn get_axis_color(normal: &[f32; 3]) -> [f32; 3] {
+    let brightness = if normal.iter().sum::<f32>() > 0f32 {
+        1f32
+    } else {
+        0.5f32
+    };
+    normal.map(|n| n.abs() * brightness)
+}
+
+fn transform_mesh(
+    vertices: &[Vertex],
+    indices: &[u32],
+    rotation: &Rotation3<f32>,
+    color: Option<[f32; 3]>,
+    offset: u32,
+) -> (Vec<Vertex>, Vec<u32>) {
+    let vertices = vertices
+        .iter()
+        .map(|v| {
+            let p = Vector3::new(v.position()[0], v.position()[1], v.position()[2]);
+            let n = Vector3::new(v.normal()[0], v.normal()[1], v.normal()[2]);
+
+            let p = rotation * p;
+            let n = rotation * n;
+
+            Vertex::new(p.data.0[0], n.data.0[0], color.unwrap_or(*v.color()))
+        })
+        .collect::<Vec<_>>();
+
+    let indices = indices.iter().map(|i| i + offset).collect::<Vec<_>>();
+
+    (vertices, indices)
 }
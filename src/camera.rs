@@ -0,0 +1,185 @@
+use std::f32::consts::PI;
+
+use derive_getters::Getters;
+use nalgebra::{Matrix4, Point3, Vector2, Vector3, Vector4};
+use serde::{Deserialize, Serialize};
+
+use crate::path_analysis::BoundingBox;
+use crate::session_log::CameraState;
+
+const FIT_VIEW_MARGIN: f32 = 1.2;
+const CAMERA_PAN_SPEED: f32 = 0.002;
+const CAMERA_TRANSITION_TIME: f32 = 0.3;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum CameraPreset {
+    Front,
+    Side,
+    Top,
+    Isometric,
+}
+
+#[derive(Debug, Clone)]
+struct CameraTransition {
+    from: Vector2<f32>,
+    to: Vector2<f32>,
+    time_elapsed: f32,
+}
+
+#[derive(Debug, Clone, Getters)]
+pub struct Camera {
+    angle: Vector2<f32>,
+    direction: Vector3<f32>,
+    up: Vector3<f32>,
+    distant: f32,
+    target: Vector3<f32>,
+    view: Matrix4<f32>,
+    grid_view: Matrix4<f32>,
+    #[getter(skip)]
+    scene: Matrix4<f32>,
+    #[getter(skip)]
+    transition: Option<CameraTransition>,
+}
+
+impl CameraPreset {
+    pub fn get_angle(&self) -> Vector2<f32> {
+        match self {
+            CameraPreset::Front => Vector2::new(0f32, 0f32),
+            CameraPreset::Side => Vector2::new(0f32, PI / 2f32),
+            CameraPreset::Top => Vector2::new(PI / 2f32, 0f32),
+            CameraPreset::Isometric => Vector2::new((1f32 / 2f32.sqrt()).atan(), PI / 4f32),
+        }
+    }
+}
+
+impl Camera {
+    pub fn new() -> Self {
+        let mut camera = Self {
+            angle: Vector2::new(0.0, 0.0),
+            direction: Vector3::new(0.0, 0.0, 1.0),
+            up: Vector3::new(0.0, 1.0, 0.0),
+            distant: 5.0,
+            target: Vector3::new(0.0, 0.0, 0.0),
+            view: Matrix4::identity(),
+            grid_view: Matrix4::identity(),
+            scene: Matrix4::identity(),
+            transition: None,
+        };
+        camera.update_view();
+        camera
+    }
+
+    pub fn get_position(&self) -> Vector3<f32> {
+        self.scene
+            .try_inverse()
+            .unwrap()
+            .transform_vector(&self.get_eye_position())
+    }
+
+    pub fn set_scene(&mut self, scene: Matrix4<f32>) {
+        self.scene = scene;
+        self.update_view();
+    }
+
+    pub fn get_state(&self) -> CameraState {
+        CameraState {
+            angle: (self.angle.x, self.angle.y),
+            distant: self.distant,
+            target: (self.target.x, self.target.y, self.target.z),
+        }
+    }
+
+    pub fn set_state(&mut self, state: &CameraState) {
+        self.angle = Vector2::new(state.angle.0, state.angle.1);
+        self.distant = state.distant;
+        self.target = Vector3::new(state.target.0, state.target.1, state.target.2);
+        self.transition = None;
+        self.update_axes();
+    }
+
+    pub fn is_transitioning(&self) -> bool {
+        self.transition.is_some()
+    }
+
+    pub fn orbit(&mut self, pitch: f32, yaw: f32) {
+        self.angle.x += pitch;
+        self.angle.y += yaw * if self.angle.x.cos() < 0.0 { -1.0 } else { 1.0 };
+        self.transition = None;
+        self.update_axes();
+    }
+
+    pub fn pan(&mut self, delta_x: f32, delta_y: f32) {
+        let right = self.direction.cross(&self.up).normalize();
+        self.target += (-right * delta_x + self.up * delta_y) * self.distant * CAMERA_PAN_SPEED;
+        self.update_view();
+    }
+
+    pub fn zoom(&mut self, delta: f32) {
+        self.distant += delta;
+        self.update_view();
+    }
+
+    pub fn fit(&mut self, bounding_box: &BoundingBox, aspect: f32, fov: f32) {
+        let half_fov = (aspect * (fov / 2.0).tan()).atan().min(fov / 2.0);
+        self.target = self.scene.transform_vector(&bounding_box.get_center());
+        self.distant = FIT_VIEW_MARGIN * bounding_box.get_extents().norm() / 2.0 / half_fov.sin();
+        self.update_view();
+    }
+
+    pub fn snap_to(&mut self, preset: &CameraPreset, animated: bool) {
+        let to = preset.get_angle();
+        if animated {
+            self.transition = Some(CameraTransition {
+                from: Vector2::new(
+                    to_nearest_turn(self.angle.x, to.x),
+                    to_nearest_turn(self.angle.y, to.y),
+                ),
+                to,
+                time_elapsed: 0f32,
+            });
+        } else {
+            self.angle = to;
+            self.transition = None;
+            self.update_axes();
+        }
+    }
+
+    pub fn update(&mut self, time_elapsed: f32) {
+        let Some(transition) = self.transition.as_mut() else {
+            return;
+        };
+
+        transition.time_elapsed += time_elapsed;
+        let x = (transition.time_elapsed / CAMERA_TRANSITION_TIME).min(1f32);
+        let eased = x * x * (3f32 - 2f32 * x);
+        self.angle = transition.from.lerp(&transition.to, eased);
+        if x >= 1f32 {
+            self.transition = None;
+        }
+        self.update_axes();
+    }
+
+    fn update_axes(&mut self) {
+        let rotation = Matrix4::from_euler_angles(self.angle.x, self.angle.y, 0.0);
+        self.direction = (rotation * Vector4::new(0.0, 0.0, 1.0, 0.0)).xyz();
+        self.up = (rotation * Vector4::new(0.0, 1.0, 0.0, 0.0)).xyz();
+        self.update_view();
+    }
+
+    fn get_eye_position(&self) -> Vector3<f32> {
+        self.target - self.distant * self.direction
+    }
+
+    fn update_view(&mut self) {
+        self.grid_view = Matrix4::look_at_rh(
+            &Point3::from(self.get_eye_position()),
+            &Point3::from(self.target),
+            &self.up,
+        );
+        self.view = self.grid_view * self.scene;
+    }
+}
+
+fn to_nearest_turn(angle: f32, reference: f32) -> f32 {
+    reference + (angle - reference + PI).rem_euclid(2f32 * PI) - PI
+}
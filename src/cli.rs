@@ -0,0 +1,41 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+
+#[derive(Debug, Clone, Parser)]
+#[command(about = "Quaternion and Euler angle movement interpolation")]
+pub struct Cli {
+    /// Interpolate the JSON config without opening a window
+    #[arg(long)]
+    pub headless: bool,
+    /// Run randomized configurations looking for numerical issues
+    #[arg(long)]
+    pub soak: bool,
+    /// Answer pose queries read line by line from stdin
+    #[arg(long)]
+    pub stream: bool,
+    /// Startup TOML config of the visualizer
+    #[arg(long, conflicts_with_all = ["headless", "soak", "stream"])]
+    pub config: Option<PathBuf>,
+    /// JSON config interpolated in headless mode
+    #[arg(long, default_value = "config.json", requires = "headless")]
+    pub headless_config: PathBuf,
+    #[arg(long, default_value = "frames.json")]
+    pub out: PathBuf,
+    #[arg(long)]
+    pub seed: Option<u64>,
+    #[arg(long)]
+    pub iterations: Option<u64>,
+    #[arg(long, default_value = "soak.log")]
+    pub log: PathBuf,
+    #[arg(long)]
+    pub replay: Option<PathBuf>,
+    #[arg(long)]
+    pub wizard: bool,
+    #[arg(long)]
+    pub width: Option<u32>,
+    #[arg(long)]
+    pub height: Option<u32>,
+    #[arg(long, value_parser = clap::value_parser!(u8).range(0..=16))]
+    pub msaa: Option<u8>,
+}
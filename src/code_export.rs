@@ -0,0 +1,286 @@
+use std::fmt::Write as _;
+use std::path::Path;
+
+use nalgebra::Vector3;
+
+use movement_interpolation::animation::AnimationAngle;
+use movement_interpolation::animation_data::{
+    AngleInput, AnimationData, PositionInterpolationType,
+};
+
+pub fn export_rust_snippet(
+    animation_data: &AnimationData,
+    angle_input: &AngleInput,
+    path: &Path,
+) -> std::io::Result<()> {
+    std::fs::write(path, get_rust_snippet(animation_data, angle_input))
+}
+
+pub fn get_rust_snippet(animation_data: &AnimationData, angle_input: &AngleInput) -> String {
+    let (begin_angle, end_angle) = animation_data.get_angles(angle_input);
+    let mut code = String::new();
+
+    writeln!(code, "fn main() {{").unwrap();
+    writeln!(
+        code,
+        "    let interpolator = InterpolatorBuilder::default()"
+    )
+    .unwrap();
+    writeln!(
+        code,
+        "        .begin_position({})",
+        get_vector_code(&animation_data.get_begin_position())
+    )
+    .unwrap();
+    writeln!(
+        code,
+        "        .end_position({})",
+        get_vector_code(&animation_data.get_end_position())
+    )
+    .unwrap();
+    writeln!(
+        code,
+        "        .begin_angle({})",
+        get_angle_code(&begin_angle)
+    )
+    .unwrap();
+    writeln!(code, "        .end_angle({})", get_angle_code(&end_angle)).unwrap();
+    writeln!(
+        code,
+        "        .normalize_quaternions({})",
+        animation_data.normalize_quaternions()
+    )
+    .unwrap();
+    writeln!(
+        code,
+        "        .rotation_interpolator(get_rotation_interpolator({:?}).unwrap())",
+        animation_data.quaternion_interpolation_type
+    )
+    .unwrap();
+    writeln!(
+        code,
+        "        .begin_euler_representation(EulerRepresentation::{:?})",
+        animation_data.begin_euler_representation
+    )
+    .unwrap();
+    writeln!(
+        code,
+        "        .end_euler_representation(EulerRepresentation::{:?})",
+        animation_data.end_euler_representation
+    )
+    .unwrap();
+    writeln!(
+        code,
+        "        .waypoints(vec![{}])",
+        animation_data
+            .waypoints
+            .iter()
+            .map(|w| get_vector_code(&Vector3::new(w.0, w.1, w.2)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+    .unwrap();
+    writeln!(
+        code,
+        "        .position_interpolation_type(PositionInterpolationType::{:?})",
+        animation_data.position_interpolation_type
+    )
+    .unwrap();
+    writeln!(
+        code,
+        "        .catmull_rom_tension({:?})",
+        animation_data.catmull_rom_tension
+    )
+    .unwrap();
+    if animation_data.position_interpolation_type == PositionInterpolationType::Tcb {
+        writeln!(
+            code,
+            "        .tcb_parameters(vec!{:?})",
+            animation_data.tcb_parameters
+        )
+        .unwrap();
+    }
+    if animation_data.position_interpolation_type == PositionInterpolationType::Hermite {
+        for (name, v) in [
+            ("begin_velocity", animation_data.begin_velocity),
+            ("end_velocity", animation_data.end_velocity),
+        ] {
+            writeln!(
+                code,
+                "        .{}({})",
+                name,
+                get_vector_code(&Vector3::new(v.0, v.1, v.2))
+            )
+            .unwrap();
+        }
+    }
+    writeln!(
+        code,
+        "        .translation_easing(Easing::{:?})",
+        animation_data.translation_easing
+    )
+    .unwrap();
+    writeln!(
+        code,
+        "        .rotation_easing(Easing::{:?})",
+        animation_data.rotation_easing
+    )
+    .unwrap();
+    writeln!(
+        code,
+        "        .euler_order(EulerOrder::{:?})",
+        animation_data.euler_order
+    )
+    .unwrap();
+    writeln!(code, "        .build()").unwrap();
+    writeln!(code, "        .unwrap();").unwrap();
+    writeln!(code).unwrap();
+
+    if animation_data.display_all_frames {
+        writeln!(
+            code,
+            "    let animation = DiscreteFrameAnimationBuilder::default()"
+        )
+        .unwrap();
+        writeln!(
+            code,
+            "        .frames_count({})",
+            animation_data.frames_count
+        )
+        .unwrap();
+        writeln!(
+            code,
+            "        .frame_spacing(FrameSpacing::{:?})",
+            animation_data.frame_spacing
+        )
+        .unwrap();
+    } else {
+        writeln!(
+            code,
+            "    let mut animation = ContinuousAnimationBuilder::default()"
+        )
+        .unwrap();
+        writeln!(
+            code,
+            "        .animation_time({:?})",
+            animation_data.animation_time
+        )
+        .unwrap();
+        writeln!(
+            code,
+            "        .time_accumulation(TimeAccumulation::{:?})",
+            animation_data.time_accumulation
+        )
+        .unwrap();
+        if animation_data.time_remap_enabled {
+            let b = &animation_data.time_remap;
+            writeln!(
+                code,
+                "        .time_remap(Some(CubicBezier::new({:?}, {:?}, {:?}, {:?})))",
+                b.x1, b.y1, b.x2, b.y2
+            )
+            .unwrap();
+        }
+        if animation_data.auto_slow_motion {
+            writeln!(
+                code,
+                "        .slow_motion(Some(SlowMotionProfile::analyze(&interpolator)))"
+            )
+            .unwrap();
+        } else if animation_data.midpoint_slow_motion {
+            writeln!(
+                code,
+                "        .slow_motion(Some(SlowMotionProfile::focus(0.5)))"
+            )
+            .unwrap();
+        }
+        if animation_data.playback_speed != 1f32 {
+            writeln!(
+                code,
+                "        .playback_speed({:?})",
+                animation_data.playback_speed as f64
+            )
+            .unwrap();
+        }
+    }
+    writeln!(code, "        .interpolator(interpolator)").unwrap();
+    writeln!(code, "        .build()").unwrap();
+    writeln!(code, "        .unwrap();").unwrap();
+    writeln!(code).unwrap();
+    if !animation_data.display_all_frames {
+        writeln!(code, "    animation.make_step(1.0 / 60.0);").unwrap();
+    }
+    writeln!(code, "    let sample = animation.get_sample(0.5);").unwrap();
+    writeln!(
+        code,
+        "    println!(\"{{:?}} {{:?}}\", sample.position(), sample.quaternion());"
+    )
+    .unwrap();
+    writeln!(code, "}}").unwrap();
+
+    format!("{}\n{}", get_imports_code(&code), code)
+}
+
+fn get_imports_code(code: &str) -> String {
+    let imports = [
+        (
+            "movement_interpolation::animation_data",
+            &[
+                "CubicBezier",
+                "Easing",
+                "EulerOrder",
+                "EulerRepresentation",
+                "FrameSpacing",
+                "PositionInterpolationType",
+                "TimeAccumulation",
+            ][..],
+        ),
+        (
+            "movement_interpolation::rotation_interpolator",
+            &["get_rotation_interpolator"][..],
+        ),
+        (
+            "movement_interpolation::slow_motion",
+            &["SlowMotionProfile"][..],
+        ),
+        (
+            "movement_interpolation",
+            &[
+                "AnimationAngle",
+                "ContinuousAnimationBuilder",
+                "DiscreteFrameAnimationBuilder",
+                "InterpolatorBuilder",
+            ][..],
+        ),
+        ("nalgebra", &["Quaternion", "Vector3"][..]),
+    ];
+
+    std::iter::once("use movement_interpolation::animation::Animation;\n".to_string())
+        .chain(imports.iter().filter_map(|(module, names)| {
+            let used = names
+                .iter()
+                .filter(|n| code.contains(&format!("{}::", n)) || code.contains(&format!("{}(", n)))
+                .cloned()
+                .collect::<Vec<_>>();
+            match used.len() {
+                0 => None,
+                1 => Some(format!("use {}::{};\n", module, used[0])),
+                _ => Some(format!("use {}::{{{}}};\n", module, used.join(", "))),
+            }
+        }))
+        .collect()
+}
+
+fn get_vector_code(v: &Vector3<f32>) -> String {
+    format!("Vector3::new({:?}, {:?}, {:?})", v.x, v.y, v.z)
+}
+
+fn get_angle_code(angle: &AnimationAngle) -> String {
+    match angle {
+        AnimationAngle::Quternion(q) => format!(
+            "AnimationAngle::new_quternion(Quaternion::new({:?}, {:?}, {:?}, {:?}))",
+            q.w, q.i, q.j, q.k
+        ),
+        AnimationAngle::Euler(e) => format!("AnimationAngle::new_euler({})", get_vector_code(e)),
+    }
+}
@@ -0,0 +1,31 @@
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Error, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+const CONFIG_DIRECTORY: &str = "movement_interpolation";
+
+pub fn get_config_path(file_name: &str) -> std::io::Result<PathBuf> {
+    dirs::config_dir()
+        .map(|d| d.join(CONFIG_DIRECTORY).join(file_name))
+        .ok_or_else(|| Error::new(ErrorKind::NotFound, "No config directory"))
+}
+
+pub fn create_config_file(path: &Path) -> std::io::Result<File> {
+    if let Some(directory) = path.parent() {
+        std::fs::create_dir_all(directory)?;
+    }
+    File::create(path)
+}
+
+pub fn load_json<T: DeserializeOwned>(path: &Path) -> std::io::Result<T> {
+    Ok(serde_json::from_reader(BufReader::new(File::open(path)?))?)
+}
+
+pub fn save_json<T: Serialize>(path: &Path, value: &T) -> std::io::Result<()> {
+    let mut writer = BufWriter::new(create_config_file(path)?);
+    serde_json::to_writer_pretty(&mut writer, value)?;
+    writer.flush()
+}
@@ -0,0 +1,131 @@
+use std::ffi::{c_char, CStr};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use nalgebra::Matrix4;
+
+use crate::animation::{AnimationSample, Interpolator};
+use crate::animation_data::{AngleInput, AnimationData};
+
+#[repr(C)]
+pub struct MiPose {
+    pub position: [f32; 3],
+    pub quaternion: [f32; 4],
+    pub euler: [f32; 3],
+    pub quaternion_frame: [f32; 16],
+    pub euler_frame: [f32; 16],
+}
+
+/// Builds an interpolator from an `AnimationData` JSON document. Returns null when the
+/// document cannot be parsed or building it panics. The result must be released with
+/// `mi_interpolator_free`.
+///
+/// # Safety
+/// `json` must be a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn mi_interpolator_from_json(
+    json: *const c_char,
+    use_euler_input: bool,
+) -> *mut Interpolator {
+    catch_unwind(|| {
+        if json.is_null() {
+            return std::ptr::null_mut();
+        }
+
+        let Ok(json) = CStr::from_ptr(json).to_str() else {
+            return std::ptr::null_mut();
+        };
+        let Ok(animation_data) = serde_json::from_str::<AnimationData>(json) else {
+            return std::ptr::null_mut();
+        };
+
+        let angle_input = if use_euler_input {
+            AngleInput::Euler
+        } else {
+            AngleInput::Quaternion
+        };
+        let (begin_angle, end_angle) = animation_data.get_angles(&angle_input);
+
+        Box::into_raw(Box::new(
+            animation_data.get_interpolator(begin_angle, end_angle),
+        ))
+    })
+    .unwrap_or(std::ptr::null_mut())
+}
+
+/// # Safety
+/// `interpolator` must be null or a pointer returned by `mi_interpolator_from_json`.
+#[no_mangle]
+pub unsafe extern "C" fn mi_interpolator_free(interpolator: *mut Interpolator) {
+    let _ = catch_unwind(AssertUnwindSafe(|| {
+        if !interpolator.is_null() {
+            drop(Box::from_raw(interpolator));
+        }
+    }));
+}
+
+/// Writes the pose at `t` in [0, 1]. Returns false when a pointer is null or sampling
+/// panics.
+///
+/// # Safety
+/// `interpolator` must come from `mi_interpolator_from_json` and `out` must point to
+/// a writable `MiPose`.
+#[no_mangle]
+pub unsafe extern "C" fn mi_interpolator_sample(
+    interpolator: *const Interpolator,
+    t: f32,
+    out: *mut MiPose,
+) -> bool {
+    catch_unwind(AssertUnwindSafe(|| {
+        if interpolator.is_null() || out.is_null() {
+            return false;
+        }
+
+        *out = get_pose(&(*interpolator).get_sample(t));
+        true
+    }))
+    .unwrap_or(false)
+}
+
+/// Writes `count` evenly spaced poses, the first at t = 0 and the last at t = 1.
+/// Returns false when a pointer is null, `count` is below 2 or sampling panics.
+///
+/// # Safety
+/// `interpolator` must come from `mi_interpolator_from_json` and `out` must point to
+/// `count` writable `MiPose` values.
+#[no_mangle]
+pub unsafe extern "C" fn mi_interpolator_frames(
+    interpolator: *const Interpolator,
+    count: u16,
+    out: *mut MiPose,
+) -> bool {
+    catch_unwind(AssertUnwindSafe(|| {
+        if interpolator.is_null() || out.is_null() || count < 2 {
+            return false;
+        }
+
+        let out = std::slice::from_raw_parts_mut(out, count as usize);
+        for (pose, sample) in out.iter_mut().zip((*interpolator).get_samples(count)) {
+            *pose = get_pose(&sample);
+        }
+        true
+    }))
+    .unwrap_or(false)
+}
+
+fn get_pose(sample: &AnimationSample) -> MiPose {
+    let position = sample.position();
+    let quaternion = sample.quaternion();
+    let euler = sample.euler();
+
+    MiPose {
+        position: [position.x, position.y, position.z],
+        quaternion: [quaternion.w, quaternion.i, quaternion.j, quaternion.k],
+        euler: [euler.x, euler.y, euler.z],
+        quaternion_frame: get_column_major(sample.quaternion_frame()),
+        euler_frame: get_column_major(sample.euler_frame()),
+    }
+}
+
+fn get_column_major(matrix: &Matrix4<f32>) -> [f32; 16] {
+    matrix.as_slice().try_into().unwrap()
+}
@@ -0,0 +1,252 @@
+use std::path::PathBuf;
+
+use glium::framebuffer::{DepthRenderBuffer, SimpleFrameBuffer};
+use glium::glutin::surface::WindowSurface;
+use glium::texture::{DepthFormat, RawImage2d};
+use glium::{Display, DrawParameters, Surface, Texture2d};
+use nalgebra::{Matrix4, Vector3};
+
+use movement_interpolation::animation::{Animation, EULER_METHOD_INDEX, QUATERNION_METHOD_INDEX};
+use movement_interpolation::animation_data::{AngleInput, AnimationData};
+use movement_interpolation::rotation_interpolator::SPHERICAL;
+
+use crate::camera::Camera;
+use crate::infinite_grid_drawer::InfiniteGridDrawer;
+use crate::mesh::Mesh;
+use crate::mesh_drawer::{MeshDrawer, MeshShading};
+use crate::path_drawer::PathDrawer;
+use crate::render_settings::{Lighting, RenderMode, RenderSettings};
+use crate::scene_view::SceneView;
+use crate::shadow_map::ShadowMap;
+use crate::{
+    build_animation, get_frame_discrepancies, get_frame_instances, get_recovered_euler_angles,
+    PATH_SAMPLES_COUNT,
+};
+
+const FIGURE_WIDTH: u32 = 1600;
+const FIGURE_HEIGHT: u32 = 1200;
+const PLOT_COLORS: [[[f32; 3]; 3]; 2] = [
+    [[1f32, 0.3, 0.3], [0.3, 1f32, 0.3], [0.3, 0.5, 1f32]],
+    [[0.6, 0.1, 0.1], [0.1, 0.6, 0.1], [0.1, 0.2, 0.6]],
+];
+
+pub struct FigureRenderers<'a> {
+    pub mesh: &'a Mesh,
+    pub mesh_drawer: &'a MeshDrawer,
+    pub path_drawer: &'a PathDrawer,
+    pub infinite_grid_drawer: &'a InfiniteGridDrawer,
+    pub shadow_map: &'a ShadowMap,
+}
+
+enum FramesFigure {
+    Quaternion,
+    Euler,
+    Overlay,
+}
+
+pub fn generate_teaching_set(
+    display: &Display<WindowSurface>,
+    animation_data: &AnimationData,
+    render_settings: &RenderSettings,
+    angle_input: &AngleInput,
+    camera: &Camera,
+    renderers: &FigureRenderers,
+    prefix: &str,
+) -> std::io::Result<Vec<PathBuf>> {
+    let animation = build_animation(
+        &AnimationData {
+            display_all_frames: true,
+            quaternion_interpolation_type: SPHERICAL.to_string(),
+            ..animation_data.clone()
+        },
+        angle_input,
+        &[],
+    );
+
+    let texture = Texture2d::empty(display, FIGURE_WIDTH, FIGURE_HEIGHT).unwrap();
+    let depth_buffer =
+        DepthRenderBuffer::new(display, DepthFormat::I24, FIGURE_WIDTH, FIGURE_HEIGHT).unwrap();
+
+    let mut paths = Vec::new();
+    for (figure, name) in [
+        (FramesFigure::Quaternion, "1_slerp_frames"),
+        (FramesFigure::Euler, "2_euler_frames"),
+        (FramesFigure::Overlay, "3_overlay"),
+    ] {
+        let mut target =
+            SimpleFrameBuffer::with_depth_buffer(display, &texture, &depth_buffer).unwrap();
+        draw_frames_figure(
+            &mut target,
+            display,
+            animation.as_ref(),
+            render_settings,
+            camera,
+            renderers,
+            &figure,
+        );
+        paths.push(save_texture(&texture, &format!("{}_{}.png", prefix, name))?);
+    }
+
+    let mut target =
+        SimpleFrameBuffer::with_depth_buffer(display, &texture, &depth_buffer).unwrap();
+    draw_angles_figure(
+        &mut target,
+        display,
+        animation.as_ref(),
+        animation_data,
+        renderers.path_drawer,
+    );
+    paths.push(save_texture(
+        &texture,
+        &format!("{}_4_angles_plot.png", prefix),
+    )?);
+
+    Ok(paths)
+}
+
+fn draw_frames_figure(
+    target: &mut SimpleFrameBuffer,
+    display: &Display<WindowSurface>,
+    animation: &dyn Animation,
+    render_settings: &RenderSettings,
+    camera: &Camera,
+    renderers: &FigureRenderers,
+    figure: &FramesFigure,
+) {
+    let perspective = render_settings.projection.get_matrix(
+        FIGURE_WIDTH as f32 / FIGURE_HEIGHT as f32,
+        *camera.distant(),
+    );
+    let drawing_parameters = DrawParameters {
+        depth: glium::Depth {
+            test: glium::draw_parameters::DepthTest::IfLess,
+            write: true,
+            ..Default::default()
+        },
+        backface_culling: glium::draw_parameters::BackfaceCullingMode::CullClockwise,
+        blend: glium::Blend::alpha_blending(),
+        ..Default::default()
+    };
+    let scene = SceneView::new(
+        &perspective,
+        camera.view(),
+        camera.get_position(),
+        &drawing_parameters,
+    );
+
+    target.clear_color_and_depth((1.0, 1.0, 1.0, 1.0), 1.0);
+
+    let quaternion_frames = animation.get_frames(QUATERNION_METHOD_INDEX);
+    let euler_frames = animation.get_frames(EULER_METHOD_INDEX);
+    let discrepancies = get_frame_discrepancies(quaternion_frames, euler_frames);
+    let frame_sets: Vec<(&[Matrix4<f32>], &Lighting)> = match figure {
+        FramesFigure::Quaternion => vec![(quaternion_frames, &render_settings.quaternion_lighting)],
+        FramesFigure::Euler => vec![(euler_frames, &render_settings.euler_lighting)],
+        FramesFigure::Overlay => vec![
+            (quaternion_frames, &render_settings.quaternion_lighting),
+            (euler_frames, &render_settings.euler_lighting),
+        ],
+    };
+
+    let mut shadow_instances = Vec::new();
+    for (frames, lighting) in &frame_sets {
+        let instances = get_frame_instances(
+            frames,
+            &discrepancies,
+            &vec![0f32; frames.len()],
+            render_settings.first_frame_alpha,
+        );
+        renderers.mesh_drawer.draw_instanced(
+            target,
+            display,
+            &scene,
+            &instances,
+            &MeshShading::new(&render_settings.up_axis, lighting, &RenderMode::Shaded),
+            renderers.mesh,
+        );
+        shadow_instances.extend(instances);
+    }
+    if render_settings.grid.shadows {
+        renderers.shadow_map.render(
+            display,
+            renderers.mesh,
+            &shadow_instances,
+            &render_settings.up_axis,
+            frame_sets[0].1,
+        );
+    }
+
+    if render_settings.display_path {
+        renderers.path_drawer.draw(
+            target,
+            display,
+            &scene,
+            &animation.get_path(QUATERNION_METHOD_INDEX, PATH_SAMPLES_COUNT),
+            [0.8f32, 0.6f32, 0f32],
+        );
+    }
+
+    renderers.infinite_grid_drawer.draw(
+        target,
+        &SceneView {
+            view: camera.grid_view(),
+            ..scene
+        },
+        &render_settings.projection,
+        &render_settings.grid,
+        renderers.shadow_map,
+    );
+}
+
+fn draw_angles_figure(
+    target: &mut SimpleFrameBuffer,
+    display: &Display<WindowSurface>,
+    animation: &dyn Animation,
+    animation_data: &AnimationData,
+    path_drawer: &PathDrawer,
+) {
+    let perspective = Matrix4::new_orthographic(-0.05, 1.05, -200f32, 200f32, -1f32, 1f32);
+    let view = Matrix4::identity();
+    let drawing_parameters = DrawParameters::default();
+    let scene = SceneView::new(&perspective, &view, Vector3::zeros(), &drawing_parameters);
+
+    target.clear_color_and_depth((1.0, 1.0, 1.0, 1.0), 1.0);
+
+    for y in [-180f32, -90f32, 0f32, 90f32, 180f32] {
+        path_drawer.draw(
+            target,
+            display,
+            &scene,
+            &[Vector3::new(0f32, y, 0f32), Vector3::new(1f32, y, 0f32)],
+            [0.8f32, 0.8f32, 0.8f32],
+        );
+    }
+
+    let angles =
+        get_recovered_euler_angles(animation, &animation_data.euler_order, PATH_SAMPLES_COUNT);
+    for (method_angles, colors) in angles.iter().zip(PLOT_COLORS) {
+        for (points, color) in method_angles.iter().zip(colors) {
+            path_drawer.draw(
+                target,
+                display,
+                &scene,
+                &points
+                    .iter()
+                    .map(|[x, y]| Vector3::new(*x as f32, *y as f32, 0f32))
+                    .collect::<Vec<_>>(),
+                color,
+            );
+        }
+    }
+}
+
+fn save_texture(texture: &Texture2d, name: &str) -> std::io::Result<PathBuf> {
+    let raw: RawImage2d<u8> = texture.read();
+    let mut image =
+        image::RgbaImage::from_raw(raw.width, raw.height, raw.data.into_owned()).unwrap();
+    image::imageops::flip_vertical_in_place(&mut image);
+
+    let path = PathBuf::from(name);
+    image.save(&path).map_err(std::io::Error::other)?;
+    Ok(path)
+}
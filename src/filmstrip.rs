@@ -0,0 +1,94 @@
+use std::rc::Rc;
+
+use egui::{TextureId, TextureOptions};
+use glium::framebuffer::{DepthRenderBuffer, SimpleFrameBuffer};
+use glium::glutin::surface::WindowSurface;
+use glium::texture::{DepthFormat, SrgbTexture2d};
+use glium::{Display, DrawParameters, Surface};
+use movement_interpolation::animation::Animation;
+
+use crate::mesh::Mesh;
+use crate::mesh_drawer::{MeshDrawer, MeshShading};
+use crate::render_settings::RenderMode;
+use crate::scene_view::SceneView;
+
+const FILMSTRIP_FRAMES_COUNT: usize = 8;
+const FILMSTRIP_THUMBNAIL_SIZE: u32 = 96;
+
+pub struct Filmstrip {
+    thumbnails: Vec<(f32, Rc<SrgbTexture2d>, TextureId)>,
+    depth_buffer: DepthRenderBuffer,
+}
+
+impl Filmstrip {
+    pub fn new(display: &Display<WindowSurface>, egui_glium: &mut egui_glium::EguiGlium) -> Self {
+        let thumbnails = (0..FILMSTRIP_FRAMES_COUNT)
+            .map(|i| {
+                let texture = Rc::new(
+                    SrgbTexture2d::empty(
+                        display,
+                        FILMSTRIP_THUMBNAIL_SIZE,
+                        FILMSTRIP_THUMBNAIL_SIZE,
+                    )
+                    .unwrap(),
+                );
+                let id = egui_glium
+                    .painter
+                    .register_native_texture(texture.clone(), TextureOptions::LINEAR);
+                (i as f32 / (FILMSTRIP_FRAMES_COUNT - 1) as f32, texture, id)
+            })
+            .collect();
+
+        Self {
+            thumbnails,
+            depth_buffer: DepthRenderBuffer::new(
+                display,
+                DepthFormat::I24,
+                FILMSTRIP_THUMBNAIL_SIZE,
+                FILMSTRIP_THUMBNAIL_SIZE,
+            )
+            .unwrap(),
+        }
+    }
+
+    pub fn render(
+        &self,
+        display: &Display<WindowSurface>,
+        animation: &dyn Animation,
+        scene: &SceneView,
+        shading: &MeshShading,
+        mesh: &Mesh,
+        mesh_drawer: &MeshDrawer,
+    ) {
+        let drawing_parameters = DrawParameters {
+            viewport: None,
+            ..scene.drawing_parameters.clone()
+        };
+        let scene = SceneView {
+            drawing_parameters: &drawing_parameters,
+            ..*scene
+        };
+
+        for (x, texture, _) in &self.thumbnails {
+            let mut target =
+                SimpleFrameBuffer::with_depth_buffer(display, texture.as_ref(), &self.depth_buffer)
+                    .unwrap();
+            target.clear_color_and_depth((0.1, 0.1, 0.1, 1.0), 1.0);
+            mesh_drawer.draw(
+                &mut target,
+                &scene,
+                animation.get_sample(*x).quaternion_frame(),
+                1f32,
+                &MeshShading {
+                    render_mode: &RenderMode::Shaded,
+                    ..*shading
+                },
+                mesh,
+            );
+        }
+    }
+
+    pub fn get_thumbnails(&self) -> Vec<(f32, TextureId)> {
+        self.thumbnails.iter().map(|(x, _, id)| (*x, *id)).collect()
+    }
+}
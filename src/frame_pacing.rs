@@ -0,0 +1,114 @@
+use std::num::NonZeroU32;
+use std::time::Duration;
+
+use glium::glutin::config::ConfigTemplateBuilder;
+use glium::glutin::context::ContextAttributesBuilder;
+use glium::glutin::display::GetGlDisplay;
+use glium::glutin::prelude::*;
+use glium::glutin::surface::{SurfaceAttributesBuilder, SwapInterval, WindowSurface};
+use glium::Display;
+use glutin_winit::DisplayBuilder;
+use serde::{Deserialize, Serialize};
+use winit::event_loop::EventLoop;
+use winit::raw_window_handle::HasWindowHandle;
+use winit::window::{Window, WindowAttributes};
+
+use crate::config_dir::{get_config_path, load_json, save_json};
+
+const FRAME_PACING_FILE: &str = "frame_pacing.json";
+const IDLE_FPS: u32 = 10;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FramePacing {
+    pub vsync: bool,
+    pub limit_fps: bool,
+    pub max_fps: u32,
+    pub throttle_when_idle: bool,
+    pub pause_when_inactive: bool,
+}
+
+impl FramePacing {
+    pub fn load() -> std::io::Result<Self> {
+        load_json(&get_config_path(FRAME_PACING_FILE)?)
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        save_json(&get_config_path(FRAME_PACING_FILE)?, self)
+    }
+
+    pub fn get_frame_time(&self, idle: bool) -> Option<Duration> {
+        let fps = match (self.limit_fps, idle && self.throttle_when_idle) {
+            (true, true) => self.max_fps.min(IDLE_FPS),
+            (true, false) => self.max_fps,
+            (false, true) => IDLE_FPS,
+            (false, false) => return None,
+        };
+        Some(Duration::from_secs_f64(1f64 / fps.max(1) as f64))
+    }
+
+    pub fn get_swap_interval(&self) -> SwapInterval {
+        if self.vsync {
+            SwapInterval::Wait(NonZeroU32::new(1).unwrap())
+        } else {
+            SwapInterval::DontWait
+        }
+    }
+}
+
+impl Default for FramePacing {
+    fn default() -> Self {
+        Self {
+            vsync: true,
+            limit_fps: false,
+            max_fps: 60,
+            throttle_when_idle: true,
+            pause_when_inactive: true,
+        }
+    }
+}
+
+pub fn build_display<T>(
+    event_loop: &EventLoop<T>,
+    window_attributes: WindowAttributes,
+    config_template_builder: ConfigTemplateBuilder,
+    frame_pacing: &FramePacing,
+) -> (Window, Display<WindowSurface>) {
+    let (window, gl_config) = DisplayBuilder::new()
+        .with_window_attributes(Some(window_attributes))
+        .build(event_loop, config_template_builder, |mut configs| {
+            configs.next().unwrap()
+        })
+        .unwrap();
+    let window = window.unwrap();
+
+    let (width, height): (u32, u32) = window.inner_size().into();
+    let window_handle = window.window_handle().unwrap().as_raw();
+    let surface_attributes = SurfaceAttributesBuilder::<WindowSurface>::new().build(
+        window_handle,
+        NonZeroU32::new(width.max(1)).unwrap(),
+        NonZeroU32::new(height.max(1)).unwrap(),
+    );
+    let surface = unsafe {
+        gl_config
+            .display()
+            .create_window_surface(&gl_config, &surface_attributes)
+            .unwrap()
+    };
+    let context_attributes = ContextAttributesBuilder::new().build(Some(window_handle));
+    let context = unsafe {
+        gl_config
+            .display()
+            .create_context(&gl_config, &context_attributes)
+            .unwrap()
+    }
+    .make_current(&surface)
+    .unwrap();
+
+    if let Err(error) = surface.set_swap_interval(&context, frame_pacing.get_swap_interval()) {
+        eprintln!("Swap interval setup failed: {}", error);
+    }
+
+    let display = Display::from_context_surface(context, surface).unwrap();
+    (window, display)
+}
@@ -0,0 +1,127 @@
+use std::fs::File;
+use std::io::{BufWriter, Error, ErrorKind, Write};
+use std::path::Path;
+
+use nalgebra::{Matrix4, Rotation3, UnitQuaternion};
+
+use movement_interpolation::animation::Animation;
+use movement_interpolation::animation_data::{AnimationData, RunStamp};
+
+use crate::path_analysis::MethodDivergence;
+use crate::render_settings::UpAxis;
+
+pub fn export_frames_to_csv(
+    animation: &dyn Animation,
+    animation_data: &AnimationData,
+    run_stamp: &RunStamp,
+    up_axis: &UpAxis,
+    path: &Path,
+) -> std::io::Result<()> {
+    let samples_count = animation_data.export_samples_count;
+    let quaternion_convention = &animation_data.quaternion_convention;
+    if samples_count < 2 {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Export samples count must be at least 2",
+        ));
+    }
+
+    let rotation = get_export_rotation(up_axis);
+    let reorient = |frame: &Matrix4<f32>| {
+        rotation.to_homogeneous() * frame * rotation.inverse().to_homogeneous()
+    };
+
+    let mut writer = BufWriter::new(File::create(path)?);
+    writeln!(writer, "# {}", run_stamp)?;
+    writeln!(writer, "# up axis {:?}", up_axis)?;
+    writeln!(writer, "# quaternion {}", quaternion_convention)?;
+
+    let mut header = ["t", "time", "px", "py", "pz"]
+        .into_iter()
+        .map(String::from)
+        .collect::<Vec<_>>();
+    header.extend(
+        quaternion_convention
+            .get_labels()
+            .map(|l| format!("q{}", l.to_lowercase())),
+    );
+    header.extend(
+        [
+            "roll",
+            "pitch",
+            "yaw",
+            "position_divergence",
+            "rotation_divergence",
+        ]
+        .map(String::from),
+    );
+    for prefix in ["quaternion", "euler"] {
+        for row in 0..4 {
+            for column in 0..4 {
+                header.push(format!("{}_m{}{}", prefix, row, column));
+            }
+        }
+    }
+    writeln!(writer, "{}", header.join(","))?;
+
+    for s in 0..samples_count {
+        let x = s as f32 / (samples_count - 1) as f32;
+        let sample = animation.get_sample(x);
+        let position = rotation * sample.position();
+        let quaternion = rotation * sample.quaternion() * rotation.inverse();
+        let euler = match up_axis {
+            UpAxis::Y => *sample.euler(),
+            UpAxis::Z => animation_data.euler_order.get_euler(
+                &(rotation
+                    * animation_data.euler_order.get_quaternion(sample.euler())
+                    * rotation.inverse()),
+            ),
+        };
+        let divergence =
+            MethodDivergence::from_frames(sample.quaternion_frame(), sample.euler_frame());
+
+        let mut values = vec![
+            x,
+            (x as f64 * animation_data.animation_time) as f32,
+            position.x,
+            position.y,
+            position.z,
+        ];
+        values.extend(quaternion_convention.to_components(&quaternion));
+        values.extend([
+            euler.x,
+            euler.y,
+            euler.z,
+            *divergence.position(),
+            *divergence.rotation(),
+        ]);
+        values.extend(get_matrix_values(&reorient(sample.quaternion_frame())));
+        values.extend(get_matrix_values(&reorient(sample.euler_frame())));
+
+        writeln!(
+            writer,
+            "{}",
+            values
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        )?;
+    }
+
+    writer.flush()
+}
+
+fn get_matrix_values(matrix: &Matrix4<f32>) -> Vec<f32> {
+    (0..4)
+        .flat_map(|row| (0..4).map(move |column| matrix[(row, column)]))
+        .collect()
+}
+
+fn get_export_rotation(up_axis: &UpAxis) -> UnitQuaternion<f32> {
+    let scene = up_axis
+        .get_scene_matrix()
+        .fixed_view::<3, 3>(0, 0)
+        .into_owned();
+    UnitQuaternion::from_rotation_matrix(&Rotation3::from_matrix_unchecked(scene)).inverse()
+}
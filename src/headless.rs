@@ -0,0 +1,159 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Error, ErrorKind, Write};
+use std::path::Path;
+
+use movement_interpolation::animation::AnimationSample;
+use movement_interpolation::animation_data::{AngleInput, AnimationData, RunStamp};
+use nalgebra::Matrix4;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HeadlessConfig {
+    pub animation: AnimationData,
+    pub angle_input: AngleInput,
+}
+
+impl Default for HeadlessConfig {
+    fn default() -> Self {
+        Self {
+            animation: AnimationData::new(),
+            angle_input: AngleInput::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+struct PoseQuery {
+    animation: AnimationData,
+    angle_input: AngleInput,
+    t: f32,
+}
+
+impl Default for PoseQuery {
+    fn default() -> Self {
+        Self {
+            animation: AnimationData::new(),
+            angle_input: AngleInput::default(),
+            t: 0f32,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct PoseError {
+    error: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct HeadlessOutput {
+    run_stamp: RunStamp,
+    frames: Vec<FrameRecord>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct FrameRecord {
+    t: f32,
+    time: f64,
+    position: [f32; 3],
+    quaternion: [f32; 4],
+    euler: [f32; 3],
+    quaternion_frame: [[f32; 4]; 4],
+    euler_frame: [[f32; 4]; 4],
+}
+
+pub fn run_headless(config_path: &Path, out_path: &Path) -> std::io::Result<()> {
+    let config: HeadlessConfig = serde_json::from_reader(BufReader::new(File::open(config_path)?))?;
+    let animation_data = &config.animation;
+
+    let (begin_angle, end_angle) = animation_data.get_angles(&config.angle_input);
+    let interpolator = animation_data.get_interpolator(begin_angle, end_angle);
+
+    let samples_count = animation_data.export_samples_count;
+    if samples_count < 2 {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Export samples count must be at least 2",
+        ));
+    }
+    let frames = interpolator
+        .get_samples(samples_count)
+        .iter()
+        .enumerate()
+        .map(|(s, sample)| {
+            get_frame_record(
+                sample,
+                s as f32 / (samples_count - 1) as f32,
+                animation_data.animation_time,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let mut writer = BufWriter::new(File::create(out_path)?);
+    serde_json::to_writer_pretty(
+        &mut writer,
+        &HeadlessOutput {
+            run_stamp: animation_data.get_run_stamp(),
+            frames,
+        },
+    )?;
+    writer.flush()
+}
+
+pub fn run_stream() -> std::io::Result<()> {
+    let stdout = std::io::stdout();
+    let mut writer = stdout.lock();
+
+    for line in std::io::stdin().lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        match serde_json::from_str::<PoseQuery>(&line) {
+            Ok(query) => {
+                let (begin_angle, end_angle) = query.animation.get_angles(&query.angle_input);
+                let sample = query
+                    .animation
+                    .get_interpolator(begin_angle, end_angle)
+                    .get_sample(query.t);
+                serde_json::to_writer(
+                    &mut writer,
+                    &get_frame_record(&sample, query.t, query.animation.animation_time),
+                )?;
+            }
+            Err(e) => {
+                serde_json::to_writer(
+                    &mut writer,
+                    &PoseError {
+                        error: e.to_string(),
+                    },
+                )?;
+            }
+        }
+        writeln!(writer)?;
+        writer.flush()?;
+    }
+
+    Ok(())
+}
+
+fn get_frame_record(sample: &AnimationSample, t: f32, animation_time: f64) -> FrameRecord {
+    let position = sample.position();
+    let quaternion = sample.quaternion();
+    let euler = sample.euler();
+    FrameRecord {
+        t,
+        time: t as f64 * animation_time,
+        position: [position.x, position.y, position.z],
+        quaternion: [quaternion.w, quaternion.i, quaternion.j, quaternion.k],
+        euler: [euler.x, euler.y, euler.z],
+        quaternion_frame: get_matrix_rows(sample.quaternion_frame()),
+        euler_frame: get_matrix_rows(sample.euler_frame()),
+    }
+}
+
+fn get_matrix_rows(matrix: &Matrix4<f32>) -> [[f32; 4]; 4] {
+    std::array::from_fn(|row| std::array::from_fn(|column| matrix[(row, column)]))
+}
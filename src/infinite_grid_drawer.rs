@@ -1,8 +1,9 @@
 use glium::glutin::surface::WindowSurface;
-use glium::{uniform, Display, DrawParameters, IndexBuffer, Program, Surface, VertexBuffer};
-
-use nalgebra::Matrix4;
+use glium::{uniform, Display, IndexBuffer, Program, Surface, VertexBuffer};
 
+use crate::render_settings::{Grid, Projection};
+use crate::scene_view::SceneView;
+use crate::shadow_map::ShadowMap;
 use crate::vertex::SimpleVertex;
 
 pub struct InfiniteGridDrawer {
@@ -45,8 +46,15 @@ impl InfiniteGridDrawer {
         let fragment_shader_src = r#"
             #version 410 core
             
-            float near = 0.01;
-            float far = 100;
+            uniform float near;
+            uniform float far;
+            uniform bool orthographic;
+            uniform float cell_size;
+            uniform vec3 line_color;
+            uniform float fade_distance;
+            uniform bool shadows;
+            uniform mat4 light_matrix;
+            uniform sampler2D shadow_map;
             in vec3 nearPoint;
             in vec3 farPoint;
             in mat4 fragView;
@@ -60,7 +68,7 @@ impl InfiniteGridDrawer {
                 float line = min(grid.x, grid.y);
                 float minimumz = min(derivative.y, 1);
                 float minimumx = min(derivative.x, 1);
-                vec4 color = vec4(0.2, 0.2, 0.2, 1.0 - min(line, 1.0));
+                vec4 color = vec4(line_color, 1.0 - min(line, 1.0));
                 // z axis
                 if(fragPos3D.x > -0.1 * minimumx && fragPos3D.x < 0.1 * minimumx)
                     color.z = 1.0;
@@ -76,8 +84,22 @@ impl InfiniteGridDrawer {
             float computeLinearDepth(vec3 pos) {
                 vec4 clip_space_pos = fragProj * fragView * vec4(pos.xyz, 1.0);
                 float clip_space_depth = (clip_space_pos.z / clip_space_pos.w) * 2.0 - 1.0; // put back between -1 and 1
-                float linearDepth = (2.0 * near * far) / (far + near - clip_space_depth * (far - near)); // get linear value between 0.01 and 100
-                return linearDepth / far; // normalize
+                float linearDepth = orthographic
+                    ? near + (clip_space_depth + 1.0) / 2.0 * (far - near)
+                    : (2.0 * near * far) / (far + near - clip_space_depth * (far - near)); // get linear value between near and far
+                return linearDepth;
+            }
+            float computeShadow(vec3 pos) {
+                vec4 light_space_pos = light_matrix * vec4(pos, 1.0);
+                vec3 coord = light_space_pos.xyz / light_space_pos.w * 0.5 + 0.5;
+                if(coord.x < 0.0 || coord.x > 1.0 || coord.y < 0.0 || coord.y > 1.0 || coord.z > 1.0)
+                    return 0.0;
+                vec2 texel = 1.0 / textureSize(shadow_map, 0);
+                float shadow = 0.0;
+                for(int x = -2; x <= 2; x++)
+                    for(int y = -2; y <= 2; y++)
+                        shadow += float(coord.z - 0.002 > texture(shadow_map, coord.xy + vec2(x, y) * texel).r);
+                return shadow / 25.0;
             }
             void main() {
                 float t = -nearPoint.y / (farPoint.y - nearPoint.y);
@@ -87,10 +109,14 @@ impl InfiniteGridDrawer {
                     gl_DepthRange.near + gl_DepthRange.far) / 2.0;
             
                 float linearDepth = computeLinearDepth(fragPos3D);
-                float fading = max(0, (0.5 - linearDepth));
+                float fading = max(0, 0.5 * (1.0 - linearDepth / fade_distance));
             
-                outColor = (grid(fragPos3D, 10) + grid(fragPos3D, 1))* float(t > 0); // adding multiple resolution for the grid
+                outColor = (grid(fragPos3D, 10 / cell_size) + grid(fragPos3D, 1 / cell_size))* float(t > 0); // adding multiple resolution for the grid
                 outColor.a *= fading;
+
+                float shadow = shadows ? 0.6 * computeShadow(fragPos3D) * float(t > 0) : 0.0;
+                float alpha = max(outColor.a, shadow);
+                outColor = vec4(outColor.rgb * outColor.a / max(alpha, 0.0001), alpha);
             }
         "#;
 
@@ -120,21 +146,36 @@ impl InfiniteGridDrawer {
 
     pub fn draw(
         &self,
-        target: &mut glium::Frame,
-        perspective: &Matrix4<f32>,
-        view: &Matrix4<f32>,
-        drawing_parameters: &DrawParameters,
+        target: &mut impl Surface,
+        scene: &SceneView,
+        projection: &Projection,
+        grid: &Grid,
+        shadow_map: &ShadowMap,
     ) {
+        if !grid.visible {
+            return;
+        }
+
+        let light_matrix = shadow_map.get_light_matrix();
         target
             .draw(
                 &self.vertex_buffer,
                 &self.index_buffer,
                 &self.program,
                 &uniform! {
-                    perspective: perspective.data.0,
-                    view: view.data.0,
+                    perspective: scene.perspective.data.0,
+                    view: scene.view.data.0,
+                    near: projection.near,
+                    far: projection.far,
+                    orthographic: projection.orthographic,
+                    cell_size: grid.cell_size,
+                    line_color: grid.line_color,
+                    fade_distance: grid.fade_distance,
+                    shadows: grid.shadows && light_matrix.is_some(),
+                    light_matrix: light_matrix.unwrap_or_default().data.0,
+                    shadow_map: shadow_map.depth_texture().sampled(),
                 },
-                &drawing_parameters,
+                scene.drawing_parameters,
             )
             .unwrap();
     }
@@ -0,0 +1,13 @@
+pub mod animation;
+pub mod animation_data;
+pub mod ffi;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod random;
+pub mod rotation_interpolator;
+pub mod slow_motion;
+
+pub use animation::{
+    Animation, AnimationAngle, AnimationSample, ContinuousAnimation, ContinuousAnimationBuilder,
+    DiscreteFrameAnimation, DiscreteFrameAnimationBuilder, Interpolator, InterpolatorBuilder,
+};
@@ -1,534 +1,1858 @@
-mod animation;
-mod animation_data;
+mod animation_player;
+mod app;
+mod appearance;
+mod attitude_hud;
+mod autosave;
+mod axes_drawer;
+mod bezier_editor;
 mod block;
-mod block_drawer;
+mod camera;
+mod cli;
+mod code_export;
+mod config_dir;
+mod figures;
+mod filmstrip;
+mod frame_pacing;
+mod frames_export;
+mod headless;
 mod infinite_grid_drawer;
+mod mesh;
+mod mesh_cache;
+mod mesh_drawer;
+mod panel_dock;
+mod path_analysis;
+mod path_drawer;
+mod path_handle;
+mod ply_export;
+mod pose_readout;
+mod presets;
+mod reference_trajectory;
+mod render_settings;
+mod rotation_gizmo;
+mod rotation_sphere;
+mod scene_view;
+mod screenshot;
+mod session_log;
+mod shadow_map;
+mod shortcuts;
+mod soak;
+mod startup_config;
 mod vertex;
+mod video_export;
+mod wizard;
 
-use std::{f32::consts::PI, ops::RangeInclusive};
-
-use animation::{
-    Animation, AnimationAngle, ContinuousAnimationBuilder, DiscreteFrameAnimationBuilder,
+use std::{
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
 };
-use animation_data::{AnimationData, QuaternionInterpolationType};
-use block::Block;
-use block_drawer::BlockDrawer;
+
+use animation_player::AnimationPlayer;
+use app::App;
+use appearance::{Appearance, Theme};
+use attitude_hud::{draw_attitude_hud, Attitude};
+use axes_drawer::AXIS_COLORS;
+use bezier_editor::bezier_editor;
+use camera::CameraPreset;
 use chrono::Local;
+use clap::Parser;
+use cli::Cli;
+use code_export::export_rust_snippet;
 use egui::{
-    emath, Button, Checkbox, DragValue, Label, RadioButton, RichText, ViewportId, WidgetText,
+    emath, load::SizedTexture, pos2, vec2, Align2, Button, Checkbox, Color32, ComboBox, CursorIcon,
+    DragValue, FontId, Id, ImageButton, Label, LayerId, Modifiers, Order, RadioButton, Response,
+    RichText, Sense, Slider, Stroke, TextEdit, TextureId, ViewportId, WidgetInfo, WidgetText,
+    WidgetType,
 };
+use egui_dock::{DockArea, DockState, Style};
 use egui_flex::{item, Flex};
-use glium::{Blend, Rect, Surface};
-use infinite_grid_drawer::InfiniteGridDrawer;
-use nalgebra::{Matrix4, Point3, Quaternion, Vector3, Vector4};
-use winit::event::{self, ElementState, MouseButton};
+use egui_plot::{Legend, Line, Plot};
+use frame_pacing::{build_display, FramePacing};
+use frames_export::export_frames_to_csv;
+use glium::glutin::config::ConfigTemplateBuilder;
+use headless::{run_headless, run_stream};
+use movement_interpolation::animation::{
+    get_frame_quaternion, is_near_gimbal_lock, Animation, AnimationAngle,
+    ContinuousAnimationBuilder, DiscreteFrameAnimationBuilder, EULER_METHOD_INDEX,
+    MAX_FRAMES_COUNT, QUATERNION_METHOD_INDEX,
+};
+use movement_interpolation::animation_data::{
+    AngleInput, AnimationData, Easing, EulerOrder, EulerRepresentation, FrameSpacing, FrameStep,
+    NumericalPolicy, Obstacle, ObstacleShape, PoseGizmo, PositionInterpolationType,
+    QuaternionComponentOrder, QuaternionConvention, QuaternionMultiplication,
+    QuaternionNormalizationPolicy, RunStamp, TimeAccumulation, EULER_METHOD,
+};
+use movement_interpolation::rotation_interpolator::{get_rotation_interpolators, MATRIX_LERP};
+use movement_interpolation::slow_motion::SlowMotionProfile;
+use nalgebra::{Matrix4, Quaternion, UnitQuaternion, Vector3};
+use panel_dock::{PanelTab, PanelTabViewer};
+use path_analysis::{BoundingBox, MethodDivergence, PathWarnings};
+use path_handle::PathHandle;
+use ply_export::export_quaternion_path_to_ply;
+use pose_readout::{build_pose_readout, get_pose_readout};
+use presets::get_presets;
+use reference_trajectory::ReferenceTrajectory;
+use render_settings::{
+    BlockGeometry, Grid, Light, Lighting, Projection, RenderMode, RenderSettings, SceneObjectType,
+    ShadingMode, UpAxis, VideoOutput, VideoSettings,
+};
+use rotation_gizmo::build_rotation_gizmo;
+use rotation_sphere::{draw_rotation_sphere, get_body_direction, get_rotation_traces};
+use screenshot::ScreenshotRegion;
+use session_log::SessionLog;
+use shortcuts::{build_shortcuts_window, ShortcutAction, Shortcuts};
+use soak::run_soak;
+use startup_config::StartupConfig;
+use vertex::InstanceData;
+use wizard::build_wizard;
+
+const PATH_SAMPLES_COUNT: u16 = 100;
+const REFERENCE_TRAJECTORY_COLOR: [f32; 3] = [1f32, 0.3f32, 0.8f32];
+const QUATERNION_NORM_TOLERANCE: f32 = 1e-2;
+const LIVE_UPDATE_DEBOUNCE_MS: i64 = 300;
+const OBSTACLE_COLOR: [f32; 3] = [0.6f32, 0.6f32, 0.6f32];
+const OBSTACLE_ALPHA: f32 = 0.6;
+const POSE_GHOST_ALPHA: f32 = 0.25;
+const MATRIX_LERP_GHOST_ALPHA: f32 = 0.5;
+const KEYBOARD_STEP_ID: &str = "keyboard_step";
+const KEYBOARD_ORBIT_STEP: f32 = 0.05;
+const VIEWPORT_SPLIT_RANGE: RangeInclusive<f32> = 0.1..=0.9;
+const SPLITTER_WIDTH: f32 = 6f32;
+const DEFAULT_MSAA_SAMPLES: u8 = 4;
+const ROTATION_AXIS_STEP: f32 = 1e-2;
+const ROTATION_AXIS_MIN_ANGLE: f32 = 1e-5;
+const ROTATION_AXIS_ARROW_LENGTH: f32 = 5.5;
+const PATH_HANDLE_RADIUS: f32 = 7f32;
+const AXIS_LABEL_DISTANCE: f32 = 2f32;
+const SCREENSHOT_REGION_ID: &str = "screenshot_region";
 
 fn main() {
-    let mut width = 1600;
-    let mut height = 1200;
+    let cli = Cli::parse();
+    if cli.headless {
+        if let Err(error) = run_headless(&cli.headless_config, &cli.out) {
+            eprintln!("Headless run failed: {}", error);
+            std::process::exit(1);
+        }
+        return;
+    }
+    if cli.soak {
+        let seed = cli
+            .seed
+            .unwrap_or_else(|| Local::now().timestamp_nanos_opt().unwrap_or(0) as u64);
+        eprintln!("Soak run with seed {}", seed);
+        if let Err(error) = run_soak(seed, cli.iterations, &cli.log) {
+            eprintln!("Soak run failed: {}", error);
+            std::process::exit(1);
+        }
+        return;
+    }
+    if cli.stream {
+        if let Err(error) = run_stream() {
+            eprintln!("Streaming failed: {}", error);
+            std::process::exit(1);
+        }
+        return;
+    }
 
-    let event_loop = winit::event_loop::EventLoop::builder().build().unwrap();
-    let (window, display) = glium::backend::glutin::SimpleWindowBuilder::new()
-        .with_title("Movement interpolation")
-        .with_inner_size(width, height)
-        .build(&event_loop);
+    let startup_config = cli
+        .config
+        .as_ref()
+        .map(|path| {
+            StartupConfig::load(path).unwrap_or_else(|error| {
+                eprintln!("Startup config load failed: {}", error);
+                std::process::exit(1);
+            })
+        })
+        .unwrap_or_default();
+    let width = cli.width.unwrap_or(startup_config.width);
+    let height = cli.height.unwrap_or(startup_config.height);
+    let msaa_samples = cli.msaa.unwrap_or(startup_config.msaa);
+    let mut config_template_builder = ConfigTemplateBuilder::new();
+    if msaa_samples > 0 {
+        config_template_builder = config_template_builder.with_multisampling(msaa_samples);
+    }
+
+    let event_loop =
+        winit::event_loop::EventLoop::<egui_winit::accesskit_winit::Event>::with_user_event()
+            .build()
+            .unwrap();
+    let frame_pacing = FramePacing::load().unwrap_or_default();
+    let (window, display) = build_display(
+        &event_loop,
+        winit::window::Window::default_attributes()
+            .with_visible(false)
+            .with_title("Movement interpolation")
+            .with_inner_size(winit::dpi::PhysicalSize::new(width, height)),
+        config_template_builder,
+        &frame_pacing,
+    );
 
     let mut egui_glium =
         egui_glium::EguiGlium::new(ViewportId::ROOT, &display, &window, &event_loop);
+    egui_glium
+        .egui_winit
+        .init_accesskit(&window, event_loop.create_proxy());
+    window.set_visible(true);
 
-    let mut drawing_parameters = glium::DrawParameters {
-        depth: glium::Depth {
-            test: glium::draw_parameters::DepthTest::IfLess,
-            write: true,
-            ..Default::default()
-        },
-        backface_culling: glium::draw_parameters::BackfaceCullingMode::CullClockwise,
-        blend: Blend::alpha_blending(),
-        ..Default::default()
-    };
-
-    let mut perspective = Matrix4::new_perspective(
-        (width / 2) as f32 / height as f32,
-        std::f32::consts::PI / 2.0,
-        0.1,
-        100.0,
-    );
-
-    let mut mouse_position = (0.0, 0.0);
-    let mut camera_direction = Vector3::new(0.0f32, 0.0, 1.0);
-    let mut camera_angle = Vector3::new(0.0f32, 0.0, 0.0);
-    let mut camera_up = Vector3::new(0.0f32, 1.0, 0.0);
-    let mut camera_distant = 5.0f32;
-    let mut view = Matrix4::look_at_rh(
-        &Point3::from_slice((-camera_distant * camera_direction).as_slice()),
-        &Point3::new(0.0, 0.0, 0.0),
-        &camera_up,
+    let mut app = App::new(
+        window,
+        display,
+        egui_glium,
+        &cli,
+        startup_config,
+        frame_pacing,
     );
-    let mut camera_move_button_pressed = false;
+    event_loop.run_app(&mut app).unwrap();
+}
 
-    let infinite_grid_drawer = InfiniteGridDrawer::new(&display);
+#[derive(Default)]
+struct UiRequests {
+    run: Option<AngleInput>,
+    fit_view: bool,
+    camera_preset: Option<CameraPreset>,
+    teaching_set_requested: bool,
+    video_export_requested: bool,
+    mesh_load_requested: bool,
+    reset_requested: bool,
+    seek: Option<f32>,
+    playback_action: Option<ShortcutAction>,
+}
 
-    let mut animation_data = AnimationData::new();
-    let mut animation: Option<Box<dyn Animation>> = None;
+struct UiState<'a> {
+    animation_data: &'a mut AnimationData,
+    render_settings: &'a mut RenderSettings,
+    status_message: &'a mut Option<String>,
+    reference_trajectory: &'a mut Option<ReferenceTrajectory>,
+    viewport_split: &'a mut f32,
+    appearance: &'a mut Appearance,
+    frame_pacing: &'a mut FramePacing,
+    shortcuts: &'a mut Shortcuts,
+    shortcut_capture: &'a mut Option<ShortcutAction>,
+    shortcuts_open: &'a mut bool,
+    wizard_open: &'a mut bool,
+    screenshot_request: &'a mut Option<ScreenshotRegion>,
+}
 
-    let block = Block::generate(10, &display);
-    let block_drawer = BlockDrawer::new(&display);
+struct RunView<'a> {
+    player: &'a AnimationPlayer,
+    displayed_models: &'a [Matrix4<f32>; 2],
+    path_warnings: &'a Option<PathWarnings>,
+    run_stamp: &'a Option<RunStamp>,
+    bounding_box: &'a Option<BoundingBox>,
+    numerical_warning: &'a Option<String>,
+    max_divergence: &'a Option<MethodDivergence>,
+    gimbal_lock_intervals: &'a [(f32, f32)],
+    last_angle_input: &'a Option<AngleInput>,
+    ghost_angle_input: &'a AngleInput,
+    filmstrip_thumbnails: &'a [(f32, TextureId)],
+    session_log: &'a SessionLog,
+    path_handle: &'a PathHandle,
+    fps: f64,
+}
 
-    let mut previous_time = Local::now();
+fn build_ui(
+    egui_glium: &mut egui_glium::EguiGlium,
+    window: &winit::window::Window,
+    dock_state: &mut DockState<PanelTab>,
+    state: &mut UiState,
+    run: &RunView,
+) -> UiRequests {
+    let mut requests = UiRequests::default();
 
-    #[allow(deprecated)]
-    let _ = event_loop.run(move |event, window_target| {
-        let mut redraw = || {
-            let current_time = Local::now();
-            let duration = current_time - previous_time;
-            let duration_in_seconds = duration.num_microseconds().unwrap_or(1) as f64 / 1_000_000.0;
-            let fps = 1.0 / duration_in_seconds;
-            previous_time = current_time;
+    egui_glium.run(window, |egui_ctx| {
+        if *state.wizard_open {
+            if let Some(setup) = build_wizard(egui_ctx, state.wizard_open, state.render_settings) {
+                *state.animation_data = setup.animation_data;
+                *state.viewport_split = setup.viewport_split;
+                requests.camera_preset = setup.camera_preset;
+                requests.run = setup.run;
+            }
+        }
 
-            build_ui(
-                &mut egui_glium,
-                &window,
-                &mut animation_data,
-                &mut animation,
-                fps,
+        if *state.shortcuts_open {
+            build_shortcuts_window(
+                egui_ctx,
+                state.shortcuts,
+                state.shortcut_capture,
+                state.shortcuts_open,
             );
+        }
 
-            window.request_redraw();
+        let mut viewer = PanelTabViewer {
+            build_tab: |ui: &mut egui::Ui, tab: &PanelTab| match tab {
+                PanelTab::PoseEditor => build_pose_editor_tab(ui, state, run, &mut requests),
+                PanelTab::Playback => build_playback_tab(ui, state, run, &mut requests),
+                PanelTab::Analysis => build_analysis_tab(ui, state, run, &mut requests),
+                PanelTab::RenderSettings => {
+                    build_render_settings_tab(ui, state, run, &mut requests)
+                }
+                PanelTab::Log => build_log_tab(ui, state, run),
+            },
+        };
+        DockArea::new(dock_state)
+            .style(Style::from_egui(&egui_ctx.style()))
+            .show(egui_ctx, &mut viewer);
 
-            let mut target = display.draw();
+        build_plot_windows(egui_ctx, state, run);
+        build_viewport_overlays(egui_ctx, state, run, &mut requests);
+    });
 
-            target.clear_color_and_depth((0.0, 0.0, 0.0, 1.0), 1.0);
+    requests
+}
 
-            drawing_parameters.viewport = Some(Rect {
-                left: 0,
-                bottom: 0,
-                width: width / 2,
-                height: height,
+fn build_pose_editor_tab(
+    ui: &mut egui::Ui,
+    state: &mut UiState,
+    run: &RunView,
+    requests: &mut UiRequests,
+) {
+    let egui_ctx = ui.ctx().clone();
+    let animation = run.player.get_animation();
+    let animation_data = &mut *state.animation_data;
+    let render_settings = &mut *state.render_settings;
+    let status_message = &mut *state.status_message;
+    let shortcuts_open = &mut *state.shortcuts_open;
+    let wizard_open = &mut *state.wizard_open;
+    let displayed_models = run.displayed_models;
+    let reset_requested = &mut requests.reset_requested;
+    Flex::vertical().show(ui, |flex| {
+        build_presets_settings(flex, animation_data);
+        if flex
+            .add(item(), Button::new("Start wizard"))
+            .inner
+            .clicked()
+        {
+            *wizard_open = true;
+        }
+        if flex.add(item(), Button::new("Shortcuts")).inner.clicked() {
+            *shortcuts_open = true;
+        }
+        if flex
+            .add(item(), Button::new("Reset to defaults"))
+            .inner
+            .clicked()
+        {
+            *reset_requested = true;
+        }
+        flex.add(
+            item().align_self(egui_flex::FlexAlign::Start),
+            Checkbox::new(&mut render_settings.live_update, "Live update"),
+        );
+        let mut keyboard_step = get_keyboard_step(&egui_ctx);
+        build_number_settings(
+            flex,
+            &mut keyboard_step,
+            "Keyboard step (Shift + Up/Down)",
+            Some(0.1f32),
+            Some(0.01f64..=100f64),
+        );
+        egui_ctx.data_mut(|d| d.insert_temp(Id::new(KEYBOARD_STEP_ID), keyboard_step));
+        flex.add_flex(item(), Flex::horizontal(), |flex| {
+            build_xyz_settings(
+                flex,
+                &mut animation_data.begin_position,
+                RichText::new("Begin Position").size(15f32),
+            );
+            build_xyz_settings(
+                flex,
+                &mut animation_data.end_position,
+                RichText::new("End Position").size(15f32),
+            );
+        });
+        build_waypoints_settings(flex, &mut animation_data.waypoints);
+        build_combo_box_settings(
+            flex,
+            &mut animation_data.position_interpolation_type,
+            "Position interpolation",
+            &[
+                (PositionInterpolationType::Linear, "Linear"),
+                (PositionInterpolationType::CatmullRom, "Catmull-Rom"),
+                (PositionInterpolationType::Tcb, "Kochanek-Bartels (TCB)"),
+                (PositionInterpolationType::Hermite, "Hermite"),
+            ],
+        );
+        if animation_data.position_interpolation_type == PositionInterpolationType::CatmullRom {
+            build_number_settings(
+                flex,
+                &mut animation_data.catmull_rom_tension,
+                "Tension",
+                Some(0.01f32),
+                Some(0.0..=1.0),
+            );
+        }
+        if animation_data.position_interpolation_type == PositionInterpolationType::Tcb {
+            build_tcb_settings(
+                flex,
+                &mut animation_data.tcb_parameters,
+                animation_data.waypoints.len(),
+            );
+        }
+        if animation_data.position_interpolation_type == PositionInterpolationType::Hermite {
+            flex.add_flex(item(), Flex::horizontal(), |flex| {
+                build_xyz_settings(flex, &mut animation_data.begin_velocity, "Begin velocity");
+                build_xyz_settings(flex, &mut animation_data.end_velocity, "End velocity");
             });
+        }
 
-            if animation.is_some() {
-                let mut a = animation.take().unwrap();
-
-                a.make_step(duration_in_seconds);
-
-                for model in a.get_quaternion_frames() {
-                    block_drawer.draw(
-                        &mut target,
-                        &perspective,
-                        &view,
-                        &model,
-                        -camera_distant * camera_direction,
-                        &block,
-                        &drawing_parameters,
-                    );
+        build_combo_box_settings(
+            flex,
+            &mut render_settings.pose_gizmo,
+            "Rotation gizmo",
+            &[
+                (PoseGizmo::Off, "Off"),
+                (PoseGizmo::Begin, "Begin"),
+                (PoseGizmo::End, "End"),
+            ],
+        );
+        flex.add_flex(item(), Flex::horizontal(), |flex| {
+            build_number_settings(flex, &mut animation_data.seed, "Seed", None::<f64>, None);
+            if flex.add(item(), Button::new("New seed")).inner.clicked() {
+                animation_data.seed = Local::now().timestamp_nanos_opt().unwrap_or(0) as u64;
+            }
+        });
+        flex.add_flex(item(), Flex::horizontal(), |flex| {
+            let label = flex.add(item(), Label::new("Matrix")).inner;
+            flex.add(
+                item().grow(1.0),
+                TextEdit::multiline(&mut animation_data.pasted_matrix)
+                    .hint_text("3x3 or 4x4 matrix, row-major"),
+            )
+            .inner
+            .labelled_by(label.id);
+            let mut decomposed = None;
+            if flex
+                .add(item(), Button::new("Decompose to begin"))
+                .inner
+                .clicked()
+            {
+                decomposed = Some(animation_data.set_begin_from_pasted_matrix());
+            }
+            if flex
+                .add(item(), Button::new("Decompose to end"))
+                .inner
+                .clicked()
+            {
+                decomposed = Some(animation_data.set_end_from_pasted_matrix());
+            }
+            match decomposed {
+                Some(Ok(())) => {
+                    *status_message = Some("Matrix decomposed".to_string());
                 }
-
-                animation = Some(a);
-            } else {
-                block_drawer.draw(
-                    &mut target,
-                    &perspective,
-                    &view,
-                    &Matrix4::identity(),
-                    -camera_distant * camera_direction,
-                    &block,
-                    &drawing_parameters,
-                );
+                Some(Err(e)) => {
+                    *status_message = Some(format!("Matrix decomposition failed: {}", e));
+                }
+                None => {}
             }
+        });
+        if flex
+            .add(item(), |ui: &mut egui::Ui| {
+                ui.add_enabled(
+                    animation.is_some(),
+                    Button::new("Set begin to current pose"),
+                )
+            })
+            .inner
+            .clicked()
+        {
+            animation_data.set_begin_pose(&displayed_models[0], &displayed_models[1]);
+        }
+        if flex
+            .add(item(), |ui: &mut egui::Ui| {
+                ui.add_enabled(animation.is_some(), Button::new("Set end to current pose"))
+            })
+            .inner
+            .clicked()
+        {
+            animation_data.set_end_pose(&displayed_models[0], &displayed_models[1]);
+        }
+        flex.add_flex(item(), Flex::horizontal(), |flex| {
+            if flex
+                .add(item(), Button::new("Swap begin/end"))
+                .inner
+                .clicked()
+            {
+                animation_data.swap_poses();
+            }
+            if flex
+                .add(item(), Button::new("Copy begin → end"))
+                .inner
+                .clicked()
+            {
+                animation_data.copy_begin_to_end();
+            }
+        });
+        flex.add_flex(item(), Flex::horizontal(), |flex| {
+            if flex.add(item(), Button::new("Randomize")).inner.clicked() {
+                animation_data.randomize_poses();
+            }
+            build_number_settings(
+                flex,
+                &mut animation_data.random_position_range,
+                "Box",
+                Some(0.1),
+                Some(0f32..=100f32),
+            );
+        });
+        flex.add_flex(
+            item(),
+            Flex::horizontal()
+                .grow_items(1.0)
+                .align_items(egui_flex::FlexAlign::Stretch),
+            |flex| {
+                flex.add_flex(item(), Flex::vertical(), |flex| {
+                    let mut quaternion_edited = false;
+                    flex.add_flex(item(), Flex::horizontal(), |flex| {
+                        quaternion_edited |= build_quaternion_settings(
+                            flex,
+                            &mut animation_data.begin_rotation_quaternion,
+                            &animation_data.quaternion_convention,
+                            RichText::new("Begin Quternion").size(15f32),
+                        );
+                        quaternion_edited |= build_quaternion_settings(
+                            flex,
+                            &mut animation_data.end_rotation_quaternion,
+                            &animation_data.quaternion_convention,
+                            RichText::new("End Quternion").size(15f32),
+                        );
+                    });
 
-            infinite_grid_drawer.draw(&mut target, &perspective, &view, &drawing_parameters);
+                    if quaternion_edited
+                        && animation_data.quaternion_normalization_policy
+                            == QuaternionNormalizationPolicy::NormalizeOnEdit
+                    {
+                        animation_data.normalize_quaternion_inputs();
+                    }
 
-            drawing_parameters.viewport = Some(Rect {
-                left: width / 2,
-                bottom: 0,
-                width: width / 2,
-                height: height,
-            });
+                    build_combo_box_settings(
+                        flex,
+                        &mut animation_data.quaternion_normalization_policy,
+                        "Normalization",
+                        &[
+                            (
+                                QuaternionNormalizationPolicy::NormalizeOnEdit,
+                                "Normalize on edit",
+                            ),
+                            (
+                                QuaternionNormalizationPolicy::NormalizeAtRun,
+                                "Normalize at run",
+                            ),
+                            (QuaternionNormalizationPolicy::Raw, "Use raw"),
+                        ],
+                    );
+                    build_combo_box_settings(
+                        flex,
+                        &mut animation_data.quaternion_convention.component_order,
+                        "Component order",
+                        &[
+                            (QuaternionComponentOrder::Wxyz, "wxyz"),
+                            (QuaternionComponentOrder::Xyzw, "xyzw"),
+                        ],
+                    );
+                    build_combo_box_settings(
+                        flex,
+                        &mut animation_data.quaternion_convention.multiplication,
+                        "Convention",
+                        &[
+                            (QuaternionMultiplication::Hamilton, "Hamilton"),
+                            (QuaternionMultiplication::Jpl, "JPL"),
+                        ],
+                    );
+                    flex.add(
+                        item().align_self(egui_flex::FlexAlign::Start),
+                        Label::new(if animation_data.normalize_quaternions() {
+                            "Math receives unit quaternions"
+                        } else {
+                            "Math receives raw quaternions"
+                        }),
+                    );
 
-            if animation.is_some() {
-                let a = animation.take().unwrap();
-
-                for model in a.get_euler_frames() {
-                    block_drawer.draw(
-                        &mut target,
-                        &perspective,
-                        &view,
-                        &model,
-                        -camera_distant * camera_direction,
-                        &block,
-                        &drawing_parameters,
+                    build_euler_representation_settings(
+                        flex,
+                        &AnimationAngle::new_quternion(animation_data.get_begin_quaternion()),
+                        &mut animation_data.begin_euler_representation,
+                        &animation_data.euler_order,
+                        &animation_data.numerical_policy,
+                        "Begin Euler representation",
+                    );
+                    build_euler_representation_settings(
+                        flex,
+                        &AnimationAngle::new_quternion(animation_data.get_end_quaternion()),
+                        &mut animation_data.end_euler_representation,
+                        &animation_data.euler_order,
+                        &animation_data.numerical_policy,
+                        "End Euler representation",
                     );
-                }
 
-                animation = Some(a);
-            } else {
-                block_drawer.draw(
-                    &mut target,
-                    &perspective,
-                    &view,
-                    &Matrix4::identity(),
-                    -camera_distant * camera_direction,
-                    &block,
-                    &drawing_parameters,
-                );
-            }
+                    let rotation_interpolators = get_rotation_interpolators();
+                    build_combo_box_settings(
+                        flex,
+                        &mut animation_data.quaternion_interpolation_type,
+                        "Interpolation",
+                        &rotation_interpolators
+                            .iter()
+                            .map(|i| (i.get_id().to_string(), i.get_name()))
+                            .collect::<Vec<_>>(),
+                    );
+                    flex.add(
+                        item().align_self(egui_flex::FlexAlign::Start),
+                        Label::new(animation_data.get_rotation_interpolator().get_description()),
+                    );
 
-            infinite_grid_drawer.draw(&mut target, &perspective, &view, &drawing_parameters);
+                    if flex.add(item(), Button::new("run")).inner.clicked() {
+                        requests.run = Some(AngleInput::Quaternion);
+                    }
+                });
 
-            egui_glium.paint(&display, &mut target);
+                flex.add_flex(item(), Flex::vertical(), |flex| {
+                    flex.add_flex(item(), Flex::horizontal(), |flex| {
+                        build_xyz_settings(
+                            flex,
+                            &mut animation_data.begin_rotation_xyz,
+                            RichText::new("Begin Euler Angle").size(15f32),
+                        );
+                        build_xyz_settings(
+                            flex,
+                            &mut animation_data.end_rotation_xyz,
+                            RichText::new("End Euler Angle").size(15f32),
+                        );
+                    });
 
-            target.finish().unwrap();
-        };
+                    build_combo_box_settings(
+                        flex,
+                        &mut animation_data.euler_order,
+                        "Euler order",
+                        &[
+                            (EulerOrder::XYZ, "XYZ"),
+                            (EulerOrder::XZY, "XZY"),
+                            (EulerOrder::YXZ, "YXZ"),
+                            (EulerOrder::YZX, "YZX"),
+                            (EulerOrder::ZXY, "ZXY"),
+                            (EulerOrder::ZYX, "ZYX"),
+                        ],
+                    );
+                    build_euler_representation_settings(
+                        flex,
+                        &AnimationAngle::new_euler(animation_data.get_begin_euler()),
+                        &mut animation_data.begin_euler_representation,
+                        &animation_data.euler_order,
+                        &animation_data.numerical_policy,
+                        "Begin representation",
+                    );
+                    build_euler_representation_settings(
+                        flex,
+                        &AnimationAngle::new_euler(animation_data.get_end_euler()),
+                        &mut animation_data.end_euler_representation,
+                        &animation_data.euler_order,
+                        &animation_data.numerical_policy,
+                        "End representation",
+                    );
 
-        match event {
-            event::Event::WindowEvent { event, .. } => {
-                use event::WindowEvent;
-                match &event {
-                    WindowEvent::RedrawRequested => redraw(),
-                    WindowEvent::CloseRequested | WindowEvent::Destroyed => {
-                        window_target.exit();
+                    if flex.add(item(), Button::new("run")).inner.clicked() {
+                        requests.run = Some(AngleInput::Euler);
                     }
-                    WindowEvent::Resized(new_size) => {
-                        display.resize((*new_size).into());
-                        perspective = Matrix4::new_perspective(
-                            (new_size.width / 2) as f32 / new_size.height as f32,
-                            std::f32::consts::PI / 2.0,
-                            0.1,
-                            100.0,
+                });
+
+                flex.add_flex(item(), Flex::vertical(), |flex| {
+                    flex.add_flex(item(), Flex::horizontal(), |flex| {
+                        build_axis_angle_settings(
+                            flex,
+                            &mut animation_data.begin_rotation_axis,
+                            &mut animation_data.begin_rotation_angle,
+                            RichText::new("Begin Axis-Angle").size(15f32),
                         );
-                        width = new_size.width;
-                        height = new_size.height;
-                    }
-                    WindowEvent::CursorMoved { position, .. } => {
-                        let delta = (position.x - mouse_position.0, position.y - mouse_position.1);
-                        mouse_position = (position.x, position.y);
-                        if camera_move_button_pressed {
-                            camera_angle.x += delta.1 as f32 * 0.01;
-                            camera_angle.y += delta.0 as f32
-                                * 0.01
-                                * if camera_angle.x.cos() < 0.0 {
-                                    -1.0
-                                } else {
-                                    1.0
-                                };
-                            camera_direction =
-                                (Matrix4::from_euler_angles(camera_angle.x, camera_angle.y, 0.0)
-                                    * Vector4::new(0.0, 0.0, 1.0, 0.0))
-                                .xyz();
-                            camera_up =
-                                (Matrix4::from_euler_angles(camera_angle.x, camera_angle.y, 0.0)
-                                    * Vector4::new(0.0, 1.0, 0.0, 0.0))
-                                .xyz();
-                            view = Matrix4::look_at_rh(
-                                &Point3::from_slice(
-                                    (-camera_distant * camera_direction).as_slice(),
-                                ),
-                                &Point3::new(0.0, 0.0, 0.0),
-                                &camera_up,
-                            );
-                        }
-                    }
-                    WindowEvent::MouseInput { state, button, .. } => {
-                        if *button == MouseButton::Middle {
-                            camera_move_button_pressed = *state == ElementState::Pressed;
-                        }
+                        build_axis_angle_settings(
+                            flex,
+                            &mut animation_data.end_rotation_axis,
+                            &mut animation_data.end_rotation_angle,
+                            RichText::new("End Axis-Angle").size(15f32),
+                        );
+                    });
+
+                    if flex.add(item(), Button::new("run")).inner.clicked() {
+                        requests.run = Some(AngleInput::AxisAngle);
                     }
-                    WindowEvent::KeyboardInput {
-                        device_id: _,
-                        event,
-                        is_synthetic: _,
-                    } => {
-                        if event.logical_key == "c" && event.state.is_pressed() && !event.repeat {
-                            camera_move_button_pressed = !camera_move_button_pressed;
+                });
+            },
+        );
+    });
+}
+
+fn build_playback_tab(
+    ui: &mut egui::Ui,
+    state: &mut UiState,
+    run: &RunView,
+    requests: &mut UiRequests,
+) {
+    let animation = run.player.get_animation();
+    let animation_data = &mut *state.animation_data;
+    let render_settings = &mut *state.render_settings;
+    let player = run.player;
+    let filmstrip_thumbnails = run.filmstrip_thumbnails;
+    let camera_preset = &mut requests.camera_preset;
+    let seek = &mut requests.seek;
+    let playback_action = &mut requests.playback_action;
+    Flex::vertical().show(ui, |flex| {
+        flex.add(
+            item().align_self(egui_flex::FlexAlign::Start),
+            Checkbox::new(&mut animation_data.display_all_frames, "Display all frames"),
+        );
+        flex.add(
+            item().align_self(egui_flex::FlexAlign::Start),
+            Checkbox::new(&mut render_settings.display_path, "Display path"),
+        );
+        build_number_settings(
+            flex,
+            &mut animation_data.frames_count,
+            "Number of frames",
+            None::<f64>,
+            Some(2..=MAX_FRAMES_COUNT),
+        );
+        build_combo_box_settings(
+            flex,
+            &mut animation_data.frame_spacing,
+            "Frame spacing",
+            &[
+                (FrameSpacing::Uniform, "Uniform in time"),
+                (FrameSpacing::ArcLength, "Uniform in arc length"),
+            ],
+        );
+        build_number_settings(
+            flex,
+            &mut render_settings.first_frame_alpha,
+            "First frame alpha",
+            Some(0.01f32),
+            Some(0.0..=1.0),
+        );
+        build_number_settings(
+            flex,
+            &mut animation_data.animation_time,
+            "Animation time",
+            Some(0.1f32),
+            Some(0.1..=300.0),
+        );
+        let separate_rotation_easing = !animation_data
+            .get_rotation_interpolator()
+            .interpolates_position();
+        for (easing, name, enabled) in [
+            (
+                &mut animation_data.translation_easing,
+                "Translation easing",
+                true,
+            ),
+            (
+                &mut animation_data.rotation_easing,
+                "Rotation easing",
+                separate_rotation_easing,
+            ),
+        ] {
+            build_enabled_combo_box_settings(
+                flex,
+                easing,
+                name,
+                &[
+                    (Easing::Linear, "Linear"),
+                    (Easing::SmoothStep, "Smooth step"),
+                    (Easing::EaseInOutCubic, "Ease in-out cubic"),
+                    (Easing::EaseOutBounce, "Ease out bounce"),
+                ],
+                enabled,
+            );
+        }
+        let slow_motion = animation_data.auto_slow_motion || animation_data.midpoint_slow_motion;
+        build_enabled_combo_box_settings(
+            flex,
+            &mut animation_data.time_accumulation,
+            "Time accumulation",
+            &[
+                (TimeAccumulation::RunningSum, "Running sum of deltas"),
+                (TimeAccumulation::AbsoluteTimestamp, "Absolute timestamp"),
+            ],
+            !slow_motion,
+        );
+        flex.add(
+            item().align_self(egui_flex::FlexAlign::Start),
+            Checkbox::new(&mut animation_data.time_remap_enabled, "Bezier time remap"),
+        );
+        if animation_data.time_remap_enabled {
+            flex.add(item(), |ui: &mut egui::Ui| {
+                bezier_editor(ui, &mut animation_data.time_remap)
+            });
+        }
+        flex.add(
+            item().align_self(egui_flex::FlexAlign::Start),
+            Checkbox::new(&mut animation_data.auto_slow_motion, "Auto slow-mo"),
+        );
+        flex.add(
+            item().align_self(egui_flex::FlexAlign::Start),
+            Checkbox::new(
+                &mut animation_data.midpoint_slow_motion,
+                "Slow-mo around t = 0.5",
+            ),
+        );
+        flex.add(item(), |ui: &mut egui::Ui| {
+            ui.add(
+                Slider::new(&mut animation_data.playback_speed, 0.1f32..=10f32)
+                    .logarithmic(true)
+                    .suffix("×")
+                    .text("Playback speed"),
+            )
+        });
+    });
+    if animation.is_some() {
+        ui.horizontal(|ui| {
+            for (x, texture_id) in filmstrip_thumbnails {
+                let response = ui
+                    .add(ImageButton::new(SizedTexture::new(
+                        *texture_id,
+                        vec2(64f32, 64f32),
+                    )))
+                    .on_hover_text(format!("t = {:.2}", x));
+                response.widget_info(|| {
+                    WidgetInfo::labeled(
+                        WidgetType::ImageButton,
+                        true,
+                        format!("Seek to t = {:.2}", x),
+                    )
+                });
+                if response.clicked() {
+                    *seek = Some(*x);
+                }
+            }
+        });
+    }
+    if animation.is_some_and(|a| a.get_progress().is_some()) {
+        ui.horizontal(|ui| {
+            if ui.button("◀ step (,)").clicked() {
+                *playback_action = Some(ShortcutAction::StepBackward);
+            }
+            let text = if player.is_paused() { "play" } else { "pause" };
+            if ui.button(text).clicked() {
+                *playback_action = Some(ShortcutAction::PlayPause);
+            }
+            if ui.button("step (.) ▶").clicked() {
+                *playback_action = Some(ShortcutAction::StepForward);
+            }
+            ui.radio_value(
+                &mut animation_data.frame_step,
+                FrameStep::Timestep,
+                "1/240 s",
+            );
+            ui.radio_value(&mut animation_data.frame_step, FrameStep::Frame, "frame");
+        });
+    }
+    ui.horizontal(|ui| {
+        ui.label("Camera:");
+        for (preset, name) in [
+            (CameraPreset::Front, "front (1)"),
+            (CameraPreset::Side, "side (3)"),
+            (CameraPreset::Top, "top (7)"),
+            (CameraPreset::Isometric, "isometric (5)"),
+        ] {
+            if ui.button(name).clicked() {
+                *camera_preset = Some(preset);
+            }
+        }
+        ui.checkbox(&mut render_settings.animate_camera_presets, "animated");
+    });
+}
+
+fn build_analysis_tab(
+    ui: &mut egui::Ui,
+    state: &mut UiState,
+    run: &RunView,
+    requests: &mut UiRequests,
+) {
+    let animation = run.player.get_animation();
+    let animation_data = &mut *state.animation_data;
+    let render_settings = &mut *state.render_settings;
+    let status_message = &mut *state.status_message;
+    let reference_trajectory = &mut *state.reference_trajectory;
+    let displayed_models = run.displayed_models;
+    let run_stamp = run.run_stamp;
+    let bounding_box = run.bounding_box;
+    let max_divergence = run.max_divergence;
+    let last_angle_input = run.last_angle_input;
+    let fit_view = &mut requests.fit_view;
+    let teaching_set_requested = &mut requests.teaching_set_requested;
+    Flex::vertical().show(ui, |flex| {
+        build_comparison_settings(flex, animation_data, render_settings);
+        flex.add(
+            item().align_self(egui_flex::FlexAlign::Start),
+            Checkbox::new(
+                &mut render_settings.display_angular_velocity_plot,
+                "Display angular velocity plot",
+            ),
+        );
+        flex.add(
+            item().align_self(egui_flex::FlexAlign::Start),
+            Checkbox::new(
+                &mut render_settings.display_euler_angles_plot,
+                "Display Euler angles plot",
+            ),
+        );
+        flex.add(
+            item().align_self(egui_flex::FlexAlign::Start),
+            Checkbox::new(&mut render_settings.tint_gimbal_lock, "Tint gimbal lock"),
+        );
+        build_number_settings(
+            flex,
+            &mut animation_data.gimbal_lock_threshold,
+            "Gimbal lock threshold [°]",
+            Some(0.1f32),
+            Some(0.1f32..=45f32),
+        );
+        build_numerical_policy_settings(flex, &mut animation_data.numerical_policy);
+        build_obstacles_settings(flex, &mut animation_data.obstacles);
+        build_number_settings(
+            flex,
+            &mut animation_data.export_samples_count,
+            "Export samples",
+            None::<f64>,
+            Some(2..=10000),
+        );
+        flex.add_flex(item(), Flex::horizontal(), |flex| {
+            let label = flex.add(item(), Label::new("Reference")).inner;
+            flex.add(
+                item().grow(1.0),
+                TextEdit::singleline(&mut animation_data.reference_trajectory_path),
+            )
+            .inner
+            .labelled_by(label.id);
+            if flex
+                .add(item(), Button::new("Load reference"))
+                .inner
+                .clicked()
+            {
+                match ReferenceTrajectory::load(Path::new(
+                    &animation_data.reference_trajectory_path,
+                )) {
+                    Ok(mut trajectory) => {
+                        if let Some(animation) = animation {
+                            trajectory.update_errors(animation);
                         }
+                        *reference_trajectory = Some(trajectory);
+                        *status_message = Some("Reference trajectory loaded".to_string());
                     }
-                    WindowEvent::MouseWheel { delta, .. } => match delta {
-                        event::MouseScrollDelta::LineDelta(_x, y) => {
-                            camera_distant += -y * 0.1;
-                            view = Matrix4::look_at_rh(
-                                &Point3::from_slice(
-                                    (-camera_distant * camera_direction).as_slice(),
-                                ),
-                                &Point3::new(0.0, 0.0, 0.0),
-                                &camera_up,
-                            );
-                        }
-                        _ => {}
-                    },
-                    WindowEvent::PinchGesture { delta, .. } => {
-                        camera_distant -= *delta as f32 * 3.0;
-                        view = Matrix4::look_at_rh(
-                            &Point3::from_slice((-camera_distant * camera_direction).as_slice()),
-                            &Point3::new(0.0, 0.0, 0.0),
-                            &camera_up,
-                        );
+                    Err(e) => {
+                        *status_message = Some(format!("Reference load failed: {}", e));
                     }
-                    _ => {}
                 }
-
-                let event_response = egui_glium.on_event(&window, &event);
-
-                if event_response.repaint {
-                    window.request_redraw();
+            }
+            if flex.add(item(), Button::new("clear")).inner.clicked() {
+                *reference_trajectory = None;
+            }
+        });
+        if flex
+            .add(item(), |ui: &mut egui::Ui| {
+                ui.add_enabled(
+                    animation.is_some() && run_stamp.is_some(),
+                    Button::new("Export frames"),
+                )
+            })
+            .inner
+            .clicked()
+        {
+            let path = PathBuf::from(format!(
+                "frames_{}.csv",
+                Local::now().format("%Y%m%d_%H%M%S")
+            ));
+            *status_message = Some(
+                match export_frames_to_csv(
+                    animation.unwrap(),
+                    animation_data,
+                    run_stamp.as_ref().unwrap(),
+                    &render_settings.up_axis,
+                    &path,
+                ) {
+                    Ok(()) => format!("Frames exported to {}", path.display()),
+                    Err(e) => format!("Frames export failed: {}", e),
+                },
+            );
+        }
+        if flex
+            .add(item(), |ui: &mut egui::Ui| {
+                ui.add_enabled(
+                    animation.is_some() && run_stamp.is_some(),
+                    Button::new("Export quaternion path (PLY)"),
+                )
+            })
+            .inner
+            .clicked()
+        {
+            let path = PathBuf::from(format!(
+                "quaternion_path_{}.ply",
+                Local::now().format("%Y%m%d_%H%M%S")
+            ));
+            *status_message = Some(
+                match export_quaternion_path_to_ply(
+                    animation.unwrap(),
+                    animation_data.export_samples_count,
+                    run_stamp.as_ref().unwrap(),
+                    &path,
+                ) {
+                    Ok(()) => format!("Quaternion path exported to {}", path.display()),
+                    Err(e) => format!("Quaternion path export failed: {}", e),
+                },
+            );
+        }
+        if flex
+            .add(item(), |ui: &mut egui::Ui| {
+                ui.add_enabled(last_angle_input.is_some(), Button::new("Export as code"))
+            })
+            .inner
+            .clicked()
+        {
+            let path = PathBuf::from(format!(
+                "animation_{}.rs",
+                Local::now().format("%Y%m%d_%H%M%S")
+            ));
+            *status_message = Some(
+                match export_rust_snippet(animation_data, last_angle_input.as_ref().unwrap(), &path)
+                {
+                    Ok(()) => format!("Code exported to {}", path.display()),
+                    Err(e) => format!("Code export failed: {}", e),
+                },
+            );
+        }
+        if flex
+            .add(item(), |ui: &mut egui::Ui| {
+                ui.add_enabled(
+                    last_angle_input.is_some(),
+                    Button::new("Generate teaching set"),
+                )
+            })
+            .inner
+            .clicked()
+        {
+            *teaching_set_requested = true;
+        }
+    });
+    if let Some(bounding_box) = bounding_box {
+        let extents = bounding_box.get_extents();
+        ui.horizontal(|ui| {
+            ui.label(format!(
+                "Bounds: min ({:.2}, {:.2}, {:.2}), max ({:.2}, {:.2}, {:.2}), extents ({:.2}, {:.2}, {:.2})",
+                bounding_box.min().x,
+                bounding_box.min().y,
+                bounding_box.min().z,
+                bounding_box.max().x,
+                bounding_box.max().y,
+                bounding_box.max().z,
+                extents.x,
+                extents.y,
+                extents.z
+            ));
+            if ui.button("fit view").clicked() {
+                *fit_view = true;
+            }
+        });
+    }
+    if let Some(max_divergence) = max_divergence {
+        let divergence = MethodDivergence::from_frames(&displayed_models[0], &displayed_models[1]);
+        ui.label(format!(
+            "Divergence: position {:.4} (max {:.4}), rotation {:.3}° (max {:.3}°)",
+            divergence.position(),
+            max_divergence.position(),
+            divergence.rotation(),
+            max_divergence.rotation()
+        ));
+    }
+    if let Some(time_drift) = animation.and_then(|a| a.get_time_drift()) {
+        ui.label(format!(
+            "Time drift (sum - absolute): {:+.6} ms",
+            time_drift * 1000.0
+        ));
+    }
+    if let Some(errors) = reference_trajectory
+        .as_ref()
+        .and_then(|r| r.errors().as_ref())
+    {
+        ui.label(format!(
+            "Reference RMS: position {:.4}, quaternion {:.3}°, Euler {:.3}°",
+            errors.position(),
+            errors.quaternion_rotation(),
+            errors.euler_rotation()
+        ));
+    }
+    if let Some(animation) = animation {
+        Plot::new("quaternion_components")
+            .height(150f32)
+            .legend(Legend::default())
+            .include_y(-1f32)
+            .include_y(1f32)
+            .x_axis_label("normalized time")
+            .y_axis_label("quaternion component")
+            .show(ui, |plot_ui| {
+                for (name, points) in ["w", "x", "y", "z"]
+                    .iter()
+                    .zip(get_quaternion_components(animation, PATH_SAMPLES_COUNT))
+                {
+                    plot_ui.line(Line::new(points).name(name));
                 }
+            });
+    }
+}
+
+fn build_render_settings_tab(
+    ui: &mut egui::Ui,
+    state: &mut UiState,
+    run: &RunView,
+    requests: &mut UiRequests,
+) {
+    let egui_ctx = ui.ctx().clone();
+    let animation_data = &mut *state.animation_data;
+    let render_settings = &mut *state.render_settings;
+    let appearance = &mut *state.appearance;
+    let frame_pacing = &mut *state.frame_pacing;
+    let screenshot_request = &mut *state.screenshot_request;
+    let last_angle_input = run.last_angle_input;
+    let video_export_requested = &mut requests.video_export_requested;
+    let mesh_load_requested = &mut requests.mesh_load_requested;
+    Flex::vertical().show(ui, |flex| {
+        build_combo_box_settings(
+            flex,
+            &mut render_settings.shading_mode,
+            "Shading",
+            &[
+                (ShadingMode::Material, "Material"),
+                (ShadingMode::Discrepancy, "Pipeline discrepancy"),
+            ],
+        );
+        build_combo_box_settings(
+            flex,
+            &mut render_settings.render_mode,
+            "Render mode",
+            &[
+                (RenderMode::Shaded, "Shaded"),
+                (RenderMode::Wireframe, "Wireframe"),
+                (RenderMode::Normals, "Normals"),
+            ],
+        );
+        build_combo_box_settings(
+            flex,
+            &mut render_settings.scene_object_type,
+            "Object",
+            &[
+                (SceneObjectType::Block, "Block"),
+                (SceneObjectType::Arrow, "Arrow"),
+                (SceneObjectType::Tripod, "Tripod"),
+                (SceneObjectType::Airplane, "Airplane"),
+                (SceneObjectType::Cube, "Cube"),
+                (SceneObjectType::Cone, "Cone"),
+                (SceneObjectType::Torus, "Torus"),
+                (SceneObjectType::Teapot, "Teapot"),
+                (SceneObjectType::Custom, "Custom mesh"),
+            ],
+        );
+        flex.add_flex(item(), Flex::horizontal(), |flex| {
+            let label = flex.add(item(), Label::new("Mesh")).inner;
+            flex.add(
+                item().grow(1.0),
+                TextEdit::singleline(&mut render_settings.custom_mesh_path),
+            )
+            .inner
+            .labelled_by(label.id);
+            if flex.add(item(), Button::new("Load mesh…")).inner.clicked() {
+                *mesh_load_requested = true;
             }
-            event::Event::NewEvents(event::StartCause::ResumeTimeReached { .. }) => {
-                window.request_redraw();
+        });
+        if render_settings.scene_object_type == SceneObjectType::Block {
+            build_block_geometry_settings(flex, &mut render_settings.block_geometry);
+        }
+        flex.add(
+            item().align_self(egui_flex::FlexAlign::Start),
+            Checkbox::new(
+                &mut render_settings.display_attitude_hud,
+                "Display attitude HUD",
+            ),
+        );
+        flex.add(
+            item().align_self(egui_flex::FlexAlign::Start),
+            Checkbox::new(
+                &mut render_settings.display_pose_readout,
+                "Display pose readout",
+            ),
+        );
+        flex.add(
+            item().align_self(egui_flex::FlexAlign::Start),
+            Checkbox::new(
+                &mut render_settings.display_rotation_axis,
+                "Display rotation axis",
+            ),
+        );
+        flex.add(
+            item().align_self(egui_flex::FlexAlign::Start),
+            Checkbox::new(
+                &mut render_settings.display_rotation_sphere,
+                "Display rotation sphere",
+            ),
+        );
+        flex.add(
+            item().align_self(egui_flex::FlexAlign::Start),
+            Checkbox::new(
+                &mut render_settings.display_pose_ghosts,
+                "Display begin/end ghosts",
+            ),
+        );
+        flex.add(
+            item().align_self(egui_flex::FlexAlign::Start),
+            Checkbox::new(
+                &mut render_settings.display_matrix_lerp_ghost,
+                "Display matrix lerp ghost",
+            ),
+        );
+        flex.add_flex(item(), Flex::horizontal(), |flex| {
+            build_lighting_settings(
+                flex,
+                &mut render_settings.quaternion_lighting,
+                "Quaternion lighting",
+            );
+            build_lighting_settings(flex, &mut render_settings.euler_lighting, "Euler lighting");
+        });
+        build_projection_settings(flex, &mut render_settings.projection);
+        build_grid_settings(flex, &mut render_settings.grid);
+        build_appearance_settings(flex, appearance);
+        build_frame_pacing_settings(flex, frame_pacing);
+        flex.add(
+            item().align_self(egui_flex::FlexAlign::Start),
+            Checkbox::new(&mut render_settings.display_axes, "Display axes"),
+        );
+        flex.add(
+            item().align_self(egui_flex::FlexAlign::Start),
+            |ui: &mut egui::Ui| {
+                ui.add_enabled(
+                    render_settings.display_axes,
+                    Checkbox::new(
+                        &mut render_settings.display_axis_labels,
+                        "Display axis labels",
+                    ),
+                )
+            },
+        );
+        flex.add(item(), Label::new("Up axis"));
+        for (up_axis, name) in [(UpAxis::Y, "Y up"), (UpAxis::Z, "Z up")] {
+            if flex
+                .add(
+                    item().align_self(egui_flex::FlexAlign::Start),
+                    RadioButton::new(render_settings.up_axis == up_axis, name),
+                )
+                .inner
+                .clicked()
+            {
+                render_settings.set_up_axis(up_axis, animation_data);
             }
-            _ => (),
         }
+        build_video_settings(flex, &mut render_settings.video);
+        if flex
+            .add(item(), |ui: &mut egui::Ui| {
+                ui.add_enabled(last_angle_input.is_some(), Button::new("Export video"))
+            })
+            .inner
+            .clicked()
+        {
+            *video_export_requested = true;
+        }
+        let mut screenshot_region = egui_ctx.data(|d| {
+            d.get_temp(Id::new(SCREENSHOT_REGION_ID))
+                .unwrap_or(ScreenshotRegion::Window)
+        });
+        let panes = get_panes(
+            run.player.get_animation(),
+            animation_data,
+            render_settings,
+            *state.viewport_split,
+        );
+        if let ScreenshotRegion::Pane(index) = screenshot_region {
+            if index >= panes.len() {
+                screenshot_region = ScreenshotRegion::Window;
+            }
+        }
+        let mut screenshot_regions = vec![(ScreenshotRegion::Window, "Window")];
+        screenshot_regions.extend(
+            panes
+                .iter()
+                .enumerate()
+                .map(|(i, pane)| (ScreenshotRegion::Pane(i), pane.title.as_str())),
+        );
+        build_combo_box_settings(
+            flex,
+            &mut screenshot_region,
+            "Screenshot region",
+            &screenshot_regions,
+        );
+        if flex
+            .add(item(), Button::new("Screenshot (F12)"))
+            .inner
+            .clicked()
+        {
+            *screenshot_request = Some(screenshot_region.clone());
+        }
+        egui_ctx.data_mut(|d| d.insert_temp(Id::new(SCREENSHOT_REGION_ID), screenshot_region));
     });
 }
 
-fn build_ui(
-    egui_glium: &mut egui_glium::EguiGlium,
-    window: &winit::window::Window,
-    animation_data: &mut AnimationData,
-    animation: &mut Option<Box<dyn Animation>>,
-    fps: f64,
+fn build_log_tab(ui: &mut egui::Ui, state: &mut UiState, run: &RunView) {
+    let status_message = &mut *state.status_message;
+    let path_warnings = run.path_warnings;
+    let run_stamp = run.run_stamp;
+    let numerical_warning = run.numerical_warning;
+    let session_log = run.session_log;
+    let fps = run.fps;
+    ui.label(RichText::new(format!("FPS: {:.1}", fps)).size(15f32));
+    if let Some(run_stamp) = run_stamp {
+        ui.label(format!("Run: {}", run_stamp));
+    }
+    if let Some(status_message) = status_message {
+        ui.label(status_message.as_str());
+    }
+    if let Some(numerical_warning) = numerical_warning {
+        ui.label(RichText::new(numerical_warning).color(Color32::RED));
+    }
+    if let Some(path_warnings) = path_warnings {
+        for message in path_warnings.get_messages() {
+            ui.label(RichText::new(message).color(Color32::RED));
+        }
+    }
+    Flex::vertical().show(ui, |flex| {
+        if flex
+            .add(item(), Button::new("Export session"))
+            .inner
+            .clicked()
+        {
+            let path = PathBuf::from(format!(
+                "session_{}.json",
+                Local::now().format("%Y%m%d_%H%M%S")
+            ));
+            *status_message = Some(match session_log.export(&path) {
+                Ok(()) => format!("Session exported to {}", path.display()),
+                Err(e) => format!("Session export failed: {}", e),
+            });
+        }
+    });
+}
+
+fn build_plot_windows(egui_ctx: &egui::Context, state: &UiState, run: &RunView) {
+    let animation = run.player.get_animation();
+    let animation_data = &*state.animation_data;
+    let render_settings = &*state.render_settings;
+    if let Some(animation) = animation.filter(|_| render_settings.display_angular_velocity_plot) {
+        let (quaternion_speeds, euler_speeds) = get_angular_speeds(animation, PATH_SAMPLES_COUNT);
+        egui::Window::new("Angular velocity").show(egui_ctx, |ui| {
+            Plot::new("angular_velocity")
+                .height(200f32)
+                .legend(Legend::default())
+                .include_y(0f32)
+                .x_axis_label("normalized time")
+                .y_axis_label("angular speed [rad / unit time]")
+                .show(ui, |plot_ui| {
+                    plot_ui.line(Line::new(quaternion_speeds).name("Quaternion"));
+                    plot_ui.line(Line::new(euler_speeds).name("Euler"));
+                });
+        });
+    }
+
+    if let Some(animation) = animation.filter(|_| render_settings.display_euler_angles_plot) {
+        let [quaternion_angles, euler_angles] =
+            get_recovered_euler_angles(animation, &animation_data.euler_order, PATH_SAMPLES_COUNT);
+        egui::Window::new("Euler angles").show(egui_ctx, |ui| {
+            for (name, angles) in [("Quaternion", quaternion_angles), ("Euler", euler_angles)] {
+                ui.label(name);
+                Plot::new(format!("euler_angles_{}", name))
+                    .height(150f32)
+                    .legend(Legend::default())
+                    .include_y(-180f32)
+                    .include_y(180f32)
+                    .x_axis_label("normalized time")
+                    .y_axis_label("angle [°]")
+                    .show(ui, |plot_ui| {
+                        for (component, points) in ["roll", "pitch", "yaw"].iter().zip(angles) {
+                            plot_ui.line(Line::new(points).name(component));
+                        }
+                    });
+            }
+        });
+    }
+}
+
+fn build_viewport_overlays(
+    egui_ctx: &egui::Context,
+    state: &mut UiState,
+    run: &RunView,
+    requests: &mut UiRequests,
 ) {
-    egui_glium.run(window, |egui_ctx| {
-        egui::Window::new("panel")
-            .auto_sized()
+    let animation = run.player.get_animation();
+    let animation_data = &mut *state.animation_data;
+    let render_settings = &mut *state.render_settings;
+    let viewport_split = &mut *state.viewport_split;
+    let displayed_models = run.displayed_models;
+    let gimbal_lock_intervals = run.gimbal_lock_intervals;
+    let path_handle = run.path_handle;
+    let seek = &mut requests.seek;
+    let panes = get_panes(animation, animation_data, render_settings, *viewport_split);
+    let screen_rect = egui_ctx.screen_rect();
+    let get_screen_pos = |x: f32, y: f32| {
+        pos2(
+            screen_rect.min.x + screen_rect.width() * x,
+            screen_rect.min.y + screen_rect.height() * y,
+        )
+    };
+    let get_model = |method: usize| {
+        run.player
+            .frames_for(method)
+            .last()
+            .copied()
+            .unwrap_or_else(Matrix4::identity)
+    };
+
+    if render_settings.display_attitude_hud
+        && render_settings.scene_object_type == SceneObjectType::Airplane
+    {
+        let painter = egui_ctx.layer_painter(LayerId::new(Order::Foreground, Id::new("hud")));
+        let radius = 60f32;
+        for pane in &panes {
+            draw_attitude_hud(
+                &painter,
+                get_screen_pos(pane.cell.max.x, pane.cell.max.y)
+                    - vec2(radius + 20f32, radius + 40f32),
+                radius,
+                &Attitude::from_model(&get_model(pane.method), &render_settings.up_axis),
+            );
+        }
+    }
+
+    if let Some((animation, pane)) = animation
+        .filter(|_| render_settings.display_rotation_sphere)
+        .zip(panes.first())
+    {
+        let painter =
+            egui_ctx.layer_painter(LayerId::new(Order::Foreground, Id::new("rotation_sphere")));
+        let radius = 70f32;
+        draw_rotation_sphere(
+            &painter,
+            get_screen_pos(pane.cell.max.x, pane.cell.max.y) - vec2(0f32, radius + 40f32),
+            radius,
+            &get_rotation_traces(animation, &render_settings.up_axis),
+            &displayed_models.map(|m| get_body_direction(&m, &render_settings.up_axis)),
+        );
+    }
+
+    if let Some(pane) = panes
+        .iter()
+        .find(|p| p.method == EULER_METHOD_INDEX)
+        .or(panes.first())
+        .filter(|_| !gimbal_lock_intervals.is_empty())
+    {
+        let active = is_near_gimbal_lock(
+            &animation_data
+                .euler_order
+                .get_euler(&get_frame_quaternion(&displayed_models[1])),
+            &animation_data.euler_order,
+            animation_data.gimbal_lock_threshold.to_radians(),
+        );
+        let intervals = gimbal_lock_intervals
+            .iter()
+            .map(|(begin, end)| format!("{:.2}-{:.2}", begin, end))
+            .collect::<Vec<_>>()
+            .join(", ");
+        egui::Area::new(Id::new("gimbal_lock_badge"))
+            .fixed_pos(get_screen_pos(pane.cell.min.x, pane.cell.min.y) + vec2(20f32, 50f32))
             .show(egui_ctx, |ui| {
-                Flex::horizontal()
-                    .grow_items(1.0)
-                    .align_items(egui_flex::FlexAlign::Stretch)
-                    .show(ui, |flex| {
-                        flex.add_flex(item(), Flex::vertical(), |flex| {
-                            flex.add_flex(item(), Flex::horizontal(), |flex| {
-                                build_xyz_settings(
-                                    flex,
-                                    &mut animation_data.begin_position,
-                                    RichText::new("Begin Position").size(15f32),
-                                );
-                                build_xyz_settings(
-                                    flex,
-                                    &mut animation_data.end_position,
-                                    RichText::new("End Position").size(15f32),
-                                );
-                            });
-
-                            flex.add(
-                                item().align_self(egui_flex::FlexAlign::Start),
-                                Checkbox::new(
-                                    &mut animation_data.display_all_frames,
-                                    "Display all frames",
-                                ),
-                            );
-                            build_number_settings(
-                                flex,
-                                &mut animation_data.frames_count,
-                                "Number of frames",
-                                None::<f64>,
-                                Some(2..=255),
-                            );
-                            build_number_settings(
-                                flex,
-                                &mut animation_data.animation_time,
-                                "Animation time",
-                                Some(0.1f32),
-                                Some(0.1..=300.0),
-                            );
-                        });
-
-                        flex.add_flex(item(), Flex::vertical(), |flex| {
-                            flex.add_flex(item(), Flex::horizontal(), |flex| {
-                                build_wxyz_settings(
-                                    flex,
-                                    &mut animation_data.begin_rotation_quaternion,
-                                    RichText::new("Begin Quternion").size(15f32),
-                                );
-                                build_wxyz_settings(
-                                    flex,
-                                    &mut animation_data.end_rotation_quaternion,
-                                    RichText::new("End Quternion").size(15f32),
-                                );
-                            });
-
-                            if flex
-                                .add(
-                                    item().align_self(egui_flex::FlexAlign::Start),
-                                    RadioButton::new(
-                                        animation_data.quaternion_interpolation_type
-                                            == QuaternionInterpolationType::Linear,
-                                        "Linear",
-                                    ),
-                                )
-                                .inner
-                                .clicked()
-                            {
-                                animation_data.quaternion_interpolation_type =
-                                    QuaternionInterpolationType::Linear;
-                            }
-
-                            if flex
-                                .add(
-                                    item().align_self(egui_flex::FlexAlign::Start),
-                                    RadioButton::new(
-                                        animation_data.quaternion_interpolation_type
-                                            == QuaternionInterpolationType::Spherical,
-                                        "Spherical",
-                                    ),
-                                )
-                                .inner
-                                .clicked()
-                            {
-                                animation_data.quaternion_interpolation_type =
-                                    QuaternionInterpolationType::Spherical;
-                            }
-
-                            if flex.add(item(), Button::new("run")).inner.clicked() {
-                                if animation_data.display_all_frames {
-                                    let a = DiscreteFrameAnimationBuilder::default()
-                                        .frames_count(animation_data.frames_count)
-                                        .begin_position(Vector3::new(
-                                            animation_data.begin_position.0,
-                                            animation_data.begin_position.1,
-                                            animation_data.begin_position.2,
-                                        ))
-                                        .end_position(Vector3::new(
-                                            animation_data.end_position.0,
-                                            animation_data.end_position.1,
-                                            animation_data.end_position.2,
-                                        ))
-                                        .begin_angle(AnimationAngle::new_quternion(
-                                            Quaternion::new(
-                                                animation_data.begin_rotation_quaternion.0,
-                                                animation_data.begin_rotation_quaternion.1,
-                                                animation_data.begin_rotation_quaternion.2,
-                                                animation_data.begin_rotation_quaternion.3,
-                                            ),
-                                        ))
-                                        .end_angle(AnimationAngle::new_quternion(Quaternion::new(
-                                            animation_data.end_rotation_quaternion.0,
-                                            animation_data.end_rotation_quaternion.1,
-                                            animation_data.end_rotation_quaternion.2,
-                                            animation_data.begin_rotation_quaternion.3,
-                                        )))
-                                        .quaternion_interpolation_type(
-                                            animation_data.quaternion_interpolation_type.clone(),
-                                        )
-                                        .build()
-                                        .unwrap();
-                                    *animation = Some(Box::new(a));
-                                } else {
-                                    let a = ContinuousAnimationBuilder::default()
-                                        .animation_time(animation_data.animation_time)
-                                        .begin_position(Vector3::new(
-                                            animation_data.begin_position.0,
-                                            animation_data.begin_position.1,
-                                            animation_data.begin_position.2,
-                                        ))
-                                        .end_position(Vector3::new(
-                                            animation_data.end_position.0,
-                                            animation_data.end_position.1,
-                                            animation_data.end_position.2,
-                                        ))
-                                        .begin_angle(AnimationAngle::new_quternion(
-                                            Quaternion::new(
-                                                animation_data.begin_rotation_quaternion.0,
-                                                animation_data.begin_rotation_quaternion.1,
-                                                animation_data.begin_rotation_quaternion.2,
-                                                animation_data.begin_rotation_quaternion.3,
-                                            ),
-                                        ))
-                                        .end_angle(AnimationAngle::new_quternion(Quaternion::new(
-                                            animation_data.end_rotation_quaternion.0,
-                                            animation_data.end_rotation_quaternion.1,
-                                            animation_data.end_rotation_quaternion.2,
-                                            animation_data.end_rotation_quaternion.3,
-                                        )))
-                                        .quaternion_interpolation_type(
-                                            animation_data.quaternion_interpolation_type.clone(),
-                                        )
-                                        .build()
-                                        .unwrap();
-                                    *animation = Some(Box::new(a));
-                                }
-                            }
-                        });
-
-                        flex.add_flex(item(), Flex::vertical(), |flex| {
-                            flex.add_flex(item(), Flex::horizontal(), |flex| {
-                                build_xyz_settings(
-                                    flex,
-                                    &mut animation_data.begin_rotation_xyz,
-                                    RichText::new("Begin Euler Angle").size(15f32),
-                                );
-                                build_xyz_settings(
-                                    flex,
-                                    &mut animation_data.end_rotation_xyz,
-                                    RichText::new("End Euler Angle").size(15f32),
-                                );
-                            });
-
-                            if flex.add(item(), Button::new("run")).inner.clicked() {
-                                if animation_data.display_all_frames {
-                                    let a = DiscreteFrameAnimationBuilder::default()
-                                        .frames_count(animation_data.frames_count)
-                                        .begin_position(Vector3::new(
-                                            animation_data.begin_position.0,
-                                            animation_data.begin_position.1,
-                                            animation_data.begin_position.2,
-                                        ))
-                                        .end_position(Vector3::new(
-                                            animation_data.end_position.0,
-                                            animation_data.end_position.1,
-                                            animation_data.end_position.2,
-                                        ))
-                                        .begin_angle(AnimationAngle::new_euler(Vector3::new(
-                                            animation_data.begin_rotation_xyz.0 / 180f32 * PI,
-                                            animation_data.begin_rotation_xyz.1 / 180f32 * PI,
-                                            animation_data.begin_rotation_xyz.2 / 180f32 * PI,
-                                        )))
-                                        .end_angle(AnimationAngle::new_euler(Vector3::new(
-                                            animation_data.end_rotation_xyz.0 / 180f32 * PI,
-                                            animation_data.end_rotation_xyz.1 / 180f32 * PI,
-                                            animation_data.end_rotation_xyz.2 / 180f32 * PI,
-                                        )))
-                                        .quaternion_interpolation_type(
-                                            animation_data.quaternion_interpolation_type.clone(),
-                                        )
-                                        .build()
-                                        .unwrap();
-                                    *animation = Some(Box::new(a));
-                                } else {
-                                    let a = ContinuousAnimationBuilder::default()
-                                        .animation_time(animation_data.animation_time)
-                                        .begin_position(Vector3::new(
-                                            animation_data.begin_position.0,
-                                            animation_data.begin_position.1,
-                                            animation_data.begin_position.2,
-                                        ))
-                                        .end_position(Vector3::new(
-                                            animation_data.end_position.0,
-                                            animation_data.end_position.1,
-                                            animation_data.end_position.2,
-                                        ))
-                                        .begin_angle(AnimationAngle::new_euler(Vector3::new(
-                                            animation_data.begin_rotation_xyz.0 / 180f32 * PI,
-                                            animation_data.begin_rotation_xyz.1 / 180f32 * PI,
-                                            animation_data.begin_rotation_xyz.2 / 180f32 * PI,
-                                        )))
-                                        .end_angle(AnimationAngle::new_euler(Vector3::new(
-                                            animation_data.end_rotation_xyz.0 / 180f32 * PI,
-                                            animation_data.end_rotation_xyz.1 / 180f32 * PI,
-                                            animation_data.end_rotation_xyz.2 / 180f32 * PI,
-                                        )))
-                                        .quaternion_interpolation_type(
-                                            animation_data.quaternion_interpolation_type.clone(),
-                                        )
-                                        .build()
-                                        .unwrap();
-                                    *animation = Some(Box::new(a));
-                                }
-                            }
-                        });
+                egui::Frame::none()
+                    .fill(if active {
+                        Color32::from_rgb(200, 0, 0)
+                    } else {
+                        Color32::from_rgb(120, 0, 0)
+                    })
+                    .rounding(4f32)
+                    .inner_margin(6f32)
+                    .show(ui, |ui| {
+                        ui.label(
+                            RichText::new(format!("Gimbal lock at t = {}", intervals))
+                                .color(Color32::WHITE)
+                                .size(15f32),
+                        );
                     });
-                ui.label(RichText::new(format!("FPS: {:.1}", fps)).size(15f32));
             });
+    }
+
+    if animation.is_some() && render_settings.display_pose_readout {
+        for (i, pane) in panes.iter().enumerate() {
+            build_pose_readout(
+                egui_ctx,
+                i,
+                get_screen_pos(pane.cell.max.x, pane.cell.min.y) + vec2(-10f32, 50f32),
+                &get_pose_readout(&get_model(pane.method), animation_data),
+            );
+        }
+    }
+
+    if !is_comparison_grid(render_settings) {
+        egui::Area::new(Id::new("viewport_splitter"))
+            .order(Order::Background)
+            .fixed_pos(get_screen_pos(*viewport_split, 0f32) - vec2(SPLITTER_WIDTH / 2f32, 0f32))
+            .show(egui_ctx, |ui| {
+                let (rect, response) = ui
+                    .allocate_exact_size(vec2(SPLITTER_WIDTH, screen_rect.height()), Sense::drag());
+                if response.dragged() {
+                    *viewport_split = (*viewport_split
+                        + response.drag_delta().x / screen_rect.width())
+                    .clamp(*VIEWPORT_SPLIT_RANGE.start(), *VIEWPORT_SPLIT_RANGE.end());
+                }
+                if response.double_clicked() {
+                    *viewport_split = 0.5f32;
+                }
+                let response = response.on_hover_cursor(CursorIcon::ResizeHorizontal);
+                ui.painter().vline(
+                    rect.center().x,
+                    rect.y_range(),
+                    Stroke::new(
+                        if response.hovered() || response.dragged() {
+                            2f32
+                        } else {
+                            1f32
+                        },
+                        Color32::GRAY,
+                    ),
+                );
+            });
+    }
+
+    for (i, pane) in panes.iter().enumerate() {
+        egui::Area::new(Id::new(("viewport_title", i)))
+            .order(Order::Background)
+            .interactable(false)
+            .pivot(Align2::CENTER_TOP)
+            .fixed_pos(get_screen_pos(pane.cell.center().x, pane.cell.min.y) + vec2(0f32, 10f32))
+            .show(egui_ctx, |ui| {
+                ui.label(RichText::new(&pane.title).color(Color32::WHITE).size(18f32));
+            });
+    }
+
+    if render_settings.display_axes && render_settings.display_axis_labels {
+        let painter =
+            egui_ctx.layer_painter(LayerId::new(Order::Background, Id::new("axis_labels")));
+        for i in 0..path_handle.get_viewports_count() {
+            for (axis, (name, color)) in ["X", "Y", "Z"].iter().zip(AXIS_COLORS).enumerate() {
+                if let Some(position) =
+                    path_handle.project(i, &Vector3::ith(axis, AXIS_LABEL_DISTANCE))
+                {
+                    painter.text(
+                        position,
+                        Align2::CENTER_CENTER,
+                        name,
+                        FontId::proportional(16f32),
+                        Color32::from_rgb(
+                            (color[0] * 255f32) as u8,
+                            (color[1] * 255f32) as u8,
+                            (color[2] * 255f32) as u8,
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    build_rotation_gizmo(
+        egui_ctx,
+        path_handle,
+        animation_data,
+        &render_settings.pose_gizmo,
+        run.ghost_angle_input,
+    );
+
+    if let Some((animation, progress)) = animation
+        .filter(|_| render_settings.display_path)
+        .and_then(|a| Some((a, a.get_progress()?)))
+    {
+        let position = *animation.get_sample(progress).position();
+        for i in 0..path_handle.get_viewports_count() {
+            let Some(center) = path_handle.project(i, &position) else {
+                continue;
+            };
+            egui::Area::new(Id::new(("path_handle", i)))
+                .order(Order::Background)
+                .fixed_pos(center - vec2(PATH_HANDLE_RADIUS, PATH_HANDLE_RADIUS))
+                .show(egui_ctx, |ui| {
+                    let (rect, response) = ui.allocate_exact_size(
+                        vec2(2f32 * PATH_HANDLE_RADIUS, 2f32 * PATH_HANDLE_RADIUS),
+                        Sense::drag(),
+                    );
+                    if response.dragged() {
+                        *seek = response
+                            .interact_pointer_pos()
+                            .and_then(|p| path_handle.get_nearest_parameter(i, p));
+                    }
+                    let response = response
+                        .on_hover_cursor(CursorIcon::Grab)
+                        .on_hover_text(format!("t = {:.3}", progress));
+                    ui.painter().circle(
+                        rect.center(),
+                        PATH_HANDLE_RADIUS,
+                        if response.dragged() {
+                            Color32::WHITE
+                        } else {
+                            Color32::from_rgb(255, 200, 0)
+                        },
+                        Stroke::new(1f32, Color32::BLACK),
+                    );
+                });
+        }
+    }
+}
+
+struct Pane {
+    method: usize,
+    cell: egui::Rect,
+    title: String,
+}
+
+fn is_comparison_grid(render_settings: &RenderSettings) -> bool {
+    render_settings.comparison_grid && !render_settings.comparison_methods.is_empty()
+}
+
+fn get_panes(
+    animation: Option<&dyn Animation>,
+    animation_data: &AnimationData,
+    render_settings: &RenderSettings,
+    viewport_split: f32,
+) -> Vec<Pane> {
+    if !is_comparison_grid(render_settings) {
+        return vec![
+            Pane {
+                method: QUATERNION_METHOD_INDEX,
+                cell: egui::Rect::from_min_max(pos2(0f32, 0f32), pos2(viewport_split, 1f32)),
+                title: format!(
+                    "Quaternion: {}",
+                    animation_data.get_rotation_interpolator().get_name()
+                ),
+            },
+            Pane {
+                method: EULER_METHOD_INDEX,
+                cell: egui::Rect::from_min_max(pos2(viewport_split, 0f32), pos2(1f32, 1f32)),
+                title: format!("Euler: {:?}", animation_data.euler_order),
+            },
+        ];
+    }
+
+    let methods = match animation {
+        Some(animation) => animation.get_methods().to_vec(),
+        None => get_animation_methods(animation_data, &render_settings.comparison_methods),
+    };
+    let cells = get_grid_cells(render_settings.comparison_methods.len());
+    render_settings
+        .comparison_methods
+        .iter()
+        .zip(cells)
+        .filter_map(|(method, cell)| {
+            Some(Pane {
+                method: methods.iter().position(|m| m == method)?,
+                cell,
+                title: animation_data.get_method_name(method),
+            })
+        })
+        .collect()
+}
+
+fn get_grid_cells(count: usize) -> Vec<egui::Rect> {
+    let columns = (count as f32).sqrt().ceil().max(1f32) as usize;
+    let rows = count.div_ceil(columns).max(1);
+    let size = vec2(1f32 / columns as f32, 1f32 / rows as f32);
+    (0..count)
+        .map(|i| {
+            egui::Rect::from_min_size(
+                pos2((i % columns) as f32 * size.x, (i / columns) as f32 * size.y),
+                size,
+            )
+        })
+        .collect()
+}
+
+fn get_grid_viewport(cell: &egui::Rect, width: u32, height: u32) -> glium::Rect {
+    let left = (cell.min.x * width as f32) as u32;
+    let right = (cell.max.x * width as f32) as u32;
+    let top = (cell.min.y * height as f32) as u32;
+    let bottom = (cell.max.y * height as f32) as u32;
+    glium::Rect {
+        left,
+        bottom: height - bottom,
+        width: (right - left).max(1),
+        height: (bottom - top).max(1),
+    }
+}
+
+fn get_frame_alpha(index: usize, frames_count: usize, first_frame_alpha: f32) -> f32 {
+    if frames_count < 2 {
+        return 1f32;
+    }
+
+    let x = index as f32 / (frames_count - 1) as f32;
+    (1f32 - x) * first_frame_alpha + x
+}
+
+fn get_frame_instances(
+    frames: &[Matrix4<f32>],
+    discrepancies: &[f32],
+    tints: &[f32],
+    first_frame_alpha: f32,
+) -> Vec<InstanceData> {
+    frames
+        .iter()
+        .zip(discrepancies)
+        .zip(tints)
+        .enumerate()
+        .filter(|(_, ((model, _), _))| model.iter().all(|v| v.is_finite()))
+        .map(|(i, ((model, discrepancy), tint))| {
+            InstanceData::new(
+                model.data.0,
+                get_frame_alpha(i, frames.len(), first_frame_alpha),
+                *discrepancy,
+                *tint,
+            )
+        })
+        .collect()
+}
+
+fn get_rotation_axis_model(animation: &dyn Animation, mesh_radius: f32) -> Option<Matrix4<f32>> {
+    let x = animation
+        .get_progress()
+        .unwrap_or(0f32)
+        .clamp(0f32, 1f32 - ROTATION_AXIS_STEP);
+    let sample = animation.get_sample(x);
+    let next_sample = animation.get_sample(x + ROTATION_AXIS_STEP);
+    let (axis, _) = (next_sample.quaternion() * sample.quaternion().inverse())
+        .axis_angle()
+        .filter(|(_, angle)| *angle > ROTATION_AXIS_MIN_ANGLE)?;
+    let rotation = UnitQuaternion::rotation_between(&-Vector3::z(), &axis).unwrap_or_else(|| {
+        UnitQuaternion::from_axis_angle(&Vector3::x_axis(), std::f32::consts::PI)
     });
+
+    Some(
+        Matrix4::new_translation(sample.position())
+            * rotation.to_homogeneous()
+            * Matrix4::new_scaling(2.5 * mesh_radius / ROTATION_AXIS_ARROW_LENGTH)
+            * Matrix4::new_translation(&Vector3::new(
+                0f32,
+                0f32,
+                ROTATION_AXIS_ARROW_LENGTH / 2f32,
+            )),
+    )
+}
+
+fn get_gimbal_lock_tints(
+    frames: &[Matrix4<f32>],
+    animation_data: &AnimationData,
+    render_settings: &RenderSettings,
+) -> Vec<f32> {
+    frames
+        .iter()
+        .map(|frame| {
+            let euler = animation_data
+                .euler_order
+                .get_euler(&get_frame_quaternion(frame));
+            let locked = is_near_gimbal_lock(
+                &euler,
+                &animation_data.euler_order,
+                animation_data.gimbal_lock_threshold.to_radians(),
+            );
+            if render_settings.tint_gimbal_lock && locked {
+                1f32
+            } else {
+                0f32
+            }
+        })
+        .collect()
+}
+
+fn get_numerical_warning(animation: &dyn Animation) -> Option<String> {
+    (0..PATH_SAMPLES_COUNT)
+        .map(|s| s as f32 / (PATH_SAMPLES_COUNT - 1) as f32)
+        .find(|x| !animation.get_sample(*x).is_finite())
+        .map(|x| {
+            format!(
+                "Non-finite values in the interpolation at t = {:.3}, affected frames are skipped",
+                x
+            )
+        })
+}
+
+fn get_frame_discrepancies(
+    quaternion_frames: &[Matrix4<f32>],
+    euler_frames: &[Matrix4<f32>],
+) -> Vec<f32> {
+    quaternion_frames
+        .iter()
+        .zip(euler_frames)
+        .map(|(q, e)| {
+            let q = get_frame_quaternion(q);
+            let e = get_frame_quaternion(e);
+            q.angle_to(&e) / std::f32::consts::PI
+        })
+        .collect()
+}
+
+fn get_angular_speeds(
+    animation: &dyn Animation,
+    samples_count: u16,
+) -> (Vec<[f64; 2]>, Vec<[f64; 2]>) {
+    let dx = 1f32 / (samples_count - 1) as f32;
+    let rotations = (0..samples_count)
+        .map(|s| {
+            let sample = animation.get_sample(s as f32 * dx);
+            (
+                get_frame_quaternion(sample.quaternion_frame()),
+                get_frame_quaternion(sample.euler_frame()),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    rotations
+        .windows(2)
+        .enumerate()
+        .map(|(i, w)| {
+            let x = ((i as f32 + 0.5) * dx) as f64;
+            (
+                [x, (w[0].0.angle_to(&w[1].0) / dx) as f64],
+                [x, (w[0].1.angle_to(&w[1].1) / dx) as f64],
+            )
+        })
+        .unzip()
+}
+
+fn get_quaternion_components(animation: &dyn Animation, samples_count: u16) -> [Vec<[f64; 2]>; 4] {
+    let mut components: [Vec<[f64; 2]>; 4] = Default::default();
+    for s in 0..samples_count {
+        let x = s as f32 / (samples_count - 1) as f32;
+        let quaternion = *animation.get_sample(x).quaternion();
+        for (component, value) in
+            components
+                .iter_mut()
+                .zip([quaternion.w, quaternion.i, quaternion.j, quaternion.k])
+        {
+            component.push([x as f64, value as f64]);
+        }
+    }
+    components
+}
+
+fn get_recovered_euler_angles(
+    animation: &dyn Animation,
+    euler_order: &EulerOrder,
+    samples_count: u16,
+) -> [[Vec<[f64; 2]>; 3]; 2] {
+    let mut angles: [[Vec<[f64; 2]>; 3]; 2] = Default::default();
+    for s in 0..samples_count {
+        let x = s as f32 / (samples_count - 1) as f32;
+        let sample = animation.get_sample(x);
+        for (method, frame) in [sample.quaternion_frame(), sample.euler_frame()]
+            .iter()
+            .enumerate()
+        {
+            let euler = euler_order.get_euler(&get_frame_quaternion(frame));
+            for component in 0..3 {
+                angles[method][component].push([x as f64, euler[component].to_degrees() as f64]);
+            }
+        }
+    }
+    angles
 }
 
 fn build_xyz_settings(
@@ -544,39 +1868,711 @@ fn build_xyz_settings(
     });
 }
 
-fn build_wxyz_settings(
+fn build_axis_angle_settings(
     flex: &mut egui_flex::FlexInstance<'_>,
-    postion: &mut (f32, f32, f32, f32),
+    axis: &mut (f32, f32, f32),
+    angle: &mut f32,
     title: impl Into<WidgetText>,
 ) {
     flex.add_flex(item(), Flex::vertical(), |flex| {
         flex.add(item(), Label::new(title).extend());
-        build_number_settings(flex, &mut postion.0, "W", Some(0.01f32), None);
-        build_number_settings(flex, &mut postion.1, "X", Some(0.01f32), None);
-        build_number_settings(flex, &mut postion.2, "Y", Some(0.01f32), None);
-        build_number_settings(flex, &mut postion.3, "Z", Some(0.01f32), None);
+        build_number_settings(flex, &mut axis.0, "Axis X", Some(0.01f32), None);
+        build_number_settings(flex, &mut axis.1, "Axis Y", Some(0.01f32), None);
+        build_number_settings(flex, &mut axis.2, "Axis Z", Some(0.01f32), None);
+        build_number_settings(flex, angle, "Angle°", Some(0.5f32), None);
     });
 }
 
+fn build_quaternion_settings(
+    flex: &mut egui_flex::FlexInstance<'_>,
+    postion: &mut (f32, f32, f32, f32),
+    convention: &QuaternionConvention,
+    title: impl Into<WidgetText>,
+) -> bool {
+    let mut edit_finished = false;
+    flex.add_flex(item(), Flex::vertical(), |flex| {
+        flex.add(item(), Label::new(title).extend());
+        let mut components =
+            convention.to_components(&Quaternion::new(postion.0, postion.1, postion.2, postion.3));
+        edit_finished = components
+            .iter_mut()
+            .zip(convention.get_labels())
+            .map(|(c, label)| build_number_settings(flex, c, label, Some(0.01f32), None))
+            .collect::<Vec<_>>()
+            .iter()
+            .any(|r| r.drag_stopped() || r.lost_focus());
+        let q = convention.from_components(components);
+        *postion = (q.w, q.i, q.j, q.k);
+
+        let quaternion = Quaternion::new(postion.0, postion.1, postion.2, postion.3);
+        let norm = quaternion.norm();
+        flex.add(
+            item(),
+            Label::new(RichText::new(format!("|q| = {:.4}", norm)).color(
+                if (norm - 1f32).abs() > QUATERNION_NORM_TOLERANCE {
+                    Color32::RED
+                } else {
+                    Color32::GRAY
+                },
+            )),
+        );
+        if flex
+            .add(item(), |ui: &mut egui::Ui| {
+                ui.add_enabled(norm > 1e-6, Button::new("Normalize"))
+            })
+            .inner
+            .clicked()
+        {
+            let q = quaternion / norm;
+            *postion = (q.w, q.i, q.j, q.k);
+        }
+    });
+    edit_finished
+}
+
 fn build_number_settings<Num: emath::Numeric>(
     flex: &mut egui_flex::FlexInstance<'_>,
     num: &mut Num,
     name: impl Into<WidgetText>,
     speed: Option<impl Into<f64>>,
     range: Option<RangeInclusive<Num>>,
+) -> Response {
+    let mut response = None;
+    let speed = speed.map(Into::into).unwrap_or(1f64);
+    flex.add_flex(item(), Flex::horizontal(), |flex| {
+        let drag_value_response = flex
+            .add(item().grow(1.0), |ui: &mut egui::Ui| {
+                let mut drag_value = DragValue::new(num).speed(speed);
+                if let Some(range) = range.clone() {
+                    drag_value = drag_value.range(range);
+                }
+                let mut response = ui.add(drag_value);
+
+                if response.has_focus() {
+                    let steps = ui.input_mut(|i| {
+                        i.count_and_consume_key(Modifiers::SHIFT, egui::Key::ArrowUp) as f64
+                            - i.count_and_consume_key(Modifiers::SHIFT, egui::Key::ArrowDown) as f64
+                    });
+                    if steps != 0f64 {
+                        let mut value = num.to_f64() + steps * speed * get_keyboard_step(ui.ctx());
+                        if let Some(range) = &range {
+                            value = value.clamp(range.start().to_f64(), range.end().to_f64());
+                        }
+                        *num = Num::from_f64(value);
+                        ui.data_mut(|d| d.remove::<String>(response.id));
+                        response.mark_changed();
+                    }
+                }
+                response
+            })
+            .inner;
+        let label = flex.add(item(), Label::new(name).extend()).inner;
+        response = Some(drag_value_response.labelled_by(label.id));
+    });
+    response.unwrap()
+}
+
+fn get_keyboard_step(ctx: &egui::Context) -> f64 {
+    ctx.data(|d| d.get_temp(Id::new(KEYBOARD_STEP_ID)))
+        .unwrap_or(1f64)
+}
+
+fn get_animation_methods(
+    animation_data: &AnimationData,
+    comparison_methods: &[String],
+) -> Vec<String> {
+    let mut methods = vec![
+        animation_data
+            .get_rotation_interpolator()
+            .get_id()
+            .to_string(),
+        EULER_METHOD.to_string(),
+    ];
+    for method in comparison_methods {
+        if !methods.contains(method) {
+            methods.push(method.clone());
+        }
+    }
+    methods
+}
+
+fn build_animation(
+    animation_data: &AnimationData,
+    angle_input: &AngleInput,
+    comparison_methods: &[String],
+) -> Box<dyn Animation> {
+    let (begin_angle, end_angle) = animation_data.get_angles(angle_input);
+    let interpolator = animation_data.get_interpolator(begin_angle, end_angle);
+    let methods = get_animation_methods(animation_data, comparison_methods);
+
+    if animation_data.display_all_frames {
+        Box::new(
+            DiscreteFrameAnimationBuilder::default()
+                .frames_count(animation_data.frames_count)
+                .frame_spacing(animation_data.frame_spacing.clone())
+                .methods(methods)
+                .interpolator(interpolator)
+                .build()
+                .unwrap(),
+        )
+    } else {
+        Box::new(
+            ContinuousAnimationBuilder::default()
+                .animation_time(animation_data.animation_time)
+                .time_accumulation(animation_data.time_accumulation.clone())
+                .time_remap(if animation_data.time_remap_enabled {
+                    Some(animation_data.time_remap.clone())
+                } else {
+                    None
+                })
+                .slow_motion(if animation_data.auto_slow_motion {
+                    Some(SlowMotionProfile::analyze(&interpolator))
+                } else if animation_data.midpoint_slow_motion {
+                    Some(SlowMotionProfile::focus(0.5))
+                } else {
+                    None
+                })
+                .playback_speed(animation_data.playback_speed as f64)
+                .methods(methods)
+                .interpolator(interpolator)
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
+fn get_pose_ghosts(
+    animation_data: &AnimationData,
+    angle_input: &AngleInput,
+) -> [[Matrix4<f32>; 2]; 2] {
+    let (begin_angle, end_angle) = animation_data.get_angles(angle_input);
+    let interpolator = animation_data.get_interpolator(begin_angle, end_angle);
+    let [begin, end] = [0f32, 1f32].map(|x| interpolator.get_sample(x));
+    [
+        [*begin.quaternion_frame(), *end.quaternion_frame()],
+        [*begin.euler_frame(), *end.euler_frame()],
+    ]
+}
+
+fn get_matrix_lerp_ghost(
+    animation_data: &AnimationData,
+    angle_input: &AngleInput,
+    x: f32,
+) -> Matrix4<f32> {
+    let (begin_angle, end_angle) = animation_data.get_angles(angle_input);
+    let interpolator = AnimationData {
+        quaternion_interpolation_type: MATRIX_LERP.to_string(),
+        ..animation_data.clone()
+    }
+    .get_interpolator(begin_angle, end_angle);
+    *interpolator.get_sample(x).quaternion_frame()
+}
+
+fn get_edited_angle_input(previous: &AnimationData, current: &AnimationData) -> Option<AngleInput> {
+    if previous.begin_rotation_quaternion != current.begin_rotation_quaternion
+        || previous.end_rotation_quaternion != current.end_rotation_quaternion
+    {
+        Some(AngleInput::Quaternion)
+    } else if previous.begin_rotation_xyz != current.begin_rotation_xyz
+        || previous.end_rotation_xyz != current.end_rotation_xyz
+    {
+        Some(AngleInput::Euler)
+    } else if previous.begin_rotation_axis != current.begin_rotation_axis
+        || previous.begin_rotation_angle != current.begin_rotation_angle
+        || previous.end_rotation_axis != current.end_rotation_axis
+        || previous.end_rotation_angle != current.end_rotation_angle
+    {
+        Some(AngleInput::AxisAngle)
+    } else {
+        None
+    }
+}
+
+fn build_waypoints_settings(
+    flex: &mut egui_flex::FlexInstance<'_>,
+    waypoints: &mut Vec<(f32, f32, f32)>,
+) {
+    let mut removed = None;
+    for (i, waypoint) in waypoints.iter_mut().enumerate() {
+        flex.add_flex(item(), Flex::horizontal(), |flex| {
+            build_xyz_settings(flex, waypoint, format!("Waypoint {}", i + 1));
+            if flex.add(item(), Button::new("remove")).inner.clicked() {
+                removed = Some(i);
+            }
+        });
+    }
+    if let Some(i) = removed {
+        waypoints.remove(i);
+    }
+
+    if flex
+        .add(item(), Button::new("Add waypoint"))
+        .inner
+        .clicked()
+    {
+        waypoints.push(waypoints.last().copied().unwrap_or_default());
+    }
+}
+
+fn build_comparison_settings(
+    flex: &mut egui_flex::FlexInstance<'_>,
+    animation_data: &AnimationData,
+    render_settings: &mut RenderSettings,
+) {
+    flex.add(
+        item().align_self(egui_flex::FlexAlign::Start),
+        Checkbox::new(&mut render_settings.comparison_grid, "Comparison grid"),
+    );
+    if !render_settings.comparison_grid {
+        return;
+    }
+    let methods = std::iter::once(EULER_METHOD.to_string())
+        .chain(
+            get_rotation_interpolators()
+                .iter()
+                .map(|i| i.get_id().to_string()),
+        )
+        .collect::<Vec<_>>();
+    flex.add(item(), |ui: &mut egui::Ui| {
+        ui.horizontal_wrapped(|ui| {
+            for method in &methods {
+                let mut selected = render_settings.comparison_methods.contains(method);
+                if ui
+                    .checkbox(&mut selected, animation_data.get_method_name(method))
+                    .changed()
+                {
+                    let previous = std::mem::take(&mut render_settings.comparison_methods);
+                    render_settings.comparison_methods = methods
+                        .iter()
+                        .filter(|m| {
+                            (*m == method && selected) || (*m != method && previous.contains(m))
+                        })
+                        .cloned()
+                        .collect();
+                }
+            }
+        })
+        .response
+    });
+}
+
+fn build_tcb_settings(
+    flex: &mut egui_flex::FlexInstance<'_>,
+    tcb_parameters: &mut Vec<(f32, f32, f32)>,
+    waypoints_count: usize,
 ) {
+    let keyframes_count = waypoints_count + 2;
+    tcb_parameters.resize(keyframes_count, (0f32, 0f32, 0f32));
+    for (i, parameters) in tcb_parameters.iter_mut().enumerate() {
+        let name = match i {
+            0 => "Begin".to_string(),
+            i if i == keyframes_count - 1 => "End".to_string(),
+            i => format!("Waypoint {}", i),
+        };
+        flex.add_flex(item(), Flex::horizontal(), |flex| {
+            flex.add(item(), Label::new(name));
+            for (value, label) in [
+                (&mut parameters.0, "T"),
+                (&mut parameters.1, "C"),
+                (&mut parameters.2, "B"),
+            ] {
+                build_number_settings(flex, value, label, Some(0.01f32), Some(-1f32..=1f32));
+            }
+        });
+    }
+}
+
+fn build_obstacles_settings(flex: &mut egui_flex::FlexInstance<'_>, obstacles: &mut Vec<Obstacle>) {
+    let mut removed = None;
+    for (i, obstacle) in obstacles.iter_mut().enumerate() {
+        let name = match obstacle.shape {
+            ObstacleShape::Box => "Box",
+            ObstacleShape::Sphere => "Sphere",
+        };
+        flex.add_flex(item(), Flex::horizontal(), |flex| {
+            build_xyz_settings(
+                flex,
+                &mut obstacle.position,
+                format!("{} {} position", name, i + 1),
+            );
+            build_xyz_settings(flex, &mut obstacle.size, "Size");
+            if flex.add(item(), Button::new("remove")).inner.clicked() {
+                removed = Some(i);
+            }
+        });
+    }
+    if let Some(i) = removed {
+        obstacles.remove(i);
+    }
+
     flex.add_flex(item(), Flex::horizontal(), |flex| {
-        let mut drag_value = DragValue::new(num);
+        if flex.add(item(), Button::new("Add box")).inner.clicked() {
+            obstacles.push(Obstacle::new(ObstacleShape::Box));
+        }
+        if flex.add(item(), Button::new("Add sphere")).inner.clicked() {
+            obstacles.push(Obstacle::new(ObstacleShape::Sphere));
+        }
+    });
+}
 
-        if let Some(speed) = speed {
-            drag_value = drag_value.speed(speed);
+fn build_lighting_settings(
+    flex: &mut egui_flex::FlexInstance<'_>,
+    lighting: &mut Lighting,
+    title: &str,
+) {
+    flex.add_flex(item(), Flex::vertical(), |flex| {
+        let label = flex.add(item(), Label::new(title)).inner;
+        flex.add(item(), |ui: &mut egui::Ui| {
+            ui.color_edit_button_rgb(&mut lighting.light_color)
+                .labelled_by(label.id)
+        });
+        build_number_settings(
+            flex,
+            &mut lighting.ambient,
+            "Ambient",
+            Some(0.01f32),
+            Some(0f32..=1f32),
+        );
+        build_number_settings(
+            flex,
+            &mut lighting.specular,
+            "Specular",
+            Some(0.01f32),
+            Some(0f32..=2f32),
+        );
+        build_number_settings(
+            flex,
+            &mut lighting.shininess,
+            "Shininess",
+            Some(0.5f32),
+            Some(1f32..=200f32),
+        );
+        for (i, light) in lighting.lights.iter_mut().enumerate() {
+            build_light_settings(flex, light, &format!("Light {}", i + 1));
         }
+    });
+}
+
+fn build_light_settings(flex: &mut egui_flex::FlexInstance<'_>, light: &mut Light, title: &str) {
+    flex.add(
+        item().align_self(egui_flex::FlexAlign::Start),
+        Checkbox::new(&mut light.enabled, title),
+    );
+    if !light.enabled {
+        return;
+    }
+    build_xyz_settings(flex, &mut light.position, "Position");
+    flex.add(item(), |ui: &mut egui::Ui| {
+        ui.horizontal(|ui| {
+            let label = ui.label("Color");
+            ui.color_edit_button_rgb(&mut light.color)
+                .labelled_by(label.id)
+        })
+        .inner
+    });
+    build_number_settings(
+        flex,
+        &mut light.intensity,
+        "Intensity",
+        Some(0.01f32),
+        Some(0f32..=10f32),
+    );
+}
+
+fn build_projection_settings(flex: &mut egui_flex::FlexInstance<'_>, projection: &mut Projection) {
+    flex.add(item(), Label::new("View"));
+    build_number_settings(
+        flex,
+        &mut projection.fov,
+        "FOV [°]",
+        Some(0.5f32),
+        Some(10f32..=170f32),
+    );
+    build_number_settings(
+        flex,
+        &mut projection.near,
+        "Near plane",
+        Some(0.01f32),
+        Some(0.001f32..=10f32),
+    );
+    build_number_settings(
+        flex,
+        &mut projection.far,
+        "Far plane",
+        Some(1f32),
+        Some(projection.near + 1f32..=10000f32),
+    );
+    flex.add(
+        item().align_self(egui_flex::FlexAlign::Start),
+        Checkbox::new(&mut projection.orthographic, "Orthographic"),
+    );
+}
+
+fn build_block_geometry_settings(
+    flex: &mut egui_flex::FlexInstance<'_>,
+    block_geometry: &mut BlockGeometry,
+) {
+    flex.add(item(), Label::new("Model"));
+    build_number_settings(
+        flex,
+        &mut block_geometry.radius,
+        "Block radius",
+        Some(0.01f32),
+        Some(0.05f32..=10f32),
+    );
+    build_number_settings(
+        flex,
+        &mut block_geometry.length,
+        "Block length",
+        Some(0.05f32),
+        Some(0.1f32..=50f32),
+    );
+    build_number_settings(
+        flex,
+        &mut block_geometry.divisions_count,
+        "Block divisions",
+        None::<f64>,
+        Some(3..=64),
+    );
+    flex.add(item(), |ui: &mut egui::Ui| {
+        ui.horizontal(|ui| {
+            let label = ui.label("Axis colors");
+            for color in block_geometry.axis_colors.iter_mut() {
+                ui.color_edit_button_rgb(color).labelled_by(label.id);
+            }
+        })
+        .response
+    });
+}
+
+fn build_grid_settings(flex: &mut egui_flex::FlexInstance<'_>, grid: &mut Grid) {
+    flex.add(
+        item().align_self(egui_flex::FlexAlign::Start),
+        Checkbox::new(&mut grid.visible, "Display grid"),
+    );
+    flex.add(
+        item().align_self(egui_flex::FlexAlign::Start),
+        Checkbox::new(&mut grid.shadows, "Shadows on grid"),
+    );
+    build_number_settings(
+        flex,
+        &mut grid.cell_size,
+        "Grid cell size",
+        Some(0.01f32),
+        Some(0.01f32..=100f32),
+    );
+    build_number_settings(
+        flex,
+        &mut grid.fade_distance,
+        "Grid fade distance",
+        Some(0.5f32),
+        Some(1f32..=1000f32),
+    );
+    flex.add(item(), |ui: &mut egui::Ui| {
+        ui.horizontal(|ui| {
+            let label = ui.label("Grid color");
+            ui.color_edit_button_rgb(&mut grid.line_color)
+                .labelled_by(label.id)
+        })
+        .inner
+    });
+}
+
+fn build_appearance_settings(flex: &mut egui_flex::FlexInstance<'_>, appearance: &mut Appearance) {
+    flex.add(item(), |ui: &mut egui::Ui| {
+        ui.horizontal(|ui| {
+            let label = ui.label("Background color");
+            ui.color_edit_button_rgb(&mut appearance.background_color)
+                .labelled_by(label.id)
+        })
+        .inner
+    });
+    build_combo_box_settings(
+        flex,
+        &mut appearance.theme,
+        "Theme",
+        &[(Theme::Dark, "Dark"), (Theme::Light, "Light")],
+    );
+}
 
-        if let Some(range) = range {
-            drag_value = drag_value.range(range);
+fn build_frame_pacing_settings(
+    flex: &mut egui_flex::FlexInstance<'_>,
+    frame_pacing: &mut FramePacing,
+) {
+    flex.add(
+        item().align_self(egui_flex::FlexAlign::Start),
+        Checkbox::new(&mut frame_pacing.vsync, "VSync (applied on restart)"),
+    );
+    flex.add_flex(item(), Flex::horizontal(), |flex| {
+        flex.add(
+            item(),
+            Checkbox::new(&mut frame_pacing.limit_fps, "Limit FPS"),
+        );
+        build_number_settings(
+            flex,
+            &mut frame_pacing.max_fps,
+            "Max FPS",
+            Some(1f32),
+            Some(1..=500),
+        );
+    });
+    flex.add(
+        item().align_self(egui_flex::FlexAlign::Start),
+        Checkbox::new(&mut frame_pacing.throttle_when_idle, "Throttle when idle"),
+    );
+    flex.add(
+        item().align_self(egui_flex::FlexAlign::Start),
+        Checkbox::new(
+            &mut frame_pacing.pause_when_inactive,
+            "Pause when unfocused or minimized",
+        ),
+    );
+}
+
+fn build_video_settings(flex: &mut egui_flex::FlexInstance<'_>, video: &mut VideoSettings) {
+    flex.add_flex(item(), Flex::horizontal(), |flex| {
+        build_number_settings(
+            flex,
+            &mut video.width,
+            "Video width",
+            None::<f64>,
+            Some(16..=7680),
+        );
+        build_number_settings(
+            flex,
+            &mut video.height,
+            "Video height",
+            None::<f64>,
+            Some(16..=4320),
+        );
+        build_number_settings(flex, &mut video.fps, "FPS", None::<f64>, Some(1..=240));
+    });
+    build_combo_box_settings(
+        flex,
+        &mut video.output,
+        "Video output",
+        &[
+            (VideoOutput::PngSequence, "PNG sequence"),
+            (VideoOutput::Ffmpeg, "ffmpeg (mp4)"),
+            (VideoOutput::Gif, "Animated GIF"),
+        ],
+    );
+    if video.output == VideoOutput::Gif {
+        build_number_settings(
+            flex,
+            &mut video.gif_scale,
+            "GIF scale",
+            Some(0.01f32),
+            Some(0.05f32..=1f32),
+        );
+    }
+}
+
+fn build_numerical_policy_settings(
+    flex: &mut egui_flex::FlexInstance<'_>,
+    policy: &mut NumericalPolicy,
+) {
+    flex.add(item(), Label::new("Numerical policy"));
+    for (epsilon, name) in [
+        (&mut policy.theta_sin_epsilon, "sin(theta) epsilon"),
+        (&mut policy.zero_norm_epsilon, "Zero norm epsilon"),
+        (&mut policy.angle_wrap_epsilon, "Angle wrap epsilon"),
+    ] {
+        flex.add(item(), |ui: &mut egui::Ui| {
+            ui.add(
+                Slider::new(epsilon, 1e-12f32..=1e-1f32)
+                    .logarithmic(true)
+                    .text(name),
+            )
+        });
+    }
+}
+
+fn build_presets_settings(
+    flex: &mut egui_flex::FlexInstance<'_>,
+    animation_data: &mut AnimationData,
+) {
+    flex.add(item(), |ui: &mut egui::Ui| {
+        ComboBox::from_label("Preset")
+            .selected_text("Select preset")
+            .show_ui(ui, |ui| {
+                for (name, preset) in get_presets() {
+                    if ui.selectable_label(false, name).clicked() {
+                        *animation_data = preset;
+                    }
+                }
+            })
+            .response
+    });
+}
+
+fn build_euler_representation_settings(
+    flex: &mut egui_flex::FlexInstance<'_>,
+    angle: &AnimationAngle,
+    representation: &mut EulerRepresentation,
+    euler_order: &EulerOrder,
+    policy: &NumericalPolicy,
+    title: &str,
+) {
+    let [primary, alternative] = angle.get_euler_representations(euler_order, policy);
+
+    flex.add(item(), Label::new(title));
+    for (value, euler) in [
+        (EulerRepresentation::Primary, primary),
+        (EulerRepresentation::Alternative, alternative),
+    ] {
+        let text = format!(
+            "({:.1}°, {:.1}°, {:.1}°)",
+            euler.x.to_degrees(),
+            euler.y.to_degrees(),
+            euler.z.to_degrees()
+        );
+        if flex
+            .add(
+                item().align_self(egui_flex::FlexAlign::Start),
+                RadioButton::new(*representation == value, text),
+            )
+            .inner
+            .clicked()
+        {
+            *representation = value;
         }
+    }
+}
 
-        flex.add(item().grow(1.0), drag_value);
-        flex.add(item(), Label::new(name).extend());
+fn build_combo_box_settings<Value: PartialEq + Clone>(
+    flex: &mut egui_flex::FlexInstance<'_>,
+    value: &mut Value,
+    name: &str,
+    options: &[(Value, &str)],
+) {
+    build_enabled_combo_box_settings(flex, value, name, options, true);
+}
+
+fn build_enabled_combo_box_settings<Value: PartialEq + Clone>(
+    flex: &mut egui_flex::FlexInstance<'_>,
+    value: &mut Value,
+    name: &str,
+    options: &[(Value, &str)],
+    enabled: bool,
+) {
+    let selected_text = options
+        .iter()
+        .find(|(option, _)| option == value)
+        .map(|(_, option_name)| *option_name)
+        .unwrap_or_default();
+
+    flex.add(item(), |ui: &mut egui::Ui| {
+        ui.add_enabled_ui(enabled, |ui| {
+            ComboBox::from_label(name)
+                .selected_text(selected_text)
+                .show_ui(ui, |ui| {
+                    for (option, option_name) in options {
+                        ui.selectable_value(value, option.clone(), *option_name);
+                    }
+                })
+                .response
+        })
+        .inner
     });
 }
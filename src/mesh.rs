@@ -0,0 +1,132 @@
+use std::fs::read_to_string;
+use std::io::{Error, ErrorKind};
+use std::path::Path;
+
+use derive_getters::Getters;
+use glium::glutin::surface::WindowSurface;
+use glium::index::PrimitiveType;
+use glium::{Display, IndexBuffer, VertexBuffer};
+use nalgebra::Vector3;
+
+use crate::vertex::Vertex;
+
+const OBJ_DEFAULT_COLOR: [f32; 3] = [0.8f32, 0.8f32, 0.8f32];
+
+#[derive(Debug, Getters)]
+pub struct Mesh {
+    vertices: VertexBuffer<Vertex>,
+    indices: IndexBuffer<u32>,
+    radius: f32,
+}
+
+impl Mesh {
+    pub fn from_mesh(
+        vertices: &[Vertex],
+        indices: &[u32],
+        display: &Display<WindowSurface>,
+    ) -> Self {
+        Self {
+            vertices: VertexBuffer::new(display, vertices).unwrap(),
+            indices: IndexBuffer::new(display, PrimitiveType::TrianglesList, indices).unwrap(),
+            radius: vertices
+                .iter()
+                .map(|v| Vector3::from(*v.position()).norm())
+                .fold(0f32, f32::max),
+        }
+    }
+
+    pub fn load_obj(path: &Path, display: &Display<WindowSurface>) -> std::io::Result<Self> {
+        let content = read_to_string(path)?;
+        let invalid_line = |i: usize| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Invalid OBJ data on line {}", i + 1),
+            )
+        };
+
+        let mut positions = Vec::new();
+        let mut colors = Vec::new();
+        let mut normals = Vec::new();
+        let mut vertices = Vec::new();
+        for (i, line) in content.lines().enumerate() {
+            let mut tokens = line.split_whitespace();
+            match tokens.next() {
+                Some("v") => {
+                    let values = parse_floats(tokens).ok_or_else(|| invalid_line(i))?;
+                    if values.len() < 3 {
+                        return Err(invalid_line(i));
+                    }
+                    positions.push(Vector3::new(values[0], values[1], values[2]));
+                    colors.push(if values.len() >= 6 {
+                        [values[3], values[4], values[5]]
+                    } else {
+                        OBJ_DEFAULT_COLOR
+                    });
+                }
+                Some("vn") => {
+                    let values = parse_floats(tokens).ok_or_else(|| invalid_line(i))?;
+                    if values.len() < 3 {
+                        return Err(invalid_line(i));
+                    }
+                    normals.push(Vector3::new(values[0], values[1], values[2]).normalize());
+                }
+                Some("f") => {
+                    let corners = tokens
+                        .map(|t| parse_face_corner(t, positions.len(), normals.len()))
+                        .collect::<Option<Vec<_>>>()
+                        .filter(|c| c.len() >= 3)
+                        .ok_or_else(|| invalid_line(i))?;
+                    let face_normal = (positions[corners[1].0] - positions[corners[0].0])
+                        .cross(&(positions[corners[2].0] - positions[corners[0].0]))
+                        .try_normalize(0f32)
+                        .unwrap_or_else(Vector3::z);
+
+                    for k in 1..corners.len() - 1 {
+                        for (p, n) in [corners[0], corners[k], corners[k + 1]] {
+                            vertices.push(Vertex::new(
+                                positions[p].into(),
+                                n.map(|n| normals[n]).unwrap_or(face_normal).into(),
+                                colors[p],
+                            ));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if vertices.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidData, "OBJ file has no faces"));
+        }
+        let indices = (0..vertices.len() as u32).collect::<Vec<_>>();
+        Ok(Self::from_mesh(&vertices, &indices, display))
+    }
+}
+
+fn parse_floats<'a>(tokens: impl Iterator<Item = &'a str>) -> Option<Vec<f32>> {
+    tokens.map(|t| t.parse().ok()).collect()
+}
+
+fn parse_face_corner(
+    token: &str,
+    positions_count: usize,
+    normals_count: usize,
+) -> Option<(usize, Option<usize>)> {
+    let mut parts = token.split('/');
+    let position = get_obj_index(parts.next()?, positions_count)?;
+    let normal = match parts.nth(1) {
+        Some(n) if !n.is_empty() => Some(get_obj_index(n, normals_count)?),
+        _ => None,
+    };
+    Some((position, normal))
+}
+
+fn get_obj_index(token: &str, count: usize) -> Option<usize> {
+    let index = token.parse::<i64>().ok()?;
+    let index = if index < 0 {
+        count as i64 + index
+    } else {
+        index - 1
+    };
+    (0..count as i64).contains(&index).then_some(index as usize)
+}
@@ -0,0 +1,49 @@
+use std::collections::HashMap;
+
+use glium::glutin::surface::WindowSurface;
+use glium::Display;
+
+use crate::mesh::Mesh;
+use crate::render_settings::{BlockGeometry, SceneObjectType};
+
+pub struct MeshCache {
+    meshes: HashMap<SceneObjectType, Mesh>,
+    custom_mesh: Option<Mesh>,
+    block_geometry: BlockGeometry,
+}
+
+impl MeshCache {
+    pub fn new() -> Self {
+        Self {
+            meshes: HashMap::new(),
+            custom_mesh: None,
+            block_geometry: BlockGeometry::default(),
+        }
+    }
+
+    pub fn set_custom_mesh(&mut self, mesh: Mesh) {
+        self.custom_mesh = Some(mesh);
+    }
+
+    pub fn get(
+        &mut self,
+        scene_object_type: &SceneObjectType,
+        block_geometry: &BlockGeometry,
+        display: &Display<WindowSurface>,
+    ) -> &Mesh {
+        if let (SceneObjectType::Custom, Some(mesh)) = (scene_object_type, &self.custom_mesh) {
+            return mesh;
+        }
+        if *block_geometry != self.block_geometry {
+            self.meshes.remove(&SceneObjectType::Block);
+            self.meshes.remove(&SceneObjectType::Custom);
+            self.block_geometry = block_geometry.clone();
+        }
+
+        self.meshes
+            .entry(scene_object_type.clone())
+            .or_insert_with(|| {
+                Mesh::generate_scene_object(scene_object_type, block_geometry, display)
+            })
+    }
+}
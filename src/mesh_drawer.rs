@@ -0,0 +1,266 @@
+use derive_new::new;
+use glium::draw_parameters::{BackfaceCullingMode, PolygonMode};
+use glium::glutin::surface::WindowSurface;
+use glium::{uniform, Display, DrawParameters, Program, Surface, VertexBuffer};
+use nalgebra::{Matrix4, Vector3};
+
+use crate::mesh::Mesh;
+use crate::render_settings::{Light, Lighting, RenderMode, UpAxis};
+use crate::scene_view::SceneView;
+use crate::vertex::InstanceData;
+
+#[derive(Clone, Copy, new)]
+pub struct MeshShading<'a> {
+    pub up_axis: &'a UpAxis,
+    pub lighting: &'a Lighting,
+    pub render_mode: &'a RenderMode,
+    #[new(default)]
+    pub discrepancy_shading: bool,
+}
+
+pub struct MeshDrawer {
+    program: Program,
+    instanced_program: Program,
+}
+
+impl MeshDrawer {
+    pub fn new(display: &Display<WindowSurface>) -> Self {
+        let vertex_shader_src = r#"
+            #version 410 core
+
+            in vec3 position;
+            in vec3 normal;
+            in vec3 color;
+
+            out vec3 normal_out;
+            out vec3 color_out;
+            out vec3 world;
+            out float alpha_out;
+            out float discrepancy_out;
+            out float tint_out;
+
+            uniform mat4 perspective;
+            uniform mat4 view;
+            uniform mat4 model;
+            uniform float alpha;
+            uniform float discrepancy;
+            uniform float tint;
+
+            void main() {
+                gl_Position = perspective * view * model * vec4(position, 1.0);
+                normal_out = mat3(model) * normal;
+                color_out = color;
+                world = (model * vec4(position, 1.0)).xyz;
+                alpha_out = alpha;
+                discrepancy_out = discrepancy;
+                tint_out = tint;
+            }
+        "#;
+
+        let instanced_vertex_shader_src = r#"
+            #version 410 core
+
+            in vec3 position;
+            in vec3 normal;
+            in vec3 color;
+            in mat4 model;
+            in float alpha;
+            in float discrepancy;
+            in float tint;
+
+            out vec3 normal_out;
+            out vec3 color_out;
+            out vec3 world;
+            out float alpha_out;
+            out float discrepancy_out;
+            out float tint_out;
+
+            uniform mat4 perspective;
+            uniform mat4 view;
+
+            void main() {
+                gl_Position = perspective * view * model * vec4(position, 1.0);
+                normal_out = mat3(model) * normal;
+                color_out = color;
+                world = (model * vec4(position, 1.0)).xyz;
+                alpha_out = alpha;
+                discrepancy_out = discrepancy;
+                tint_out = tint;
+            }
+        "#;
+
+        let fragment_shader_src = r#"
+            #version 410 core
+
+            in vec3 normal_out;
+            in vec3 color_out;
+            in vec3 world;
+            in float alpha_out;
+            in float discrepancy_out;
+            in float tint_out;
+
+            out vec4 frag_color;
+
+            const vec3 tint_color = vec3(1.0, 0.1, 0.1);
+
+            uniform vec3 cam_pos;
+            uniform vec3 light0_pos;
+            uniform vec3 light0_radiance;
+            uniform vec3 light1_pos;
+            uniform vec3 light1_radiance;
+            uniform bool discrepancy_shading;
+            uniform bool normals_shading;
+            uniform vec3 light_color;
+            uniform float ambient;
+            uniform float specular_strength;
+            uniform float shininess;
+
+            vec3 heat(float d) {
+                return vec3(d, 1.0 - abs(2.0 * d - 1.0), 1.0 - d);
+            }
+
+            vec3 shade(vec3 light_pos, vec3 radiance, vec3 normal, vec3 to_cam) {
+                vec3 to_light = normalize(light_pos - world);
+
+                float diffuse =  max(dot(normal, to_light), 0.0);
+                vec3 reflected = normalize(reflect(-to_light, normal));
+                float specular = specular_strength * pow(max(dot(reflected, to_cam), 0.0), shininess);
+
+                return (diffuse + specular) * radiance;
+            }
+
+            void main() {
+                if (normals_shading) {
+                    frag_color = vec4(normalize(normal_out) * 0.5 + 0.5, alpha_out);
+                    return;
+                }
+
+                vec3 base_color = discrepancy_shading ? heat(clamp(discrepancy_out, 0.0, 1.0)) : color_out;
+                base_color = mix(base_color, tint_color, 0.7 * tint_out);
+                vec3 normal = normalize(normal_out);
+                vec3 to_cam = normalize(cam_pos - world);
+                vec3 lit = ambient
+                    + shade(light0_pos, light0_radiance, normal, to_cam)
+                    + shade(light1_pos, light1_radiance, normal, to_cam);
+
+                frag_color = vec4(lit * light_color * base_color, alpha_out);
+            }
+        "#;
+
+        let program =
+            Program::from_source(display, vertex_shader_src, fragment_shader_src, None).unwrap();
+        let instanced_program = Program::from_source(
+            display,
+            instanced_vertex_shader_src,
+            fragment_shader_src,
+            None,
+        )
+        .unwrap();
+
+        Self {
+            program,
+            instanced_program,
+        }
+    }
+
+    pub fn draw(
+        &self,
+        target: &mut impl Surface,
+        scene: &SceneView,
+        model: &Matrix4<f32>,
+        alpha: f32,
+        shading: &MeshShading,
+        mesh: &Mesh,
+    ) {
+        let lighting = shading.lighting;
+        target
+            .draw(
+                mesh.vertices(),
+                mesh.indices(),
+                &self.program,
+                &uniform! {
+                    perspective: scene.perspective.data.0,
+                    view: scene.view.data.0,
+                    model: model.data.0,
+                    cam_pos: scene.camera_position.data.0[0],
+                    light0_pos: get_light_position(shading.up_axis, &lighting.lights[0]).data.0[0],
+                    light0_radiance: lighting.lights[0].get_radiance(),
+                    light1_pos: get_light_position(shading.up_axis, &lighting.lights[1]).data.0[0],
+                    light1_radiance: lighting.lights[1].get_radiance(),
+                    alpha: alpha,
+                    discrepancy: 0f32,
+                    tint: 0f32,
+                    discrepancy_shading: false,
+                    normals_shading: *shading.render_mode == RenderMode::Normals,
+                    light_color: lighting.light_color,
+                    ambient: lighting.ambient,
+                    specular_strength: lighting.specular,
+                    shininess: lighting.shininess,
+                },
+                &get_drawing_parameters(scene.drawing_parameters, shading.render_mode),
+            )
+            .unwrap();
+    }
+
+    pub fn draw_instanced(
+        &self,
+        target: &mut impl Surface,
+        display: &Display<WindowSurface>,
+        scene: &SceneView,
+        instances: &[InstanceData],
+        shading: &MeshShading,
+        mesh: &Mesh,
+    ) {
+        let lighting = shading.lighting;
+        let instance_buffer = VertexBuffer::dynamic(display, instances).unwrap();
+
+        target
+            .draw(
+                (mesh.vertices(), instance_buffer.per_instance().unwrap()),
+                mesh.indices(),
+                &self.instanced_program,
+                &uniform! {
+                    perspective: scene.perspective.data.0,
+                    view: scene.view.data.0,
+                    cam_pos: scene.camera_position.data.0[0],
+                    light0_pos: get_light_position(shading.up_axis, &lighting.lights[0]).data.0[0],
+                    light0_radiance: lighting.lights[0].get_radiance(),
+                    light1_pos: get_light_position(shading.up_axis, &lighting.lights[1]).data.0[0],
+                    light1_radiance: lighting.lights[1].get_radiance(),
+                    discrepancy_shading: shading.discrepancy_shading,
+                    normals_shading: *shading.render_mode == RenderMode::Normals,
+                    light_color: lighting.light_color,
+                    ambient: lighting.ambient,
+                    specular_strength: lighting.specular,
+                    shininess: lighting.shininess,
+                },
+                &get_drawing_parameters(scene.drawing_parameters, shading.render_mode),
+            )
+            .unwrap();
+    }
+}
+
+fn get_drawing_parameters<'a>(
+    drawing_parameters: &DrawParameters<'a>,
+    render_mode: &RenderMode,
+) -> DrawParameters<'a> {
+    match render_mode {
+        RenderMode::Wireframe => DrawParameters {
+            polygon_mode: PolygonMode::Line,
+            backface_culling: BackfaceCullingMode::CullingDisabled,
+            ..drawing_parameters.clone()
+        },
+        RenderMode::Shaded | RenderMode::Normals => drawing_parameters.clone(),
+    }
+}
+
+fn get_light_position(up_axis: &UpAxis, light: &Light) -> Vector3<f32> {
+    up_axis
+        .get_scene_matrix()
+        .transpose()
+        .transform_vector(&Vector3::new(
+            light.position.0,
+            light.position.1,
+            light.position.2,
+        ))
+}
@@ -0,0 +1,56 @@
+use egui::{Ui, WidgetText};
+use egui_dock::{DockState, NodeIndex, TabViewer};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PanelTab {
+    PoseEditor,
+    Playback,
+    Analysis,
+    RenderSettings,
+    Log,
+}
+
+impl PanelTab {
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            PanelTab::PoseEditor => "Pose editor",
+            PanelTab::Playback => "Playback",
+            PanelTab::Analysis => "Analysis",
+            PanelTab::RenderSettings => "Render settings",
+            PanelTab::Log => "Log",
+        }
+    }
+}
+
+pub struct PanelTabViewer<F: FnMut(&mut Ui, &PanelTab)> {
+    pub build_tab: F,
+}
+
+impl<F: FnMut(&mut Ui, &PanelTab)> TabViewer for PanelTabViewer<F> {
+    type Tab = PanelTab;
+
+    fn title(&mut self, tab: &mut PanelTab) -> WidgetText {
+        tab.get_name().into()
+    }
+
+    fn ui(&mut self, ui: &mut Ui, tab: &mut PanelTab) {
+        (self.build_tab)(ui, tab);
+    }
+
+    fn closeable(&mut self, _tab: &mut PanelTab) -> bool {
+        false
+    }
+}
+
+pub fn get_default_dock_state() -> DockState<PanelTab> {
+    let mut dock_state = DockState::new(vec![PanelTab::PoseEditor, PanelTab::Playback]);
+    let surface = dock_state.main_surface_mut();
+    let [left, _] = surface.split_right(
+        NodeIndex::root(),
+        0.6,
+        vec![PanelTab::RenderSettings, PanelTab::Analysis],
+    );
+    surface.split_below(left, 0.8, vec![PanelTab::Log]);
+    dock_state
+}
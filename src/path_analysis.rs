@@ -0,0 +1,123 @@
+use derive_getters::Getters;
+use nalgebra::{Matrix4, Vector3};
+
+use movement_interpolation::animation::{get_arc_lengths, get_frame_quaternion, Animation};
+use movement_interpolation::animation_data::Obstacle;
+
+const SELF_INTERSECTION_DISTANCE: f32 = 1e-3;
+
+#[derive(Debug, Clone, Default, Getters)]
+pub struct PathWarnings {
+    below_ground: Vec<Vector3<f32>>,
+    inside_obstacle: Vec<Vector3<f32>>,
+    self_intersections: Vec<Vector3<f32>>,
+}
+
+#[derive(Debug, Clone, Getters)]
+pub struct BoundingBox {
+    min: Vector3<f32>,
+    max: Vector3<f32>,
+}
+
+#[derive(Debug, Clone, Default, Getters)]
+pub struct MethodDivergence {
+    position: f32,
+    rotation: f32,
+}
+
+impl PathWarnings {
+    pub fn analyze(path: &[Vector3<f32>], obstacles: &[Obstacle], up: &Vector3<f32>) -> Self {
+        let below_ground = path.iter().filter(|p| p.dot(up) < 0f32).cloned().collect();
+
+        let inside_obstacle = path
+            .iter()
+            .filter(|p| obstacles.iter().any(|o| o.contains(p)))
+            .cloned()
+            .collect();
+
+        let arc_lengths = get_arc_lengths(path);
+        let self_intersections = path
+            .iter()
+            .enumerate()
+            .filter(|(i, p)| {
+                path.iter().enumerate().skip(i + 2).any(|(j, q)| {
+                    (*p - q).norm() < SELF_INTERSECTION_DISTANCE
+                        && arc_lengths[j] - arc_lengths[*i] > 10f32 * SELF_INTERSECTION_DISTANCE
+                })
+            })
+            .map(|(_, p)| *p)
+            .collect();
+
+        Self {
+            below_ground,
+            inside_obstacle,
+            self_intersections,
+        }
+    }
+
+    pub fn get_markers(&self) -> Vec<Vector3<f32>> {
+        [
+            self.below_ground.as_slice(),
+            self.inside_obstacle.as_slice(),
+            self.self_intersections.as_slice(),
+        ]
+        .concat()
+    }
+
+    pub fn get_messages(&self) -> Vec<&'static str> {
+        let mut messages = Vec::new();
+        if !self.below_ground.is_empty() {
+            messages.push("Path goes below the grid plane");
+        }
+        if !self.inside_obstacle.is_empty() {
+            messages.push("Path passes through an obstacle");
+        }
+        if !self.self_intersections.is_empty() {
+            messages.push("Path intersects itself");
+        }
+        messages
+    }
+}
+
+impl BoundingBox {
+    pub fn from_path(path: &[Vector3<f32>], object_radius: f32) -> Self {
+        let margin = Vector3::repeat(object_radius);
+        Self {
+            min: path.iter().fold(Vector3::repeat(f32::MAX), |m, p| m.inf(p)) - margin,
+            max: path.iter().fold(Vector3::repeat(f32::MIN), |m, p| m.sup(p)) + margin,
+        }
+    }
+
+    pub fn get_extents(&self) -> Vector3<f32> {
+        self.max - self.min
+    }
+
+    pub fn get_center(&self) -> Vector3<f32> {
+        (self.min + self.max) / 2f32
+    }
+}
+
+impl MethodDivergence {
+    pub fn from_frames(quaternion_frame: &Matrix4<f32>, euler_frame: &Matrix4<f32>) -> Self {
+        Self {
+            position: (quaternion_frame.fixed_view::<3, 1>(0, 3)
+                - euler_frame.fixed_view::<3, 1>(0, 3))
+            .norm(),
+            rotation: get_frame_quaternion(quaternion_frame)
+                .angle_to(&get_frame_quaternion(euler_frame))
+                .to_degrees(),
+        }
+    }
+
+    pub fn get_max(animation: &dyn Animation, samples_count: u16) -> Self {
+        (0..samples_count)
+            .map(|s| {
+                let sample = animation.get_sample(s as f32 / (samples_count - 1) as f32);
+                Self::from_frames(sample.quaternion_frame(), sample.euler_frame())
+            })
+            .fold(Self::default(), |max, d| Self {
+                position: max.position.max(d.position),
+                rotation: max.rotation.max(d.rotation),
+            })
+    }
+}
@@ -0,0 +1,109 @@
+use glium::glutin::surface::WindowSurface;
+use glium::index::{NoIndices, PrimitiveType};
+use glium::{uniform, Display, DrawParameters, Program, Surface, VertexBuffer};
+use nalgebra::Vector3;
+
+use crate::scene_view::SceneView;
+use crate::vertex::SimpleVertex;
+
+pub struct PathDrawer {
+    program: Program,
+}
+
+impl PathDrawer {
+    pub fn new(display: &Display<WindowSurface>) -> Self {
+        let vertex_shader_src = r#"
+            #version 410 core
+
+            in vec3 position;
+
+            uniform mat4 perspective;
+            uniform mat4 view;
+
+            void main() {
+                gl_Position = perspective * view * vec4(position, 1.0);
+            }
+        "#;
+
+        let fragment_shader_src = r#"
+            #version 410 core
+
+            out vec4 frag_color;
+
+            uniform vec3 color;
+
+            void main() {
+                frag_color = vec4(color, 1.0);
+            }
+        "#;
+
+        let program =
+            Program::from_source(display, vertex_shader_src, fragment_shader_src, None).unwrap();
+
+        Self { program }
+    }
+
+    pub fn draw(
+        &self,
+        target: &mut impl Surface,
+        display: &Display<WindowSurface>,
+        scene: &SceneView,
+        path: &[Vector3<f32>],
+        color: [f32; 3],
+    ) {
+        let vertices = path
+            .iter()
+            .filter(|p| p.iter().all(|v| v.is_finite()))
+            .map(|p| SimpleVertex::new(p.data.0[0]))
+            .collect::<Vec<_>>();
+
+        target
+            .draw(
+                &VertexBuffer::new(display, &vertices).unwrap(),
+                NoIndices(PrimitiveType::LineStrip),
+                &self.program,
+                &uniform! {
+                    perspective: scene.perspective.data.0,
+                    view: scene.view.data.0,
+                    color: color,
+                },
+                scene.drawing_parameters,
+            )
+            .unwrap();
+    }
+
+    pub fn draw_markers(
+        &self,
+        target: &mut impl Surface,
+        display: &Display<WindowSurface>,
+        scene: &SceneView,
+        markers: &[Vector3<f32>],
+        color: [f32; 3],
+    ) {
+        if markers.is_empty() {
+            return;
+        }
+
+        let vertices = markers
+            .iter()
+            .map(|p| SimpleVertex::new(p.data.0[0]))
+            .collect::<Vec<_>>();
+
+        target
+            .draw(
+                &VertexBuffer::new(display, &vertices).unwrap(),
+                NoIndices(PrimitiveType::Points),
+                &self.program,
+                &uniform! {
+                    perspective: scene.perspective.data.0,
+                    view: scene.view.data.0,
+                    color: color,
+                },
+                &DrawParameters {
+                    point_size: Some(8f32),
+                    ..scene.drawing_parameters.clone()
+                },
+            )
+            .unwrap();
+    }
+}
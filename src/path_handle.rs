@@ -0,0 +1,87 @@
+use egui::{pos2, Pos2, Rect};
+use nalgebra::{Matrix4, Vector3};
+
+use movement_interpolation::animation::{get_arc_lengths, get_parameter_at_arc_length, Animation};
+
+pub struct PathHandle {
+    path: Vec<Vector3<f32>>,
+    arc_lengths: Vec<f32>,
+    view_projections: Vec<Matrix4<f32>>,
+    viewports: Vec<Rect>,
+}
+
+impl PathHandle {
+    pub fn new() -> Self {
+        Self {
+            path: Vec::new(),
+            arc_lengths: Vec::new(),
+            view_projections: Vec::new(),
+            viewports: Vec::new(),
+        }
+    }
+
+    pub fn set_path(&mut self, animation: &dyn Animation, samples_count: u16) {
+        self.path = (0..samples_count)
+            .map(|s| {
+                *animation
+                    .get_sample(s as f32 / (samples_count - 1) as f32)
+                    .position()
+            })
+            .collect();
+        self.arc_lengths = get_arc_lengths(&self.path);
+    }
+
+    pub fn set_viewports_count(&mut self, count: usize) {
+        self.view_projections.resize(count, Matrix4::identity());
+        self.viewports.resize(count, Rect::NOTHING);
+    }
+
+    pub fn get_viewports_count(&self) -> usize {
+        self.viewports.len()
+    }
+
+    pub fn set_viewport(&mut self, index: usize, view_projection: Matrix4<f32>, viewport: Rect) {
+        self.view_projections[index] = view_projection;
+        self.viewports[index] = viewport;
+    }
+
+    pub fn project(&self, index: usize, point: &Vector3<f32>) -> Option<Pos2> {
+        let viewport = *self.viewports.get(index)?;
+        let clip = self.view_projections[index] * point.to_homogeneous();
+        if clip.w <= 0f32 || !viewport.is_positive() {
+            return None;
+        }
+
+        Some(pos2(
+            viewport.min.x + (clip.x / clip.w + 1f32) / 2f32 * viewport.width(),
+            viewport.min.y + (1f32 - clip.y / clip.w) / 2f32 * viewport.height(),
+        ))
+    }
+
+    pub fn get_nearest_parameter(&self, index: usize, pointer: Pos2) -> Option<f32> {
+        let points = self
+            .path
+            .iter()
+            .map(|p| self.project(index, p))
+            .collect::<Vec<_>>();
+
+        let (i, fraction, _) = points
+            .windows(2)
+            .enumerate()
+            .filter_map(|(i, w)| {
+                let (begin, end) = (w[0]?, w[1]?);
+                let segment = end - begin;
+                let fraction = if segment.length_sq() > 0f32 {
+                    ((pointer - begin).dot(segment) / segment.length_sq()).clamp(0f32, 1f32)
+                } else {
+                    0f32
+                };
+                Some((i, fraction, pointer.distance(begin + segment * fraction)))
+            })
+            .min_by(|a, b| a.2.total_cmp(&b.2))?;
+
+        let arc_length =
+            self.arc_lengths[i] + fraction * (self.arc_lengths[i + 1] - self.arc_lengths[i]);
+        Some(get_parameter_at_arc_length(&self.arc_lengths, arc_length))
+    }
+}
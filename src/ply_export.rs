@@ -0,0 +1,66 @@
+use std::fs::File;
+use std::io::{BufWriter, Error, ErrorKind, Write};
+use std::path::Path;
+
+use movement_interpolation::animation::Animation;
+use movement_interpolation::animation_data::RunStamp;
+
+pub fn export_quaternion_path_to_ply(
+    animation: &dyn Animation,
+    samples_count: u16,
+    run_stamp: &RunStamp,
+    path: &Path,
+) -> std::io::Result<()> {
+    if samples_count < 2 {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Export samples count must be at least 2",
+        ));
+    }
+
+    let quaternions = (0..samples_count)
+        .map(|s| {
+            let x = s as f32 / (samples_count - 1) as f32;
+            let q = animation.get_sample(x).quaternion().into_inner();
+            (x, if q.w < 0f32 { -q } else { q }, q.w < 0f32)
+        })
+        .collect::<Vec<_>>();
+
+    let edges = quaternions
+        .windows(2)
+        .enumerate()
+        .filter(|(_, w)| w[0].2 == w[1].2)
+        .map(|(i, _)| (i, i + 1))
+        .collect::<Vec<_>>();
+
+    let mut writer = BufWriter::new(File::create(path)?);
+    writeln!(writer, "ply")?;
+    writeln!(writer, "format ascii 1.0")?;
+    writeln!(writer, "comment {}", run_stamp)?;
+    writeln!(
+        writer,
+        "comment x y z: stereographic projection of the w >= 0 quaternion from (-1, 0, 0, 0)"
+    )?;
+    writeln!(writer, "element vertex {}", quaternions.len())?;
+    for property in ["x", "y", "z", "qw", "qx", "qy", "qz", "t"] {
+        writeln!(writer, "property float {}", property)?;
+    }
+    writeln!(writer, "element edge {}", edges.len())?;
+    writeln!(writer, "property int vertex1")?;
+    writeln!(writer, "property int vertex2")?;
+    writeln!(writer, "end_header")?;
+
+    for (x, q, _) in &quaternions {
+        let projection = q.imag() / (1f32 + q.w);
+        writeln!(
+            writer,
+            "{} {} {} {} {} {} {} {}",
+            projection.x, projection.y, projection.z, q.w, q.i, q.j, q.k, x
+        )?;
+    }
+    for (begin, end) in edges {
+        writeln!(writer, "{} {}", begin, end)?;
+    }
+
+    writer.flush()
+}
@@ -0,0 +1,50 @@
+use egui::{Align2, Button, Color32, Context, Id, Pos2, RichText};
+use nalgebra::Matrix4;
+
+use movement_interpolation::animation::get_frame_quaternion;
+use movement_interpolation::animation_data::AnimationData;
+
+pub fn get_pose_readout(model: &Matrix4<f32>, animation_data: &AnimationData) -> String {
+    let quaternion = get_frame_quaternion(model);
+    let components = animation_data
+        .quaternion_convention
+        .to_components(&quaternion);
+    let euler = animation_data.euler_order.get_euler(&quaternion);
+    format!(
+        "position  {:>9.3} {:>9.3} {:>9.3}\nquaternion {} {:>7.4} {:>7.4} {:>7.4} {:>7.4}\neuler {:?}  {:>8.2}° {:>8.2}° {:>8.2}°",
+        model[(0, 3)],
+        model[(1, 3)],
+        model[(2, 3)],
+        animation_data
+            .quaternion_convention
+            .get_labels()
+            .concat()
+            .to_lowercase(),
+        components[0],
+        components[1],
+        components[2],
+        components[3],
+        animation_data.euler_order,
+        euler.x.to_degrees(),
+        euler.y.to_degrees(),
+        euler.z.to_degrees(),
+    )
+}
+
+pub fn build_pose_readout(egui_ctx: &Context, index: usize, anchor: Pos2, readout: &str) {
+    egui::Area::new(Id::new(("pose_readout", index)))
+        .pivot(Align2::RIGHT_TOP)
+        .fixed_pos(anchor)
+        .show(egui_ctx, |ui| {
+            egui::Frame::none()
+                .fill(Color32::from_black_alpha(160))
+                .rounding(4f32)
+                .inner_margin(6f32)
+                .show(ui, |ui| {
+                    ui.label(RichText::new(readout).monospace().color(Color32::WHITE));
+                    if ui.add(Button::new("copy").small()).clicked() {
+                        ui.output_mut(|o| o.copied_text = readout.to_string());
+                    }
+                });
+        });
+}
@@ -0,0 +1,68 @@
+use nalgebra::UnitQuaternion;
+
+use movement_interpolation::animation_data::AnimationData;
+use movement_interpolation::rotation_interpolator::SPHERICAL;
+
+pub fn get_presets() -> Vec<(&'static str, AnimationData)> {
+    vec![
+        ("Default", AnimationData::new()),
+        (
+            "Gimbal lock",
+            AnimationData {
+                display_all_frames: true,
+                ..with_rotations((45f32, 90f32, 0f32), (0f32, 90f32, 45f32))
+            },
+        ),
+        (
+            "179° flip",
+            with_rotations((0f32, 0f32, 0f32), (179f32, 0f32, 0f32)),
+        ),
+        (
+            "181° flip",
+            with_rotations((0f32, 0f32, 0f32), (181f32, 0f32, 0f32)),
+        ),
+        (
+            "Antipodal quaternions",
+            AnimationData {
+                end_rotation_quaternion: (-1f32, 0f32, 0f32, 0f32),
+                ..AnimationData::new()
+            },
+        ),
+        (
+            "Pure translation",
+            AnimationData {
+                begin_position: (-3f32, 0f32, 0f32),
+                end_position: (3f32, 0f32, 2f32),
+                ..AnimationData::new()
+            },
+        ),
+        (
+            "Combined motion",
+            AnimationData {
+                begin_position: (-3f32, 0f32, 0f32),
+                end_position: (3f32, 2f32, 0f32),
+                quaternion_interpolation_type: SPHERICAL.to_string(),
+                ..with_rotations((0f32, 0f32, 0f32), (90f32, 45f32, 120f32))
+            },
+        ),
+    ]
+}
+
+fn with_rotations(begin_xyz: (f32, f32, f32), end_xyz: (f32, f32, f32)) -> AnimationData {
+    AnimationData {
+        begin_rotation_xyz: begin_xyz,
+        end_rotation_xyz: end_xyz,
+        begin_rotation_quaternion: get_quaternion_tuple(&begin_xyz),
+        end_rotation_quaternion: get_quaternion_tuple(&end_xyz),
+        ..AnimationData::new()
+    }
+}
+
+fn get_quaternion_tuple(xyz: &(f32, f32, f32)) -> (f32, f32, f32, f32) {
+    let q = UnitQuaternion::from_euler_angles(
+        xyz.0.to_radians(),
+        xyz.1.to_radians(),
+        xyz.2.to_radians(),
+    );
+    (q.w, q.i, q.j, q.k)
+}
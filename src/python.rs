@@ -0,0 +1,128 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::animation::{AnimationSample, Interpolator};
+use crate::animation_data::{AngleInput, AnimationData};
+use crate::rotation_interpolator::get_rotation_interpolator;
+
+#[pyclass(name = "Interpolator")]
+pub struct PyInterpolator {
+    interpolator: Interpolator,
+}
+
+#[pymethods]
+impl PyInterpolator {
+    #[new]
+    #[pyo3(signature = (
+        begin_position = (0f32, 0f32, 0f32),
+        end_position = (0f32, 0f32, 0f32),
+        begin_quaternion = None,
+        end_quaternion = None,
+        begin_euler = None,
+        end_euler = None,
+        interpolation = "Spherical",
+    ))]
+    fn new(
+        begin_position: (f32, f32, f32),
+        end_position: (f32, f32, f32),
+        begin_quaternion: Option<(f32, f32, f32, f32)>,
+        end_quaternion: Option<(f32, f32, f32, f32)>,
+        begin_euler: Option<(f32, f32, f32)>,
+        end_euler: Option<(f32, f32, f32)>,
+        interpolation: &str,
+    ) -> PyResult<Self> {
+        let angle_input = if begin_euler.is_some() || end_euler.is_some() {
+            AngleInput::Euler
+        } else {
+            AngleInput::Quaternion
+        };
+        let quaternion_interpolation_type = get_rotation_interpolator(interpolation)
+            .ok_or_else(|| {
+                PyValueError::new_err(format!("Unknown interpolation: {}", interpolation))
+            })?
+            .get_id()
+            .to_string();
+
+        let animation_data = AnimationData {
+            begin_position,
+            end_position,
+            begin_rotation_quaternion: begin_quaternion.unwrap_or((1f32, 0f32, 0f32, 0f32)),
+            end_rotation_quaternion: end_quaternion.unwrap_or((1f32, 0f32, 0f32, 0f32)),
+            begin_rotation_xyz: begin_euler.unwrap_or_default(),
+            end_rotation_xyz: end_euler.unwrap_or_default(),
+            quaternion_interpolation_type,
+            ..AnimationData::new()
+        };
+        Ok(Self::from_animation_data(&animation_data, &angle_input))
+    }
+
+    #[staticmethod]
+    #[pyo3(signature = (json, use_euler_input = false))]
+    fn from_json(json: &str, use_euler_input: bool) -> PyResult<Self> {
+        let animation_data = serde_json::from_str::<AnimationData>(json)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let angle_input = if use_euler_input {
+            AngleInput::Euler
+        } else {
+            AngleInput::Quaternion
+        };
+        Ok(Self::from_animation_data(&animation_data, &angle_input))
+    }
+
+    fn sample<'py>(&self, py: Python<'py>, t: f32) -> PyResult<Bound<'py, PyDict>> {
+        get_pose(py, &self.interpolator.get_sample(t))
+    }
+
+    fn frames<'py>(&self, py: Python<'py>, count: u16) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        if count < 2 {
+            return Err(PyValueError::new_err("count must be at least 2"));
+        }
+        self.interpolator
+            .get_samples(count)
+            .iter()
+            .map(|s| get_pose(py, s))
+            .collect()
+    }
+}
+
+impl PyInterpolator {
+    fn from_animation_data(animation_data: &AnimationData, angle_input: &AngleInput) -> Self {
+        let (begin_angle, end_angle) = animation_data.get_angles(angle_input);
+        Self {
+            interpolator: animation_data.get_interpolator(begin_angle, end_angle),
+        }
+    }
+}
+
+fn get_pose<'py>(py: Python<'py>, sample: &AnimationSample) -> PyResult<Bound<'py, PyDict>> {
+    let position = sample.position();
+    let quaternion = sample.quaternion();
+    let euler = sample.euler();
+
+    let pose = PyDict::new_bound(py);
+    pose.set_item("position", (position.x, position.y, position.z))?;
+    pose.set_item(
+        "quaternion",
+        (quaternion.w, quaternion.i, quaternion.j, quaternion.k),
+    )?;
+    pose.set_item("euler", (euler.x, euler.y, euler.z))?;
+    pose.set_item(
+        "quaternion_frame",
+        get_matrix_rows(sample.quaternion_frame()),
+    )?;
+    pose.set_item("euler_frame", get_matrix_rows(sample.euler_frame()))?;
+    Ok(pose)
+}
+
+fn get_matrix_rows(matrix: &nalgebra::Matrix4<f32>) -> Vec<Vec<f32>> {
+    (0..4)
+        .map(|row| (0..4).map(|column| matrix[(row, column)]).collect())
+        .collect()
+}
+
+#[pymodule]
+fn movement_interpolation(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyInterpolator>()?;
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use std::f32::consts::PI;
+
+pub struct Random {
+    state: u64,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    pub fn next_f32(&mut self, min: f32, max: f32) -> f32 {
+        let x = (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32;
+        min + (max - min) * x
+    }
+
+    pub fn next_bool(&mut self) -> bool {
+        self.next_u64() & 1 == 1
+    }
+
+    pub fn next_index(&mut self, count: usize) -> usize {
+        (self.next_u64() % count as u64) as usize
+    }
+
+    pub fn choose<T: Clone>(&mut self, values: &[T]) -> T {
+        values[self.next_index(values.len())].clone()
+    }
+
+    pub fn next_xyz(&mut self, min: f32, max: f32) -> (f32, f32, f32) {
+        (
+            self.next_f32(min, max),
+            self.next_f32(min, max),
+            self.next_f32(min, max),
+        )
+    }
+
+    pub fn next_unit_wxyz(&mut self) -> (f32, f32, f32, f32) {
+        let u = self.next_f32(0f32, 1f32);
+        let a = self.next_f32(0f32, 2f32 * PI);
+        let b = self.next_f32(0f32, 2f32 * PI);
+        let r1 = (1f32 - u).sqrt();
+        let r2 = u.sqrt();
+        (r2 * b.cos(), r1 * a.sin(), r1 * a.cos(), r2 * b.sin())
+    }
+}
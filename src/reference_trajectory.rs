@@ -0,0 +1,146 @@
+use std::fs::read_to_string;
+use std::io::{Error, ErrorKind};
+use std::path::Path;
+
+use derive_getters::Getters;
+use movement_interpolation::animation::{get_frame_quaternion, Animation};
+use nalgebra::{Matrix4, Quaternion, UnitQuaternion, Vector3};
+
+#[derive(Debug, Clone, Getters)]
+pub struct ReferencePose {
+    t: f32,
+    position: Vector3<f32>,
+    quaternion: UnitQuaternion<f32>,
+}
+
+#[derive(Debug, Clone, Getters)]
+pub struct TrajectoryErrors {
+    position: f32,
+    quaternion_rotation: f32,
+    euler_rotation: f32,
+}
+
+#[derive(Debug, Clone, Getters)]
+pub struct ReferenceTrajectory {
+    poses: Vec<ReferencePose>,
+    errors: Option<TrajectoryErrors>,
+}
+
+impl ReferenceTrajectory {
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        let content = read_to_string(path)?;
+        let mut lines = content
+            .lines()
+            .map(|l| l.trim())
+            .filter(|l| !l.is_empty() && !l.starts_with('#'));
+
+        let header = lines
+            .next()
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "Reference file is empty"))?
+            .split(',')
+            .map(|c| c.trim())
+            .collect::<Vec<_>>();
+        let column = |name: &str| header.iter().position(|c| *c == name);
+        let get_column = |name: &str| {
+            column(name).ok_or_else(|| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Reference file has no '{}' column", name),
+                )
+            })
+        };
+        let position_columns = [get_column("px")?, get_column("py")?, get_column("pz")?];
+        let quaternion_columns = [
+            get_column("qw")?,
+            get_column("qx")?,
+            get_column("qy")?,
+            get_column("qz")?,
+        ];
+        let time_column = column("t").or_else(|| column("time"));
+
+        let rows = lines
+            .map(|l| {
+                l.split(',')
+                    .map(|v| v.trim().parse::<f32>())
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| Error::new(ErrorKind::InvalidData, e))
+            })
+            .collect::<std::io::Result<Vec<_>>>()?;
+        if rows.len() < 2 {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "Reference file needs at least two poses",
+            ));
+        }
+
+        let get_value = |row: &Vec<f32>, c: usize| {
+            row.get(c).copied().ok_or_else(|| {
+                Error::new(ErrorKind::InvalidData, "Reference row has too few values")
+            })
+        };
+        let times = rows
+            .iter()
+            .enumerate()
+            .map(|(i, row)| match time_column {
+                Some(c) => get_value(row, c),
+                None => Ok(i as f32),
+            })
+            .collect::<std::io::Result<Vec<_>>>()?;
+        let (first_time, last_time) = (times[0], times[times.len() - 1]);
+
+        let poses = rows
+            .iter()
+            .zip(times)
+            .map(|(row, time)| {
+                Ok(ReferencePose {
+                    t: (time - first_time) / (last_time - first_time),
+                    position: Vector3::new(
+                        get_value(row, position_columns[0])?,
+                        get_value(row, position_columns[1])?,
+                        get_value(row, position_columns[2])?,
+                    ),
+                    quaternion: UnitQuaternion::from_quaternion(Quaternion::new(
+                        get_value(row, quaternion_columns[0])?,
+                        get_value(row, quaternion_columns[1])?,
+                        get_value(row, quaternion_columns[2])?,
+                        get_value(row, quaternion_columns[3])?,
+                    )),
+                })
+            })
+            .collect::<std::io::Result<Vec<_>>>()?;
+
+        Ok(Self {
+            poses,
+            errors: None,
+        })
+    }
+
+    pub fn get_path(&self) -> Vec<Vector3<f32>> {
+        self.poses.iter().map(|p| p.position).collect()
+    }
+
+    pub fn update_errors(&mut self, animation: &dyn Animation) {
+        let mut position = 0f32;
+        let mut quaternion_rotation = 0f32;
+        let mut euler_rotation = 0f32;
+
+        for pose in &self.poses {
+            let sample = animation.get_sample(pose.t);
+            position += (sample.position() - pose.position).norm_squared();
+            quaternion_rotation +=
+                get_rotation_error(sample.quaternion_frame(), &pose.quaternion).powi(2);
+            euler_rotation += get_rotation_error(sample.euler_frame(), &pose.quaternion).powi(2);
+        }
+
+        let count = self.poses.len() as f32;
+        self.errors = Some(TrajectoryErrors {
+            position: (position / count).sqrt(),
+            quaternion_rotation: (quaternion_rotation / count).sqrt(),
+            euler_rotation: (euler_rotation / count).sqrt(),
+        });
+    }
+}
+
+fn get_rotation_error(frame: &Matrix4<f32>, reference: &UnitQuaternion<f32>) -> f32 {
+    get_frame_quaternion(frame).angle_to(reference).to_degrees()
+}
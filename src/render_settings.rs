@@ -0,0 +1,335 @@
+use std::f32::consts::PI;
+
+use nalgebra::{Matrix4, Vector3};
+use serde::{Deserialize, Serialize};
+
+use movement_interpolation::animation_data::{AnimationData, PoseGizmo, EULER_METHOD};
+use movement_interpolation::rotation_interpolator::{LINEAR, SCLERP, SPHERICAL};
+
+pub const LIGHTS_COUNT: usize = 2;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default = "RenderSettings::new")]
+pub struct RenderSettings {
+    pub display_path: bool,
+    pub first_frame_alpha: f32,
+    pub scene_object_type: SceneObjectType,
+    pub block_geometry: BlockGeometry,
+    pub custom_mesh_path: String,
+    pub display_attitude_hud: bool,
+    pub display_pose_readout: bool,
+    pub display_rotation_axis: bool,
+    pub display_rotation_sphere: bool,
+    pub display_pose_ghosts: bool,
+    pub display_matrix_lerp_ghost: bool,
+    pub comparison_grid: bool,
+    pub comparison_methods: Vec<String>,
+    pub pose_gizmo: PoseGizmo,
+    pub shading_mode: ShadingMode,
+    pub render_mode: RenderMode,
+    pub quaternion_lighting: Lighting,
+    pub euler_lighting: Lighting,
+    pub display_angular_velocity_plot: bool,
+    pub display_euler_angles_plot: bool,
+    pub tint_gimbal_lock: bool,
+    pub animate_camera_presets: bool,
+    pub projection: Projection,
+    pub up_axis: UpAxis,
+    pub grid: Grid,
+    pub display_axes: bool,
+    pub display_axis_labels: bool,
+    pub video: VideoSettings,
+    pub live_update: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SceneObjectType {
+    #[default]
+    Block,
+    Arrow,
+    Tripod,
+    Airplane,
+    Cube,
+    Cone,
+    Torus,
+    Teapot,
+    Custom,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Lighting {
+    pub light_color: [f32; 3],
+    pub ambient: f32,
+    pub specular: f32,
+    pub shininess: f32,
+    #[serde(default = "Lighting::get_default_lights")]
+    pub lights: [Light; LIGHTS_COUNT],
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Light {
+    pub enabled: bool,
+    pub position: (f32, f32, f32),
+    pub color: [f32; 3],
+    pub intensity: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Projection {
+    pub fov: f32,
+    pub near: f32,
+    pub far: f32,
+    pub orthographic: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockGeometry {
+    pub radius: f32,
+    pub length: f32,
+    pub divisions_count: u32,
+    pub axis_colors: [[f32; 3]; 3],
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Grid {
+    pub visible: bool,
+    pub cell_size: f32,
+    pub line_color: [f32; 3],
+    pub fade_distance: f32,
+    #[serde(default)]
+    pub shadows: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VideoSettings {
+    pub width: u32,
+    pub height: u32,
+    pub fps: u32,
+    pub output: VideoOutput,
+    pub gif_scale: f32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum VideoOutput {
+    #[default]
+    PngSequence,
+    Ffmpeg,
+    Gif,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum UpAxis {
+    #[default]
+    Y,
+    Z,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum ShadingMode {
+    #[default]
+    Material,
+    Discrepancy,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum RenderMode {
+    #[default]
+    Shaded,
+    Wireframe,
+    Normals,
+}
+
+impl RenderSettings {
+    pub fn new() -> Self {
+        Self {
+            display_path: true,
+            first_frame_alpha: 0.2,
+            display_attitude_hud: true,
+            display_pose_readout: true,
+            display_pose_ghosts: true,
+            comparison_methods: vec![
+                EULER_METHOD.to_string(),
+                LINEAR.to_string(),
+                SPHERICAL.to_string(),
+                SCLERP.to_string(),
+            ],
+            quaternion_lighting: Lighting::new([1f32, 0.9, 0.75], 0.3, 1f32, 50f32),
+            euler_lighting: Lighting::new([0.75, 0.9, 1f32], 0.2, 0.4, 10f32),
+            animate_camera_presets: true,
+            display_axes: true,
+            display_axis_labels: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn set_up_axis(&mut self, up_axis: UpAxis, animation_data: &mut AnimationData) {
+        let (old_up, new_up) = (self.up_axis.get_up(), up_axis.get_up());
+        for axis in [
+            &mut animation_data.begin_rotation_axis,
+            &mut animation_data.end_rotation_axis,
+        ] {
+            if *axis == (old_up.x, old_up.y, old_up.z) {
+                *axis = (new_up.x, new_up.y, new_up.z);
+            }
+        }
+        self.up_axis = up_axis;
+    }
+}
+
+impl Lighting {
+    pub fn new(light_color: [f32; 3], ambient: f32, specular: f32, shininess: f32) -> Self {
+        Self {
+            light_color,
+            ambient,
+            specular,
+            shininess,
+            lights: Self::get_default_lights(),
+        }
+    }
+
+    pub fn get_default_lights() -> [Light; LIGHTS_COUNT] {
+        [
+            Light::new(true, (10f32, 100f32, 10f32), [1f32, 1f32, 1f32], 1f32),
+            Light::new(false, (-50f32, 30f32, -50f32), [1f32, 1f32, 1f32], 0.5),
+        ]
+    }
+}
+
+impl Light {
+    pub fn new(enabled: bool, position: (f32, f32, f32), color: [f32; 3], intensity: f32) -> Self {
+        Self {
+            enabled,
+            position,
+            color,
+            intensity,
+        }
+    }
+
+    pub fn get_radiance(&self) -> [f32; 3] {
+        if self.enabled {
+            self.color.map(|c| c * self.intensity)
+        } else {
+            [0f32; 3]
+        }
+    }
+}
+
+impl Projection {
+    pub fn new(fov: f32, near: f32, far: f32, orthographic: bool) -> Self {
+        Self {
+            fov,
+            near,
+            far,
+            orthographic,
+        }
+    }
+
+    pub fn get_matrix(&self, aspect: f32, distance: f32) -> Matrix4<f32> {
+        if self.orthographic {
+            let half_height = distance * (self.fov.to_radians() / 2f32).tan();
+            let half_width = aspect * half_height;
+            Matrix4::new_orthographic(
+                -half_width,
+                half_width,
+                -half_height,
+                half_height,
+                self.near,
+                self.far,
+            )
+        } else {
+            Matrix4::new_perspective(aspect, self.fov.to_radians(), self.near, self.far)
+        }
+    }
+}
+
+impl BlockGeometry {
+    pub fn new(radius: f32, length: f32, divisions_count: u32, axis_colors: [[f32; 3]; 3]) -> Self {
+        Self {
+            radius,
+            length,
+            divisions_count,
+            axis_colors,
+        }
+    }
+}
+
+impl Grid {
+    pub fn new(
+        visible: bool,
+        cell_size: f32,
+        line_color: [f32; 3],
+        fade_distance: f32,
+        shadows: bool,
+    ) -> Self {
+        Self {
+            visible,
+            cell_size,
+            line_color,
+            fade_distance,
+            shadows,
+        }
+    }
+}
+
+impl VideoSettings {
+    pub fn new(width: u32, height: u32, fps: u32, output: VideoOutput, gif_scale: f32) -> Self {
+        Self {
+            width,
+            height,
+            fps,
+            output,
+            gif_scale,
+        }
+    }
+}
+
+impl UpAxis {
+    pub fn get_up(&self) -> Vector3<f32> {
+        match self {
+            UpAxis::Y => Vector3::new(0f32, 1f32, 0f32),
+            UpAxis::Z => Vector3::new(0f32, 0f32, 1f32),
+        }
+    }
+
+    pub fn get_scene_matrix(&self) -> Matrix4<f32> {
+        match self {
+            UpAxis::Y => Matrix4::identity(),
+            UpAxis::Z => Matrix4::from_euler_angles(-PI / 2f32, 0f32, 0f32),
+        }
+    }
+}
+
+impl Default for Lighting {
+    fn default() -> Self {
+        Self::new([1f32, 1f32, 1f32], 0.3, 1f32, 50f32)
+    }
+}
+
+impl Default for Projection {
+    fn default() -> Self {
+        Self::new(90f32, 0.1, 100f32, false)
+    }
+}
+
+impl Default for BlockGeometry {
+    fn default() -> Self {
+        Self::new(
+            1f32,
+            5f32,
+            10,
+            [[1f32, 0f32, 0f32], [0f32, 1f32, 0f32], [0f32, 0f32, 1f32]],
+        )
+    }
+}
+
+impl Default for Grid {
+    fn default() -> Self {
+        Self::new(true, 1f32, [0.2, 0.2, 0.2], 50f32, false)
+    }
+}
+
+impl Default for VideoSettings {
+    fn default() -> Self {
+        Self::new(1920, 1080, 30, VideoOutput::PngSequence, 0.5)
+    }
+}
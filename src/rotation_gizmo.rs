@@ -0,0 +1,142 @@
+use std::f32::consts::PI;
+
+use egui::{vec2, Color32, Context, CursorIcon, Id, Order, Pos2, Sense, Stroke};
+use nalgebra::{UnitQuaternion, Vector3};
+
+use movement_interpolation::animation_data::{AngleInput, AnimationData, PoseGizmo};
+
+use crate::axes_drawer::AXIS_COLORS;
+use crate::path_handle::PathHandle;
+
+const GIZMO_RADIUS: f32 = 2.5;
+const GIZMO_SEGMENTS_COUNT: usize = 64;
+const GIZMO_PICK_DISTANCE: f32 = 8f32;
+const GIZMO_MARGIN: f32 = 4f32;
+const DRAG_ID: &str = "rotation_gizmo_drag";
+
+pub fn get_ring_points(center: &Vector3<f32>, axis: usize) -> Vec<Vector3<f32>> {
+    (0..=GIZMO_SEGMENTS_COUNT)
+        .map(|i| {
+            let a = i as f32 / GIZMO_SEGMENTS_COUNT as f32 * 2f32 * PI;
+            let mut v = Vector3::zeros();
+            v[(axis + 1) % 3] = a.cos() * GIZMO_RADIUS;
+            v[(axis + 2) % 3] = a.sin() * GIZMO_RADIUS;
+            center + v
+        })
+        .collect()
+}
+
+pub fn build_rotation_gizmo(
+    egui_ctx: &Context,
+    path_handle: &PathHandle,
+    animation_data: &mut AnimationData,
+    pose: &PoseGizmo,
+    angle_input: &AngleInput,
+) {
+    let Some((position, _)) = animation_data.get_pose(pose, angle_input) else {
+        return;
+    };
+    let rings = (0..3)
+        .map(|axis| get_ring_points(&position, axis))
+        .collect::<Vec<_>>();
+
+    for i in 0..path_handle.get_viewports_count() {
+        let Some(center) = path_handle.project(i, &position) else {
+            continue;
+        };
+        let projected_rings = rings
+            .iter()
+            .map(|r| {
+                r.iter()
+                    .map(|p| path_handle.project(i, p))
+                    .collect::<Option<Vec<_>>>()
+            })
+            .collect::<Vec<_>>();
+        let extent = projected_rings
+            .iter()
+            .flatten()
+            .flatten()
+            .map(|p| p.distance(center))
+            .fold(0f32, f32::max)
+            + GIZMO_MARGIN;
+        if extent <= GIZMO_MARGIN {
+            continue;
+        }
+
+        let drag_id = Id::new((DRAG_ID, i));
+        egui::Area::new(Id::new(("rotation_gizmo", i)))
+            .order(Order::Background)
+            .fixed_pos(center - vec2(extent, extent))
+            .show(egui_ctx, |ui| {
+                let (_, response) =
+                    ui.allocate_exact_size(vec2(2f32 * extent, 2f32 * extent), Sense::drag());
+                if response.drag_started() {
+                    if let Some(pointer) = response.interact_pointer_pos() {
+                        if let Some(grab) = get_nearest_ring_point(&projected_rings, pointer) {
+                            ui.data_mut(|d| d.insert_temp(drag_id, grab));
+                        }
+                    }
+                }
+                let grab = ui.data(|d| d.get_temp::<(usize, usize)>(drag_id));
+                if response.drag_stopped() {
+                    ui.data_mut(|d| d.remove::<(usize, usize)>(drag_id));
+                }
+
+                if let Some((axis, index)) = grab.filter(|_| response.dragged()) {
+                    let point = rings[axis][index];
+                    let tangent = Vector3::ith(axis, 1f32).cross(&(point - position));
+                    if let (Some(a), Some(b)) = (
+                        path_handle.project(i, &point),
+                        path_handle.project(i, &(point + tangent * 1e-2)),
+                    ) {
+                        let direction = b - a;
+                        if direction.length() > 1e-3 {
+                            let angle = response.drag_delta().dot(direction.normalized()) / extent;
+                            animation_data.rotate_pose(
+                                pose,
+                                angle_input,
+                                &UnitQuaternion::from_axis_angle(&Vector3::ith_axis(axis), angle),
+                            );
+                        }
+                    }
+                }
+
+                response.on_hover_cursor(CursorIcon::Grab);
+                let painter = ui.painter();
+                for (axis, ring) in projected_rings.iter().enumerate() {
+                    let Some(ring) = ring else {
+                        continue;
+                    };
+                    let color = if grab.is_some_and(|(a, _)| a == axis) {
+                        Color32::WHITE
+                    } else {
+                        let color = AXIS_COLORS[axis];
+                        Color32::from_rgb(
+                            (color[0] * 255f32) as u8,
+                            (color[1] * 255f32) as u8,
+                            (color[2] * 255f32) as u8,
+                        )
+                    };
+                    for segment in ring.windows(2) {
+                        painter.line_segment([segment[0], segment[1]], Stroke::new(2f32, color));
+                    }
+                }
+            });
+    }
+}
+
+fn get_nearest_ring_point(rings: &[Option<Vec<Pos2>>], pointer: Pos2) -> Option<(usize, usize)> {
+    rings
+        .iter()
+        .enumerate()
+        .filter_map(|(axis, ring)| {
+            ring.as_ref()?
+                .iter()
+                .enumerate()
+                .map(|(index, p)| (axis, index, p.distance(pointer)))
+                .min_by(|a, b| a.2.total_cmp(&b.2))
+        })
+        .filter(|(_, _, distance)| *distance <= GIZMO_PICK_DISTANCE)
+        .min_by(|a, b| a.2.total_cmp(&b.2))
+        .map(|(axis, index, _)| (axis, index))
+}
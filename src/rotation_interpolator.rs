@@ -0,0 +1,504 @@
+use std::fmt::Debug;
+use std::sync::{Arc, OnceLock, RwLock};
+
+use nalgebra::{Matrix3, Quaternion, Rotation3, UnitQuaternion, Vector3};
+
+use crate::animation::{
+    get_screw_motion_positions_interpolation, get_screw_positions_interpolation,
+};
+use crate::animation_data::NumericalPolicy;
+
+pub const LINEAR: &str = "Linear";
+pub const RAW_LERP: &str = "RawLerp";
+pub const NLERP: &str = "NLerp";
+pub const SPHERICAL: &str = "Spherical";
+pub const POWER: &str = "Power";
+pub const MATRIX_LERP: &str = "MatrixLerp";
+pub const SCLERP: &str = "ScLerp";
+pub const SCREW_MOTION: &str = "ScrewMotion";
+
+static REGISTRY: OnceLock<RwLock<Vec<Arc<dyn RotationInterpolator>>>> = OnceLock::new();
+
+pub trait RotationInterpolator: Debug + Send + Sync {
+    fn get_id(&self) -> &str;
+    fn get_name(&self) -> &str;
+    fn get_description(&self) -> &str;
+
+    fn interpolate(
+        &self,
+        a: &UnitQuaternion<f32>,
+        b: &UnitQuaternion<f32>,
+        t: f32,
+        policy: &NumericalPolicy,
+    ) -> UnitQuaternion<f32>;
+
+    fn allows_normalization(&self) -> bool {
+        true
+    }
+
+    fn interpolates_position(&self) -> bool {
+        false
+    }
+
+    fn interpolate_position(
+        &self,
+        _begin_position: &Vector3<f32>,
+        _begin_quaternion: &UnitQuaternion<f32>,
+        _end_position: &Vector3<f32>,
+        _end_quaternion: &UnitQuaternion<f32>,
+        _t: f32,
+        _policy: &NumericalPolicy,
+    ) -> Option<Vector3<f32>> {
+        None
+    }
+}
+
+#[derive(Debug)]
+struct Linear;
+
+#[derive(Debug)]
+struct RawLerp;
+
+#[derive(Debug)]
+struct NLerp;
+
+#[derive(Debug)]
+struct Spherical;
+
+#[derive(Debug)]
+struct Power;
+
+#[derive(Debug)]
+struct MatrixLerp;
+
+#[derive(Debug)]
+struct ScLerp;
+
+#[derive(Debug)]
+struct ScrewMotion;
+
+impl RotationInterpolator for Linear {
+    fn get_id(&self) -> &str {
+        LINEAR
+    }
+
+    fn get_name(&self) -> &str {
+        "Linear"
+    }
+
+    fn get_description(&self) -> &str {
+        "Lerp, re-unitized unless quaternion normalization is turned off"
+    }
+
+    fn interpolate(
+        &self,
+        a: &UnitQuaternion<f32>,
+        b: &UnitQuaternion<f32>,
+        t: f32,
+        _policy: &NumericalPolicy,
+    ) -> UnitQuaternion<f32> {
+        UnitQuaternion::new_unchecked(get_lerp(a, b, t))
+    }
+}
+
+impl RotationInterpolator for RawLerp {
+    fn get_id(&self) -> &str {
+        RAW_LERP
+    }
+
+    fn get_name(&self) -> &str {
+        "Raw lerp (unnormalized)"
+    }
+
+    fn get_description(&self) -> &str {
+        "Raw lerp: the quaternion shrinks mid-way, so the frame scales and shears"
+    }
+
+    fn interpolate(
+        &self,
+        a: &UnitQuaternion<f32>,
+        b: &UnitQuaternion<f32>,
+        t: f32,
+        _policy: &NumericalPolicy,
+    ) -> UnitQuaternion<f32> {
+        UnitQuaternion::new_unchecked(get_lerp(a, b, t))
+    }
+
+    fn allows_normalization(&self) -> bool {
+        false
+    }
+}
+
+impl RotationInterpolator for NLerp {
+    fn get_id(&self) -> &str {
+        NLERP
+    }
+
+    fn get_name(&self) -> &str {
+        "NLerp"
+    }
+
+    fn get_description(&self) -> &str {
+        "Normalized lerp: same path as SLERP, but angular velocity peaks mid-way"
+    }
+
+    fn interpolate(
+        &self,
+        a: &UnitQuaternion<f32>,
+        b: &UnitQuaternion<f32>,
+        t: f32,
+        policy: &NumericalPolicy,
+    ) -> UnitQuaternion<f32> {
+        let r = get_lerp(a, b, t);
+        if r.norm_squared() < policy.zero_norm_epsilon {
+            *a
+        } else {
+            UnitQuaternion::from_quaternion(r)
+        }
+    }
+}
+
+impl RotationInterpolator for Spherical {
+    fn get_id(&self) -> &str {
+        SPHERICAL
+    }
+
+    fn get_name(&self) -> &str {
+        "Spherical"
+    }
+
+    fn get_description(&self) -> &str {
+        "SLERP: constant angular velocity along the shortest arc"
+    }
+
+    fn interpolate(
+        &self,
+        a: &UnitQuaternion<f32>,
+        b: &UnitQuaternion<f32>,
+        t: f32,
+        policy: &NumericalPolicy,
+    ) -> UnitQuaternion<f32> {
+        UnitQuaternion::new_unchecked(get_slerp(a, b, t, policy))
+    }
+}
+
+impl RotationInterpolator for Power {
+    fn get_id(&self) -> &str {
+        POWER
+    }
+
+    fn get_name(&self) -> &str {
+        "Power (q0 · (q0⁻¹q1)ᵗ)"
+    }
+
+    fn get_description(&self) -> &str {
+        "Exp/log map: the relative rotation raised to the power t, the same curve as SLERP"
+    }
+
+    fn interpolate(
+        &self,
+        a: &UnitQuaternion<f32>,
+        b: &UnitQuaternion<f32>,
+        t: f32,
+        policy: &NumericalPolicy,
+    ) -> UnitQuaternion<f32> {
+        UnitQuaternion::new_unchecked(get_power(a, b, t, policy))
+    }
+}
+
+impl RotationInterpolator for MatrixLerp {
+    fn get_id(&self) -> &str {
+        MATRIX_LERP
+    }
+
+    fn get_name(&self) -> &str {
+        "Matrix lerp + Gram-Schmidt"
+    }
+
+    fn get_description(&self) -> &str {
+        "Element-wise lerp of rotation matrices, re-orthonormalized: uneven speed and a skewed path"
+    }
+
+    fn interpolate(
+        &self,
+        a: &UnitQuaternion<f32>,
+        b: &UnitQuaternion<f32>,
+        t: f32,
+        policy: &NumericalPolicy,
+    ) -> UnitQuaternion<f32> {
+        let m = (1f32 - t) * a.to_rotation_matrix().into_inner()
+            + t * b.to_rotation_matrix().into_inner();
+        get_gram_schmidt(&m, policy)
+            .map(|r| UnitQuaternion::from_rotation_matrix(&r))
+            .unwrap_or(*a)
+    }
+}
+
+impl RotationInterpolator for ScLerp {
+    fn get_id(&self) -> &str {
+        SCLERP
+    }
+
+    fn get_name(&self) -> &str {
+        "ScLERP (dual quaternion)"
+    }
+
+    fn get_description(&self) -> &str {
+        "ScLERP: rotation and translation follow one screw motion"
+    }
+
+    fn interpolate(
+        &self,
+        a: &UnitQuaternion<f32>,
+        b: &UnitQuaternion<f32>,
+        t: f32,
+        policy: &NumericalPolicy,
+    ) -> UnitQuaternion<f32> {
+        UnitQuaternion::new_unchecked(get_slerp(a, b, t, policy))
+    }
+
+    fn interpolate_position(
+        &self,
+        begin_position: &Vector3<f32>,
+        begin_quaternion: &UnitQuaternion<f32>,
+        end_position: &Vector3<f32>,
+        end_quaternion: &UnitQuaternion<f32>,
+        t: f32,
+        policy: &NumericalPolicy,
+    ) -> Option<Vector3<f32>> {
+        Some(get_screw_positions_interpolation(
+            begin_position,
+            begin_quaternion,
+            end_position,
+            end_quaternion,
+            t,
+            policy,
+        ))
+    }
+
+    fn interpolates_position(&self) -> bool {
+        true
+    }
+}
+
+impl RotationInterpolator for ScrewMotion {
+    fn get_id(&self) -> &str {
+        SCREW_MOTION
+    }
+
+    fn get_name(&self) -> &str {
+        "Screw motion (SE(3) exp map)"
+    }
+
+    fn get_description(&self) -> &str {
+        "SE(3) exp map: t times the log of the relative transform, the same positions as ScLERP"
+    }
+
+    fn interpolate(
+        &self,
+        a: &UnitQuaternion<f32>,
+        b: &UnitQuaternion<f32>,
+        t: f32,
+        policy: &NumericalPolicy,
+    ) -> UnitQuaternion<f32> {
+        UnitQuaternion::new_unchecked(get_slerp(a, b, t, policy))
+    }
+
+    fn interpolate_position(
+        &self,
+        begin_position: &Vector3<f32>,
+        begin_quaternion: &UnitQuaternion<f32>,
+        end_position: &Vector3<f32>,
+        end_quaternion: &UnitQuaternion<f32>,
+        t: f32,
+        policy: &NumericalPolicy,
+    ) -> Option<Vector3<f32>> {
+        Some(get_screw_motion_positions_interpolation(
+            begin_position,
+            begin_quaternion,
+            end_position,
+            end_quaternion,
+            t,
+            policy,
+        ))
+    }
+
+    fn interpolates_position(&self) -> bool {
+        true
+    }
+}
+
+pub fn get_rotation_interpolators() -> Vec<Arc<dyn RotationInterpolator>> {
+    get_registry().read().unwrap().clone()
+}
+
+pub fn get_rotation_interpolator(id: &str) -> Option<Arc<dyn RotationInterpolator>> {
+    get_registry()
+        .read()
+        .unwrap()
+        .iter()
+        .find(|i| i.get_id() == id)
+        .cloned()
+}
+
+pub fn register_rotation_interpolator(interpolator: Arc<dyn RotationInterpolator>) {
+    let mut registry = get_registry().write().unwrap();
+    registry.retain(|i| i.get_id() != interpolator.get_id());
+    registry.push(interpolator);
+}
+
+fn get_registry() -> &'static RwLock<Vec<Arc<dyn RotationInterpolator>>> {
+    REGISTRY.get_or_init(|| {
+        RwLock::new(vec![
+            Arc::new(Linear),
+            Arc::new(RawLerp),
+            Arc::new(NLerp),
+            Arc::new(Spherical),
+            Arc::new(Power),
+            Arc::new(MatrixLerp),
+            Arc::new(ScLerp),
+            Arc::new(ScrewMotion),
+        ])
+    })
+}
+
+fn get_lerp(a: &UnitQuaternion<f32>, b: &UnitQuaternion<f32>, t: f32) -> Quaternion<f32> {
+    (1f32 - t) * a.quaternion() + t * b.quaternion()
+}
+
+fn get_slerp(
+    a: &UnitQuaternion<f32>,
+    b: &UnitQuaternion<f32>,
+    t: f32,
+    policy: &NumericalPolicy,
+) -> Quaternion<f32> {
+    let cos = a.dot(b).clamp(-1f32, 1f32);
+    let theta = cos.acos();
+    let theta_sin = theta.sin();
+    let (s1, s2) = if theta_sin.abs() < policy.theta_sin_epsilon {
+        (1f32 - t, t)
+    } else {
+        (
+            ((1f32 - t) * theta).sin() / theta_sin,
+            (t * theta).sin() / theta_sin,
+        )
+    };
+    s1 * a.into_inner() + s2 * b.into_inner()
+}
+
+fn get_power(
+    a: &UnitQuaternion<f32>,
+    b: &UnitQuaternion<f32>,
+    t: f32,
+    policy: &NumericalPolicy,
+) -> Quaternion<f32> {
+    let relative = a.quaternion().conjugate() * b.quaternion();
+    let axis_sin = relative.imag().norm();
+    if axis_sin < policy.theta_sin_epsilon {
+        return a.into_inner();
+    }
+    let half_angle = axis_sin.atan2(relative.w);
+    let log = Quaternion::from_imag(relative.imag() / axis_sin * half_angle);
+    a.quaternion() * (log * t).exp()
+}
+
+fn get_gram_schmidt(m: &Matrix3<f32>, policy: &NumericalPolicy) -> Option<Rotation3<f32>> {
+    let x = m.column(0).into_owned();
+    if x.norm_squared() < policy.zero_norm_epsilon {
+        return None;
+    }
+    let x = x.normalize();
+    let y = m.column(1) - x * x.dot(&m.column(1));
+    if y.norm_squared() < policy.zero_norm_epsilon {
+        return None;
+    }
+    let y = y.normalize();
+    Some(Rotation3::from_matrix_unchecked(Matrix3::from_columns(&[
+        x,
+        y,
+        x.cross(&y),
+    ])))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f32 = 1e-4;
+
+    fn get_ends() -> (UnitQuaternion<f32>, UnitQuaternion<f32>) {
+        (
+            UnitQuaternion::from_euler_angles(0.3, -0.2, 0.1),
+            UnitQuaternion::from_euler_angles(-0.4, 1.1, 0.6),
+        )
+    }
+
+    fn get_quaternion_distance(a: &Quaternion<f32>, b: &Quaternion<f32>) -> f32 {
+        (a - b).norm().min((a + b).norm())
+    }
+
+    #[test]
+    fn interpolators_hit_endpoints() {
+        let (a, b) = get_ends();
+        let policy = NumericalPolicy::default();
+        for interpolator in get_rotation_interpolators() {
+            let begin = interpolator.interpolate(&a, &b, 0f32, &policy);
+            let end = interpolator.interpolate(&a, &b, 1f32, &policy);
+
+            let id = interpolator.get_id();
+            assert!(
+                get_quaternion_distance(begin.quaternion(), a.quaternion()) < EPSILON,
+                "{id}"
+            );
+            assert!(
+                get_quaternion_distance(end.quaternion(), b.quaternion()) < EPSILON,
+                "{id}"
+            );
+        }
+    }
+
+    #[test]
+    fn interpolator_midpoints_lie_on_the_shortest_arc() {
+        let (a, b) = get_ends();
+        let policy = NumericalPolicy::default();
+        let expected = a.slerp(&b, 0.5);
+        for interpolator in get_rotation_interpolators() {
+            let midpoint = UnitQuaternion::new_normalize(
+                interpolator.interpolate(&a, &b, 0.5, &policy).into_inner(),
+            );
+
+            let id = interpolator.get_id();
+            if id == MATRIX_LERP {
+                assert!(midpoint.angle_to(&a) < a.angle_to(&b), "{id}");
+                assert!(midpoint.angle_to(&b) < a.angle_to(&b), "{id}");
+            } else {
+                assert!(
+                    get_quaternion_distance(midpoint.quaternion(), expected.quaternion()) < EPSILON,
+                    "{id}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn power_midpoint_matches_slerp() {
+        let (a, b) = get_ends();
+        let policy = NumericalPolicy::default();
+
+        let power = get_power(&a, &b, 0.5, &policy);
+        let slerp = get_slerp(&a, &b, 0.5, &policy);
+        assert!(get_quaternion_distance(&power, &slerp) < EPSILON);
+    }
+
+    #[test]
+    fn gram_schmidt_returns_a_proper_rotation() {
+        let (a, b) = get_ends();
+        let policy = NumericalPolicy::default();
+        let m =
+            0.3 * a.to_rotation_matrix().into_inner() + 0.7 * b.to_rotation_matrix().into_inner();
+
+        let r = get_gram_schmidt(&m, &policy).unwrap().into_inner();
+        assert!((r.transpose() * r - Matrix3::identity()).norm() < EPSILON);
+        assert!((r.determinant() - 1f32).abs() < EPSILON);
+    }
+}
@@ -0,0 +1,104 @@
+use std::f32::consts::PI;
+
+use egui::{vec2, Align2, Color32, FontId, Painter, Pos2, Stroke};
+use nalgebra::{Matrix4, Rotation3, Vector3};
+
+use crate::render_settings::UpAxis;
+use movement_interpolation::animation::{get_frame_quaternion, Animation};
+
+const TRACE_SAMPLES_COUNT: u32 = 100;
+const CIRCLE_SAMPLES_COUNT: u32 = 64;
+const QUATERNION_TRACE_COLOR: Color32 = Color32::from_rgb(255, 170, 60);
+const EULER_TRACE_COLOR: Color32 = Color32::from_rgb(80, 200, 255);
+const BACK_SIDE_FACTOR: f32 = 0.35;
+
+pub fn get_rotation_traces(animation: &dyn Animation, up_axis: &UpAxis) -> [Vec<Vector3<f32>>; 2] {
+    let samples = (0..=TRACE_SAMPLES_COUNT)
+        .map(|i| animation.get_sample(i as f32 / TRACE_SAMPLES_COUNT as f32))
+        .collect::<Vec<_>>();
+    [
+        samples
+            .iter()
+            .map(|s| get_body_direction(s.quaternion_frame(), up_axis))
+            .collect(),
+        samples
+            .iter()
+            .map(|s| get_body_direction(s.euler_frame(), up_axis))
+            .collect(),
+    ]
+}
+
+pub fn get_body_direction(model: &Matrix4<f32>, up_axis: &UpAxis) -> Vector3<f32> {
+    up_axis
+        .get_scene_matrix()
+        .transform_vector(&(get_frame_quaternion(model) * Vector3::new(0f32, 0f32, -1f32)))
+}
+
+pub fn draw_rotation_sphere(
+    painter: &Painter,
+    center: Pos2,
+    radius: f32,
+    traces: &[Vec<Vector3<f32>>; 2],
+    current: &[Vector3<f32>; 2],
+) {
+    let view = Rotation3::from_axis_angle(&Vector3::x_axis(), 0.35f32)
+        * Rotation3::from_axis_angle(&Vector3::y_axis(), -0.6f32);
+    let project = |v: &Vector3<f32>| {
+        let p = view * v;
+        (center + vec2(p.x, -p.y) * radius, p.z >= 0f32)
+    };
+    let draw_polyline = |points: &[Vector3<f32>], width: f32, color: Color32| {
+        for segment in points.windows(2) {
+            let (a, front) = project(&segment[0]);
+            let (b, _) = project(&segment[1]);
+            let color = if front {
+                color
+            } else {
+                color.gamma_multiply(BACK_SIDE_FACTOR)
+            };
+            painter.line_segment([a, b], Stroke::new(width, color));
+        }
+    };
+
+    painter.circle_filled(center, radius, Color32::from_black_alpha(160));
+    for axes in [(0, 2), (0, 1), (1, 2)] {
+        let circle = (0..=CIRCLE_SAMPLES_COUNT)
+            .map(|i| {
+                let a = i as f32 / CIRCLE_SAMPLES_COUNT as f32 * 2f32 * PI;
+                let mut v = Vector3::zeros();
+                v[axes.0] = a.cos();
+                v[axes.1] = a.sin();
+                v
+            })
+            .collect::<Vec<_>>();
+        draw_polyline(&circle, 1f32, Color32::GRAY);
+    }
+    painter.circle_stroke(center, radius, Stroke::new(1f32, Color32::GRAY));
+
+    for ((trace, current), color) in traces
+        .iter()
+        .zip(current)
+        .zip([QUATERNION_TRACE_COLOR, EULER_TRACE_COLOR])
+    {
+        draw_polyline(trace, 2f32, color);
+        painter.circle_filled(project(current).0, 4f32, color);
+    }
+
+    for (text, color, x, align) in [
+        (
+            "Quaternion",
+            QUATERNION_TRACE_COLOR,
+            -radius,
+            Align2::LEFT_TOP,
+        ),
+        ("Euler", EULER_TRACE_COLOR, radius, Align2::RIGHT_TOP),
+    ] {
+        painter.text(
+            center + vec2(x, radius + 4f32),
+            align,
+            text,
+            FontId::monospace(12f32),
+            color,
+        );
+    }
+}
@@ -0,0 +1,11 @@
+use derive_new::new;
+use glium::DrawParameters;
+use nalgebra::{Matrix4, Vector3};
+
+#[derive(Clone, Copy, new)]
+pub struct SceneView<'a> {
+    pub perspective: &'a Matrix4<f32>,
+    pub view: &'a Matrix4<f32>,
+    pub camera_position: Vector3<f32>,
+    pub drawing_parameters: &'a DrawParameters<'a>,
+}
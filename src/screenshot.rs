@@ -0,0 +1,48 @@
+use std::path::PathBuf;
+
+use glium::glutin::surface::WindowSurface;
+use glium::texture::RawImage2d;
+use glium::Display;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScreenshotRegion {
+    Window,
+    Pane(usize),
+}
+
+pub fn save_screenshot(
+    display: &Display<WindowSurface>,
+    cell: Option<&egui::Rect>,
+    name: &str,
+) -> std::io::Result<PathBuf> {
+    let raw: RawImage2d<u8> = display
+        .read_front_buffer()
+        .map_err(|e| std::io::Error::other(format!("{:?}", e)))?;
+    let mut image =
+        image::RgbaImage::from_raw(raw.width, raw.height, raw.data.into_owned()).unwrap();
+    image::imageops::flip_vertical_in_place(&mut image);
+    image.pixels_mut().for_each(|p| p[3] = u8::MAX);
+
+    let image = match cell {
+        Some(cell) => {
+            let (width, height) = (image.width() as f32, image.height() as f32);
+            let left = (cell.min.x * width) as u32;
+            let top = (cell.min.y * height) as u32;
+            let right = (cell.max.x * width) as u32;
+            let bottom = (cell.max.y * height) as u32;
+            image::imageops::crop_imm(
+                &image,
+                left,
+                top,
+                (right - left).max(1),
+                (bottom - top).max(1),
+            )
+            .to_image()
+        }
+        None => image,
+    };
+
+    let path = PathBuf::from(name);
+    image.save(&path).map_err(std::io::Error::other)?;
+    Ok(path)
+}
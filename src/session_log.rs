@@ -0,0 +1,116 @@
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use movement_interpolation::animation_data::{AngleInput, AnimationData};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CameraState {
+    pub angle: (f32, f32),
+    pub distant: f32,
+    pub target: (f32, f32, f32),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SessionAction {
+    SetParameters(Box<AnimationData>),
+    Run(AngleInput),
+    MoveCamera(usize, CameraState),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionEntry {
+    time: f64,
+    action: SessionAction,
+}
+
+pub struct SessionLog {
+    start_time: DateTime<Local>,
+    entries: Vec<SessionEntry>,
+    logged_animation_data: Option<AnimationData>,
+    logged_cameras: Vec<Option<CameraState>>,
+}
+
+pub struct SessionReplay {
+    start_time: DateTime<Local>,
+    entries: Vec<SessionEntry>,
+    next: usize,
+}
+
+impl SessionLog {
+    pub fn new(start_time: DateTime<Local>) -> Self {
+        Self {
+            start_time,
+            entries: Vec::new(),
+            logged_animation_data: None,
+            logged_cameras: Vec::new(),
+        }
+    }
+
+    pub fn record(&mut self, time: DateTime<Local>, action: SessionAction) {
+        self.entries.push(SessionEntry {
+            time: get_seconds_between(self.start_time, time),
+            action,
+        });
+    }
+
+    pub fn record_parameters(&mut self, time: DateTime<Local>, animation_data: &AnimationData) {
+        if self.logged_animation_data.as_ref() != Some(animation_data) {
+            self.logged_animation_data = Some(animation_data.clone());
+            self.record(
+                time,
+                SessionAction::SetParameters(Box::new(animation_data.clone())),
+            );
+        }
+    }
+
+    pub fn record_camera(&mut self, time: DateTime<Local>, index: usize, state: CameraState) {
+        if self.logged_cameras.len() <= index {
+            self.logged_cameras.resize(index + 1, None);
+        }
+        if self.logged_cameras[index].as_ref() != Some(&state) {
+            self.logged_cameras[index] = Some(state.clone());
+            self.record(time, SessionAction::MoveCamera(index, state));
+        }
+    }
+
+    pub fn export(&self, path: &Path) -> std::io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(&mut writer, &self.entries)?;
+        writer.flush()
+    }
+}
+
+impl SessionReplay {
+    pub fn load(path: &Path, start_time: DateTime<Local>) -> std::io::Result<Self> {
+        let entries: Vec<SessionEntry> =
+            serde_json::from_reader(BufReader::new(File::open(path)?))?;
+        Ok(Self {
+            start_time,
+            entries,
+            next: 0,
+        })
+    }
+
+    pub fn poll(&mut self, time: DateTime<Local>) -> Vec<SessionAction> {
+        let elapsed = get_seconds_between(self.start_time, time);
+        let due = self.entries[self.next..]
+            .iter()
+            .take_while(|e| e.time <= elapsed)
+            .map(|e| e.action.clone())
+            .collect::<Vec<_>>();
+        self.next += due.len();
+        due
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.entries.len()
+    }
+}
+
+fn get_seconds_between(begin: DateTime<Local>, end: DateTime<Local>) -> f64 {
+    (end - begin).num_microseconds().unwrap_or(0) as f64 / 1_000_000.0
+}
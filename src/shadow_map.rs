@@ -0,0 +1,136 @@
+use std::cell::Cell;
+
+use derive_getters::Getters;
+use glium::framebuffer::SimpleFrameBuffer;
+use glium::glutin::surface::WindowSurface;
+use glium::texture::DepthTexture2d;
+use glium::{uniform, Display, DrawParameters, Program, Surface, VertexBuffer};
+use nalgebra::{Matrix4, Point3, Vector3};
+
+use crate::mesh::Mesh;
+use crate::render_settings::{Lighting, UpAxis};
+use crate::vertex::InstanceData;
+
+const SHADOW_MAP_SIZE: u32 = 2048;
+
+#[derive(Getters)]
+pub struct ShadowMap {
+    #[getter(skip)]
+    program: Program,
+    depth_texture: DepthTexture2d,
+    #[getter(skip)]
+    light_matrix: Cell<Option<Matrix4<f32>>>,
+}
+
+impl ShadowMap {
+    pub fn new(display: &Display<WindowSurface>) -> Self {
+        let vertex_shader_src = r#"
+            #version 410 core
+
+            in vec3 position;
+            in mat4 model;
+
+            uniform mat4 light_matrix;
+
+            void main() {
+                gl_Position = light_matrix * model * vec4(position, 1.0);
+            }
+        "#;
+
+        let fragment_shader_src = r#"
+            #version 410 core
+
+            void main() {
+            }
+        "#;
+
+        Self {
+            program: Program::from_source(display, vertex_shader_src, fragment_shader_src, None)
+                .unwrap(),
+            depth_texture: DepthTexture2d::empty(display, SHADOW_MAP_SIZE, SHADOW_MAP_SIZE)
+                .unwrap(),
+            light_matrix: Cell::new(None),
+        }
+    }
+
+    pub fn get_light_matrix(&self) -> Option<Matrix4<f32>> {
+        self.light_matrix.get()
+    }
+
+    pub fn render(
+        &self,
+        display: &Display<WindowSurface>,
+        mesh: &Mesh,
+        instances: &[InstanceData],
+        up_axis: &UpAxis,
+        lighting: &Lighting,
+    ) {
+        let scene = up_axis.get_scene_matrix();
+        let light_matrix = lighting.lights.iter().find(|l| l.enabled).and_then(|l| {
+            get_light_matrix(
+                &Vector3::new(l.position.0, l.position.1, l.position.2),
+                &instances
+                    .iter()
+                    .map(|i| (scene * Matrix4::from(*i.model())).column(3).xyz())
+                    .collect::<Vec<_>>(),
+                *mesh.radius(),
+            )
+        });
+        self.light_matrix.set(light_matrix);
+        let Some(light_matrix) = light_matrix else {
+            return;
+        };
+
+        let mut target = SimpleFrameBuffer::depth_only(display, &self.depth_texture).unwrap();
+        target.clear_depth(1.0);
+
+        let instance_buffer = VertexBuffer::dynamic(display, instances).unwrap();
+        target
+            .draw(
+                (mesh.vertices(), instance_buffer.per_instance().unwrap()),
+                mesh.indices(),
+                &self.program,
+                &uniform! {
+                    light_matrix: (light_matrix * scene).data.0,
+                },
+                &DrawParameters {
+                    depth: glium::Depth {
+                        test: glium::draw_parameters::DepthTest::IfLess,
+                        write: true,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+    }
+}
+
+fn get_light_matrix(
+    light_position: &Vector3<f32>,
+    positions: &[Vector3<f32>],
+    radius: f32,
+) -> Option<Matrix4<f32>> {
+    if positions.is_empty() {
+        return None;
+    }
+
+    let center = positions.iter().sum::<Vector3<f32>>() / positions.len() as f32;
+    let extent = positions
+        .iter()
+        .map(|p| (p - center).norm())
+        .fold(0f32, f32::max)
+        + radius;
+    let direction = (light_position - center).try_normalize(0f32)?;
+    let up = if direction.y.abs() > 0.99 {
+        Vector3::x()
+    } else {
+        Vector3::y()
+    };
+
+    let eye = center + direction * (extent + 1f32);
+    let depth = 2f32 * extent + 1f32 + (center.y.abs() + extent) / direction.y.abs().max(0.1);
+    let view = Matrix4::look_at_rh(&Point3::from(eye), &Point3::from(center), &up);
+    let projection = Matrix4::new_orthographic(-extent, extent, -extent, extent, 0f32, depth);
+    Some(projection * view)
+}
@@ -0,0 +1,147 @@
+use egui::{Button, Context, Grid};
+use serde::{Deserialize, Serialize};
+use winit::keyboard::Key;
+
+use crate::camera::CameraPreset;
+use crate::config_dir::{get_config_path, load_json, save_json};
+
+const SHORTCUTS_FILE: &str = "shortcuts.json";
+pub const PRESET_SHORTCUTS_COUNT: usize = 9;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ShortcutAction {
+    Run,
+    ToggleCamera,
+    PlayPause,
+    StepBackward,
+    StepForward,
+    Screenshot,
+    Camera(CameraPreset),
+    Preset(usize),
+}
+
+impl ShortcutAction {
+    pub fn get_name(&self) -> String {
+        match self {
+            ShortcutAction::Run => "Run animation".to_string(),
+            ShortcutAction::ToggleCamera => "Toggle camera orbit".to_string(),
+            ShortcutAction::PlayPause => "Play/pause".to_string(),
+            ShortcutAction::StepBackward => "Step backward".to_string(),
+            ShortcutAction::StepForward => "Step forward".to_string(),
+            ShortcutAction::Screenshot => "Screenshot".to_string(),
+            ShortcutAction::Camera(preset) => format!("{:?} camera", preset),
+            ShortcutAction::Preset(i) => format!("Preset {}", i + 1),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Shortcuts {
+    pub bindings: Vec<(ShortcutAction, String)>,
+}
+
+impl Shortcuts {
+    pub fn load() -> std::io::Result<Self> {
+        let mut shortcuts: Self = load_json(&get_config_path(SHORTCUTS_FILE)?)?;
+        for (action, key) in Self::default().bindings {
+            if !shortcuts.bindings.iter().any(|(a, _)| *a == action) {
+                shortcuts.bindings.push((action, key));
+            }
+        }
+        Ok(shortcuts)
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        save_json(&get_config_path(SHORTCUTS_FILE)?, self)
+    }
+
+    pub fn get_action(&self, key: &Key) -> Option<ShortcutAction> {
+        let name = get_key_name(key)?;
+        self.bindings
+            .iter()
+            .find(|(_, k)| *k == name)
+            .map(|(a, _)| a.clone())
+    }
+
+    pub fn bind(&mut self, action: &ShortcutAction, key: &Key) {
+        let Some(name) = get_key_name(key) else {
+            return;
+        };
+        for (a, k) in self.bindings.iter_mut() {
+            if a == action {
+                *k = name.clone();
+            } else if *k == name {
+                k.clear();
+            }
+        }
+    }
+}
+
+impl Default for Shortcuts {
+    fn default() -> Self {
+        let mut bindings = vec![
+            (ShortcutAction::Run, "r".to_string()),
+            (ShortcutAction::ToggleCamera, "c".to_string()),
+            (ShortcutAction::PlayPause, "Space".to_string()),
+            (ShortcutAction::StepBackward, ",".to_string()),
+            (ShortcutAction::StepForward, ".".to_string()),
+            (ShortcutAction::Screenshot, "F12".to_string()),
+            (ShortcutAction::Camera(CameraPreset::Front), "1".to_string()),
+            (ShortcutAction::Camera(CameraPreset::Side), "3".to_string()),
+            (ShortcutAction::Camera(CameraPreset::Top), "7".to_string()),
+            (
+                ShortcutAction::Camera(CameraPreset::Isometric),
+                "5".to_string(),
+            ),
+        ];
+        bindings.extend(
+            (0..PRESET_SHORTCUTS_COUNT).map(|i| (ShortcutAction::Preset(i), format!("F{}", i + 1))),
+        );
+        Self { bindings }
+    }
+}
+
+pub fn get_key_name(key: &Key) -> Option<String> {
+    match key {
+        Key::Character(c) => Some(c.to_lowercase()),
+        Key::Named(named) => Some(format!("{:?}", named)),
+        _ => None,
+    }
+}
+
+pub fn build_shortcuts_window(
+    egui_ctx: &Context,
+    shortcuts: &mut Shortcuts,
+    capture: &mut Option<ShortcutAction>,
+    open: &mut bool,
+) {
+    egui::Window::new("Shortcuts")
+        .open(open)
+        .resizable(false)
+        .show(egui_ctx, |ui| {
+            Grid::new("shortcuts_grid").striped(true).show(ui, |ui| {
+                for (action, key) in &shortcuts.bindings {
+                    ui.label(action.get_name());
+                    let text = if capture.as_ref() == Some(action) {
+                        "Press a key…"
+                    } else if key.is_empty() {
+                        "—"
+                    } else {
+                        key
+                    };
+                    if ui
+                        .add(Button::new(text).min_size([90f32, 0f32].into()))
+                        .clicked()
+                    {
+                        *capture = Some(action.clone());
+                    }
+                    ui.end_row();
+                }
+            });
+            if ui.button("Restore defaults").clicked() {
+                *shortcuts = Shortcuts::default();
+                *capture = None;
+            }
+        });
+}
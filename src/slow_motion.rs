@@ -0,0 +1,92 @@
+use crate::animation::{get_frame_quaternion, Interpolator};
+
+const SLOW_MOTION_SAMPLES_COUNT: u16 = 200;
+const MIN_PLAYBACK_SPEED: f32 = 0.25;
+const MAX_PLAYBACK_SPEED: f32 = 2f32;
+const GIMBAL_LOCK_MARGIN: f32 = 0.3;
+const KEYFRAME_RADIUS: f32 = 0.05;
+const FOCUS_RADIUS: f32 = 0.1;
+
+#[derive(Debug, Clone)]
+pub struct SlowMotionProfile {
+    speeds: Vec<f32>,
+}
+
+impl SlowMotionProfile {
+    pub fn analyze(interpolator: &Interpolator) -> Self {
+        let samples = interpolator.get_samples(SLOW_MOTION_SAMPLES_COUNT);
+        let xs = (0..samples.len())
+            .map(|s| s as f32 / (samples.len() - 1) as f32)
+            .collect::<Vec<_>>();
+
+        let divergences = samples
+            .iter()
+            .map(|s| {
+                get_frame_quaternion(s.quaternion_frame())
+                    .angle_to(&get_frame_quaternion(s.euler_frame()))
+            })
+            .collect::<Vec<_>>();
+        let max_divergence = divergences.iter().cloned().fold(0f32, f32::max);
+
+        let middle_axis = interpolator.euler_order().get_middle_axis();
+        let gimbal_locks = samples
+            .iter()
+            .map(|s| (1f32 - s.euler()[middle_axis].cos().abs() / GIMBAL_LOCK_MARGIN).max(0f32))
+            .collect::<Vec<_>>();
+
+        let keyframes = [0f32, 1f32]
+            .into_iter()
+            .chain(interpolator.waypoints().iter().map(|w| {
+                samples
+                    .iter()
+                    .zip(&xs)
+                    .min_by(|(a, _), (b, _)| {
+                        (a.position() - w)
+                            .norm()
+                            .total_cmp(&(b.position() - w).norm())
+                    })
+                    .map(|(_, x)| *x)
+                    .unwrap()
+            }))
+            .collect::<Vec<_>>();
+
+        let speeds = xs
+            .iter()
+            .enumerate()
+            .map(|(i, x)| {
+                let divergence = if max_divergence > 1e-3 {
+                    divergences[i] / max_divergence
+                } else {
+                    0f32
+                };
+                let keyframe = keyframes
+                    .iter()
+                    .map(|k| (-((x - k) / KEYFRAME_RADIUS).powi(2)).exp())
+                    .fold(0f32, f32::max);
+                let interest = divergence.max(gimbal_locks[i]).max(keyframe);
+                MAX_PLAYBACK_SPEED - (MAX_PLAYBACK_SPEED - MIN_PLAYBACK_SPEED) * interest
+            })
+            .collect();
+
+        Self { speeds }
+    }
+
+    pub fn focus(x: f32) -> Self {
+        let speeds = (0..SLOW_MOTION_SAMPLES_COUNT)
+            .map(|i| {
+                let t = i as f32 / (SLOW_MOTION_SAMPLES_COUNT - 1) as f32;
+                let interest = (-((t - x) / FOCUS_RADIUS).powi(2)).exp();
+                1f32 - (1f32 - MIN_PLAYBACK_SPEED) * interest
+            })
+            .collect();
+
+        Self { speeds }
+    }
+
+    pub fn get_speed(&self, x: f32) -> f32 {
+        let position = x.clamp(0f32, 1f32) * (self.speeds.len() - 1) as f32;
+        let index = (position.floor() as usize).min(self.speeds.len() - 2);
+        let t = position - index as f32;
+        (1f32 - t) * self.speeds[index] + t * self.speeds[index + 1]
+    }
+}
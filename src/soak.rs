@@ -0,0 +1,286 @@
+use std::fs::OpenOptions;
+use std::io::{BufWriter, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+
+use movement_interpolation::animation::{
+    AnimationSample, EULER_METHOD_INDEX, QUATERNION_METHOD_INDEX,
+};
+use movement_interpolation::animation_data::{
+    AngleInput, AnimationData, CubicBezier, Easing, EulerOrder, EulerRepresentation, FrameSpacing,
+    Obstacle, ObstacleShape, PositionInterpolationType, QuaternionNormalizationPolicy,
+    TimeAccumulation,
+};
+use movement_interpolation::random::Random;
+use movement_interpolation::rotation_interpolator::get_rotation_interpolators;
+use movement_interpolation::slow_motion::SlowMotionProfile;
+use serde::Serialize;
+
+use crate::headless::HeadlessConfig;
+use crate::path_analysis::PathWarnings;
+use crate::render_settings::UpAxis;
+
+const SOAK_SAMPLES_COUNT: u16 = 64;
+const SOAK_STEPS_COUNT: u16 = 32;
+const SOAK_PROGRESS_INTERVAL: u64 = 1000;
+const NORM_TOLERANCE: f32 = 1e-3;
+
+#[derive(Debug, Clone, Serialize)]
+struct SoakFailure {
+    iteration: u64,
+    issue: String,
+    config: HeadlessConfig,
+}
+
+pub fn run_soak(seed: u64, iterations: Option<u64>, log_path: &Path) -> std::io::Result<()> {
+    let mut log = BufWriter::new(
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(log_path)?,
+    );
+    let mut random = Random::new(seed);
+    let mut failures_count = 0u64;
+
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let mut iteration = 0u64;
+    while iterations.is_none_or(|n| iteration < n) {
+        let config = get_random_config(&mut random);
+
+        let issue = match panic::catch_unwind(AssertUnwindSafe(|| check_config(&config))) {
+            Ok(issue) => issue,
+            Err(payload) => Some(format!(
+                "panic: {}",
+                payload
+                    .downcast_ref::<&str>()
+                    .map(|s| s.to_string())
+                    .or_else(|| payload.downcast_ref::<String>().cloned())
+                    .unwrap_or_default()
+            )),
+        };
+
+        if let Some(issue) = issue {
+            failures_count += 1;
+            serde_json::to_writer(
+                &mut log,
+                &SoakFailure {
+                    iteration,
+                    issue,
+                    config,
+                },
+            )?;
+            writeln!(log)?;
+            log.flush()?;
+        }
+
+        iteration += 1;
+        if iteration.is_multiple_of(SOAK_PROGRESS_INTERVAL) {
+            eprintln!("{} iterations, {} failures", iteration, failures_count);
+        }
+    }
+
+    panic::set_hook(default_hook);
+    eprintln!("{} iterations, {} failures", iteration, failures_count);
+    Ok(())
+}
+
+fn get_random_config(random: &mut Random) -> HeadlessConfig {
+    let mut animation = AnimationData {
+        begin_position: random.next_xyz(-10f32, 10f32),
+        end_position: random.next_xyz(-10f32, 10f32),
+        begin_rotation_quaternion: get_random_wxyz(random),
+        end_rotation_quaternion: get_random_wxyz(random),
+        begin_rotation_xyz: random.next_xyz(-360f32, 360f32),
+        end_rotation_xyz: random.next_xyz(-360f32, 360f32),
+        begin_rotation_axis: random.next_xyz(-1f32, 1f32),
+        begin_rotation_angle: random.next_f32(-360f32, 360f32),
+        end_rotation_axis: random.next_xyz(-1f32, 1f32),
+        end_rotation_angle: random.next_f32(-360f32, 360f32),
+        quaternion_interpolation_type: random.choose(
+            &get_rotation_interpolators()
+                .iter()
+                .map(|i| i.get_id().to_string())
+                .collect::<Vec<_>>(),
+        ),
+        quaternion_normalization_policy: random.choose(&[
+            QuaternionNormalizationPolicy::NormalizeOnEdit,
+            QuaternionNormalizationPolicy::NormalizeAtRun,
+            QuaternionNormalizationPolicy::Raw,
+        ]),
+        begin_euler_representation: random.choose(&[
+            EulerRepresentation::Primary,
+            EulerRepresentation::Alternative,
+        ]),
+        end_euler_representation: random.choose(&[
+            EulerRepresentation::Primary,
+            EulerRepresentation::Alternative,
+        ]),
+        euler_order: random.choose(&[
+            EulerOrder::XYZ,
+            EulerOrder::XZY,
+            EulerOrder::YXZ,
+            EulerOrder::YZX,
+            EulerOrder::ZXY,
+            EulerOrder::ZYX,
+        ]),
+        display_all_frames: random.next_bool(),
+        frames_count: 2 + random.next_index(49) as u32,
+        frame_spacing: random.choose(&[FrameSpacing::Uniform, FrameSpacing::ArcLength]),
+        animation_time: random.next_f32(0.1, 20f32) as f64,
+        waypoints: (0..random.next_index(4))
+            .map(|_| random.next_xyz(-10f32, 10f32))
+            .collect(),
+        position_interpolation_type: random.choose(&[
+            PositionInterpolationType::Linear,
+            PositionInterpolationType::CatmullRom,
+            PositionInterpolationType::Tcb,
+            PositionInterpolationType::Hermite,
+        ]),
+        catmull_rom_tension: random.next_f32(0f32, 1f32),
+        tcb_parameters: (0..random.next_index(6))
+            .map(|_| {
+                (
+                    random.next_f32(-1f32, 1f32),
+                    random.next_f32(-1f32, 1f32),
+                    random.next_f32(-1f32, 1f32),
+                )
+            })
+            .collect(),
+        begin_velocity: random.next_xyz(-10f32, 10f32),
+        end_velocity: random.next_xyz(-10f32, 10f32),
+        translation_easing: random.choose(&[
+            Easing::Linear,
+            Easing::SmoothStep,
+            Easing::EaseInOutCubic,
+            Easing::EaseOutBounce,
+        ]),
+        rotation_easing: random.choose(&[
+            Easing::Linear,
+            Easing::SmoothStep,
+            Easing::EaseInOutCubic,
+            Easing::EaseOutBounce,
+        ]),
+        time_remap_enabled: random.next_bool(),
+        time_remap: CubicBezier::new(
+            random.next_f32(0f32, 1f32),
+            random.next_f32(-1f32, 2f32),
+            random.next_f32(0f32, 1f32),
+            random.next_f32(-1f32, 2f32),
+        ),
+        time_accumulation: random.choose(&[
+            TimeAccumulation::RunningSum,
+            TimeAccumulation::AbsoluteTimestamp,
+        ]),
+        auto_slow_motion: random.next_bool(),
+        obstacles: (0..random.next_index(3))
+            .map(|_| Obstacle {
+                position: random.next_xyz(-10f32, 10f32),
+                size: random.next_xyz(0.1, 5f32),
+                ..Obstacle::new(random.choose(&[ObstacleShape::Box, ObstacleShape::Sphere]))
+            })
+            .collect(),
+        seed: random.next_u64(),
+        ..AnimationData::new()
+    };
+    if animation.quaternion_normalization_policy == QuaternionNormalizationPolicy::NormalizeOnEdit {
+        animation.normalize_quaternion_inputs();
+    }
+
+    HeadlessConfig {
+        animation,
+        angle_input: random.choose(&[
+            AngleInput::Quaternion,
+            AngleInput::Euler,
+            AngleInput::AxisAngle,
+        ]),
+    }
+}
+
+fn check_config(config: &HeadlessConfig) -> Option<String> {
+    let animation_data = &config.animation;
+    let (begin_angle, end_angle) = animation_data.get_angles(&config.angle_input);
+    let interpolator = animation_data.get_interpolator(begin_angle, end_angle);
+
+    let norm_limit = match config.angle_input {
+        AngleInput::Quaternion if !animation_data.normalize_quaternions() => animation_data
+            .get_begin_quaternion()
+            .norm()
+            .max(animation_data.get_end_quaternion().norm())
+            .max(1f32),
+        _ => 1f32,
+    } * (1f32 + NORM_TOLERANCE);
+
+    let samples = interpolator.get_samples(SOAK_SAMPLES_COUNT);
+    for (s, sample) in samples.iter().enumerate() {
+        let t = s as f32 / (SOAK_SAMPLES_COUNT - 1) as f32;
+        if let Some(issue) = get_sample_issue(sample, norm_limit) {
+            return Some(format!("{} at t = {}", issue, t));
+        }
+    }
+
+    SlowMotionProfile::analyze(&interpolator);
+    let path = samples.iter().map(|s| *s.position()).collect::<Vec<_>>();
+    PathWarnings::analyze(
+        &path,
+        &animation_data.obstacles,
+        &UpAxis::default().get_up(),
+    );
+
+    let mut animation = crate::build_animation(animation_data, &config.angle_input, &[]);
+    let step = animation_data.animation_time / SOAK_STEPS_COUNT as f64;
+    for _ in 0..SOAK_STEPS_COUNT {
+        animation.make_step(step);
+        for frame in animation
+            .get_frames(QUATERNION_METHOD_INDEX)
+            .iter()
+            .chain(animation.get_frames(EULER_METHOD_INDEX))
+        {
+            if frame.iter().any(|v| !v.is_finite()) {
+                return Some("non-finite animation frame".to_string());
+            }
+        }
+    }
+
+    None
+}
+
+fn get_sample_issue(sample: &AnimationSample, norm_limit: f32) -> Option<String> {
+    if sample.position().iter().any(|v| !v.is_finite()) {
+        return Some("non-finite position".to_string());
+    }
+    if sample.quaternion().coords.iter().any(|v| !v.is_finite()) {
+        return Some("non-finite quaternion".to_string());
+    }
+    if sample.euler().iter().any(|v| !v.is_finite()) {
+        return Some("non-finite Euler angles".to_string());
+    }
+    if sample
+        .quaternion_frame()
+        .iter()
+        .chain(sample.euler_frame().iter())
+        .any(|v| !v.is_finite())
+    {
+        return Some("non-finite frame".to_string());
+    }
+
+    let norm = sample.quaternion().coords.norm();
+    if norm > norm_limit {
+        return Some(format!(
+            "quaternion norm blow-up: {} (limit {})",
+            norm, norm_limit
+        ));
+    }
+    None
+}
+
+fn get_random_wxyz(random: &mut Random) -> (f32, f32, f32, f32) {
+    let scale = random.choose(&[1e-4, 1f32, 1f32, 1f32, 100f32]);
+    (
+        scale * random.next_f32(-1f32, 1f32),
+        scale * random.next_f32(-1f32, 1f32),
+        scale * random.next_f32(-1f32, 1f32),
+        scale * random.next_f32(-1f32, 1f32),
+    )
+}
@@ -0,0 +1,43 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use movement_interpolation::animation_data::{AngleInput, AnimationData};
+
+use crate::render_settings::RenderSettings;
+use crate::session_log::CameraState;
+use crate::DEFAULT_MSAA_SAMPLES;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StartupConfig {
+    pub width: u32,
+    pub height: u32,
+    pub msaa: u8,
+    pub viewport_split: f32,
+    pub cameras: Vec<CameraState>,
+    pub run: Option<AngleInput>,
+    pub animation: AnimationData,
+    pub render: RenderSettings,
+}
+
+impl StartupConfig {
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        toml::from_str(&std::fs::read_to_string(path)?).map_err(std::io::Error::other)
+    }
+}
+
+impl Default for StartupConfig {
+    fn default() -> Self {
+        Self {
+            width: 1600,
+            height: 1200,
+            msaa: DEFAULT_MSAA_SAMPLES,
+            viewport_split: 0.5,
+            cameras: Vec::new(),
+            run: None,
+            animation: AnimationData::new(),
+            render: RenderSettings::new(),
+        }
+    }
+}
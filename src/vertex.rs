@@ -17,3 +17,21 @@ pub struct Vertex {
 }
 
 implement_vertex!(Vertex, position, normal, color);
+
+#[derive(Debug, Clone, Copy, Getters, new)]
+pub struct InstanceData {
+    model: [[f32; 4]; 4],
+    alpha: f32,
+    discrepancy: f32,
+    tint: f32,
+}
+
+implement_vertex!(InstanceData, model, alpha, discrepancy, tint);
+
+#[derive(Debug, Clone, Copy, Getters, new)]
+pub struct ColorVertex {
+    position: [f32; 3],
+    color: [f32; 3],
+}
+
+implement_vertex!(ColorVertex, position, color);
@@ -0,0 +1,273 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+
+use chrono::Local;
+use glium::framebuffer::{DepthRenderBuffer, SimpleFrameBuffer};
+use glium::glutin::surface::WindowSurface;
+use glium::texture::{DepthFormat, RawImage2d};
+use glium::{Display, DrawParameters, Rect, Surface, Texture2d};
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::{Delay, Frame, RgbaImage};
+
+use movement_interpolation::animation::{
+    make_fixed_steps, Animation, EULER_METHOD_INDEX, QUATERNION_METHOD_INDEX,
+};
+use movement_interpolation::animation_data::{AngleInput, AnimationData, TimeAccumulation};
+
+use crate::appearance::Appearance;
+use crate::camera::Camera;
+use crate::figures::FigureRenderers;
+use crate::mesh_drawer::MeshShading;
+use crate::render_settings::{RenderSettings, ShadingMode, VideoOutput};
+use crate::scene_view::SceneView;
+use crate::{build_animation, get_frame_discrepancies, get_frame_instances, PATH_SAMPLES_COUNT};
+
+enum VideoSink {
+    PngSequence(PathBuf),
+    Ffmpeg(Child),
+    Gif(GifEncoder<BufWriter<File>>),
+}
+
+pub fn export_video(
+    display: &Display<WindowSurface>,
+    animation_data: &AnimationData,
+    render_settings: &RenderSettings,
+    angle_input: &AngleInput,
+    cameras: &[Camera],
+    appearance: &Appearance,
+    renderers: &FigureRenderers,
+) -> std::io::Result<PathBuf> {
+    let name = format!("video_{}", Local::now().format("%Y%m%d_%H%M%S"));
+    let settings = &render_settings.video;
+    let mut animation = build_animation(
+        &AnimationData {
+            display_all_frames: false,
+            time_accumulation: TimeAccumulation::RunningSum,
+            playback_speed: 1f32,
+            ..animation_data.clone()
+        },
+        angle_input,
+        &[],
+    );
+
+    let texture = Texture2d::empty(display, settings.width, settings.height).unwrap();
+    let depth_buffer =
+        DepthRenderBuffer::new(display, DepthFormat::I24, settings.width, settings.height).unwrap();
+
+    let (path, mut sink) = match settings.output {
+        VideoOutput::PngSequence => {
+            let path = PathBuf::from(name);
+            std::fs::create_dir_all(&path)?;
+            (path.clone(), VideoSink::PngSequence(path))
+        }
+        VideoOutput::Ffmpeg => {
+            let path = PathBuf::from(format!("{}.mp4", name));
+            let child = Command::new("ffmpeg")
+                .args([
+                    "-y",
+                    "-f",
+                    "rawvideo",
+                    "-pixel_format",
+                    "rgba",
+                    "-video_size",
+                ])
+                .arg(format!("{}x{}", settings.width, settings.height))
+                .arg("-framerate")
+                .arg(settings.fps.to_string())
+                .args(["-i", "-", "-vf", "vflip", "-pix_fmt", "yuv420p"])
+                .arg(&path)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()?;
+            (path, VideoSink::Ffmpeg(child))
+        }
+        VideoOutput::Gif => {
+            let path = PathBuf::from(format!("{}.gif", name));
+            let mut encoder = GifEncoder::new(BufWriter::new(File::create(&path)?));
+            encoder
+                .set_repeat(Repeat::Infinite)
+                .map_err(std::io::Error::other)?;
+            (path, VideoSink::Gif(encoder))
+        }
+    };
+
+    let time_step = 1f64 / settings.fps as f64;
+    let mut accumulator = 0f64;
+    let frames_count = (animation_data.animation_time * settings.fps as f64).ceil() as usize + 1;
+    for frame in 0..frames_count {
+        let mut target =
+            SimpleFrameBuffer::with_depth_buffer(display, &texture, &depth_buffer).unwrap();
+        draw_frame(
+            &mut target,
+            display,
+            animation.as_ref(),
+            render_settings,
+            cameras,
+            appearance,
+            renderers,
+        );
+
+        let raw: RawImage2d<u8> = texture.read();
+        match &mut sink {
+            VideoSink::PngSequence(directory) => {
+                get_image(raw)
+                    .save(directory.join(format!("frame_{:05}.png", frame)))
+                    .map_err(std::io::Error::other)?;
+            }
+            VideoSink::Ffmpeg(child) => {
+                child.stdin.as_mut().unwrap().write_all(&raw.data)?;
+            }
+            VideoSink::Gif(encoder) => {
+                let image = get_image(raw);
+                let image = image::imageops::resize(
+                    &image,
+                    ((settings.width as f32 * settings.gif_scale) as u32).max(1),
+                    ((settings.height as f32 * settings.gif_scale) as u32).max(1),
+                    image::imageops::FilterType::Triangle,
+                );
+                encoder
+                    .encode_frame(Frame::from_parts(
+                        image,
+                        0,
+                        0,
+                        Delay::from_numer_denom_ms(1000, settings.fps),
+                    ))
+                    .map_err(std::io::Error::other)?;
+            }
+        }
+
+        make_fixed_steps(animation.as_mut(), &mut accumulator, time_step);
+    }
+
+    if let VideoSink::Ffmpeg(mut child) = sink {
+        drop(child.stdin.take());
+        let status = child.wait()?;
+        if !status.success() {
+            return Err(std::io::Error::other(format!(
+                "ffmpeg exited with {}",
+                status
+            )));
+        }
+    }
+
+    Ok(path)
+}
+
+fn get_image(raw: RawImage2d<u8>) -> RgbaImage {
+    let mut image = RgbaImage::from_raw(raw.width, raw.height, raw.data.into_owned()).unwrap();
+    image::imageops::flip_vertical_in_place(&mut image);
+    image
+}
+
+fn draw_frame(
+    target: &mut SimpleFrameBuffer,
+    display: &Display<WindowSurface>,
+    animation: &dyn Animation,
+    render_settings: &RenderSettings,
+    cameras: &[Camera],
+    appearance: &Appearance,
+    renderers: &FigureRenderers,
+) {
+    let settings = &render_settings.video;
+    let left_width = settings.width / 2;
+
+    target.clear_color_and_depth(appearance.get_clear_color(), 1.0);
+
+    let quaternion_frames = animation.get_frames(QUATERNION_METHOD_INDEX);
+    let euler_frames = animation.get_frames(EULER_METHOD_INDEX);
+    let discrepancies = get_frame_discrepancies(quaternion_frames, euler_frames);
+    for (i, (frames, lighting)) in [
+        (quaternion_frames, &render_settings.quaternion_lighting),
+        (euler_frames, &render_settings.euler_lighting),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let (left, width) = if i == 0 {
+            (0, left_width)
+        } else {
+            (left_width, settings.width - left_width)
+        };
+        let camera = &cameras[i];
+        let perspective = render_settings
+            .projection
+            .get_matrix(width as f32 / settings.height as f32, *camera.distant());
+        let drawing_parameters = DrawParameters {
+            depth: glium::Depth {
+                test: glium::draw_parameters::DepthTest::IfLess,
+                write: true,
+                ..Default::default()
+            },
+            backface_culling: glium::draw_parameters::BackfaceCullingMode::CullClockwise,
+            blend: glium::Blend::alpha_blending(),
+            viewport: Some(Rect {
+                left,
+                bottom: 0,
+                width,
+                height: settings.height,
+            }),
+            ..Default::default()
+        };
+        let scene = SceneView::new(
+            &perspective,
+            camera.view(),
+            camera.get_position(),
+            &drawing_parameters,
+        );
+
+        let instances = get_frame_instances(
+            frames,
+            &discrepancies,
+            &vec![0f32; frames.len()],
+            render_settings.first_frame_alpha,
+        );
+        if render_settings.grid.shadows {
+            renderers.shadow_map.render(
+                display,
+                renderers.mesh,
+                &instances,
+                &render_settings.up_axis,
+                lighting,
+            );
+        }
+        renderers.mesh_drawer.draw_instanced(
+            target,
+            display,
+            &scene,
+            &instances,
+            &MeshShading {
+                discrepancy_shading: render_settings.shading_mode == ShadingMode::Discrepancy,
+                ..MeshShading::new(
+                    &render_settings.up_axis,
+                    lighting,
+                    &render_settings.render_mode,
+                )
+            },
+            renderers.mesh,
+        );
+
+        if render_settings.display_path {
+            renderers.path_drawer.draw(
+                target,
+                display,
+                &scene,
+                &animation.get_path(i, PATH_SAMPLES_COUNT),
+                [1f32, 1f32, 0f32],
+            );
+        }
+
+        renderers.infinite_grid_drawer.draw(
+            target,
+            &SceneView {
+                view: camera.grid_view(),
+                ..scene
+            },
+            &render_settings.projection,
+            &render_settings.grid,
+            renderers.shadow_map,
+        );
+    }
+}
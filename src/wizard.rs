@@ -0,0 +1,165 @@
+use egui::{Align2, Button, Context, RichText};
+
+use movement_interpolation::animation_data::{AngleInput, AnimationData};
+use movement_interpolation::rotation_interpolator::SPHERICAL;
+
+use crate::camera::CameraPreset;
+use crate::config_dir::{create_config_file, get_config_path};
+use crate::presets::get_presets;
+use crate::render_settings::{RenderSettings, ShadingMode};
+
+const WIZARD_MARKER_FILE: &str = "wizard_done";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WizardScenario {
+    BasicComparison,
+    GimbalLock,
+    ManyFrames,
+    Custom,
+}
+
+pub struct WizardSetup {
+    pub animation_data: AnimationData,
+    pub viewport_split: f32,
+    pub camera_preset: Option<CameraPreset>,
+    pub run: Option<AngleInput>,
+}
+
+impl WizardScenario {
+    pub fn get_all() -> [WizardScenario; 4] {
+        [
+            WizardScenario::BasicComparison,
+            WizardScenario::GimbalLock,
+            WizardScenario::ManyFrames,
+            WizardScenario::Custom,
+        ]
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            WizardScenario::BasicComparison => "Basic SLERP vs Euler",
+            WizardScenario::GimbalLock => "Gimbal lock",
+            WizardScenario::ManyFrames => "Many frames",
+            WizardScenario::Custom => "Custom",
+        }
+    }
+
+    pub fn get_description(&self) -> &'static str {
+        match self {
+            WizardScenario::BasicComparison => {
+                "Side by side comparison of spherical quaternion interpolation and Euler angle interpolation for a combined move."
+            }
+            WizardScenario::GimbalLock => {
+                "Euler angles passing through pitch = 90°, with gimbal lock tinting and the recovered angles plot."
+            }
+            WizardScenario::ManyFrames => {
+                "Dense frame trail shaded by the difference between both methods."
+            }
+            WizardScenario::Custom => "Empty scene with default settings.",
+        }
+    }
+
+    pub fn get_setup(&self) -> WizardSetup {
+        let preset = |name: &str| {
+            get_presets()
+                .into_iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, data)| data)
+                .unwrap()
+        };
+
+        match self {
+            WizardScenario::BasicComparison => WizardSetup {
+                animation_data: AnimationData {
+                    display_all_frames: true,
+                    quaternion_interpolation_type: SPHERICAL.to_string(),
+                    ..preset("Combined motion")
+                },
+                viewport_split: 0.5,
+                camera_preset: Some(CameraPreset::Isometric),
+                run: Some(AngleInput::Euler),
+            },
+            WizardScenario::GimbalLock => WizardSetup {
+                animation_data: preset("Gimbal lock"),
+                viewport_split: 0.4,
+                camera_preset: Some(CameraPreset::Front),
+                run: Some(AngleInput::Euler),
+            },
+            WizardScenario::ManyFrames => WizardSetup {
+                animation_data: AnimationData {
+                    display_all_frames: true,
+                    frames_count: 40,
+                    quaternion_interpolation_type: SPHERICAL.to_string(),
+                    ..preset("Combined motion")
+                },
+                viewport_split: 0.5,
+                camera_preset: Some(CameraPreset::Isometric),
+                run: Some(AngleInput::Euler),
+            },
+            WizardScenario::Custom => WizardSetup {
+                animation_data: AnimationData::new(),
+                viewport_split: 0.5,
+                camera_preset: None,
+                run: None,
+            },
+        }
+    }
+
+    pub fn apply_view_settings(&self, render_settings: &mut RenderSettings) {
+        match self {
+            WizardScenario::GimbalLock => {
+                render_settings.tint_gimbal_lock = true;
+                render_settings.display_euler_angles_plot = true;
+            }
+            WizardScenario::ManyFrames => {
+                render_settings.first_frame_alpha = 0.1;
+                render_settings.shading_mode = ShadingMode::Discrepancy;
+            }
+            WizardScenario::BasicComparison | WizardScenario::Custom => {}
+        }
+    }
+}
+
+pub fn build_wizard(
+    egui_ctx: &Context,
+    open: &mut bool,
+    render_settings: &mut RenderSettings,
+) -> Option<WizardSetup> {
+    let mut setup = None;
+    let mut skipped = false;
+
+    egui::Window::new("What do you want to demonstrate?")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(Align2::CENTER_CENTER, [0f32, 0f32])
+        .show(egui_ctx, |ui| {
+            for scenario in WizardScenario::get_all() {
+                ui.group(|ui| {
+                    ui.set_width(360f32);
+                    if ui
+                        .add(Button::new(RichText::new(scenario.get_name()).size(15f32)))
+                        .clicked()
+                    {
+                        scenario.apply_view_settings(render_settings);
+                        setup = Some(scenario.get_setup());
+                    }
+                    ui.label(scenario.get_description());
+                });
+            }
+            ui.separator();
+            skipped = ui.button("Skip").clicked();
+        });
+
+    if setup.is_some() || skipped {
+        *open = false;
+        let marker = get_config_path(WIZARD_MARKER_FILE).and_then(|p| create_config_file(&p));
+        if let Err(error) = marker {
+            eprintln!("Wizard marker write failed: {}", error);
+        }
+    }
+    setup
+}
+
+pub fn is_wizard_done() -> bool {
+    get_config_path(WIZARD_MARKER_FILE).is_ok_and(|p| p.exists())
+}